@@ -0,0 +1,164 @@
+use leptos::prelude::*;
+use std::collections::HashMap;
+use thaw::*;
+
+use crate::components::{ChartSeries, LineChart, ModelSelect};
+use crate::models::ModelPricing;
+use crate::utils::agent::{simulate_agent, AgentPlan};
+
+fn format_dollars(value: f64) -> String {
+    format!("${:.4}", value)
+}
+
+#[component]
+pub fn AgentSimulator(
+    model_prices: ReadSignal<HashMap<String, ModelPricing>>,
+    selected_model: RwSignal<String>,
+) -> impl IntoView {
+    let initial_context_tokens = RwSignal::new(3_000_usize);
+    let thought_tokens = RwSignal::new(300_usize);
+    let action_tokens = RwSignal::new(150_usize);
+    let tool_result_tokens = RwSignal::new(1_500_usize);
+    let min_steps = RwSignal::new(3_usize);
+    let max_steps = RwSignal::new(30_usize);
+    let stop_percent = RwSignal::new(15.0_f64);
+    let use_cache = RwSignal::new(true);
+
+    let estimate = Memo::new(move |_| {
+        let prices = model_prices.get();
+        let pricing = prices.get(&selected_model.get())?;
+        let plan = AgentPlan {
+            initial_context_tokens: initial_context_tokens.get(),
+            thought_tokens: thought_tokens.get(),
+            action_tokens: action_tokens.get(),
+            tool_result_tokens: tool_result_tokens.get(),
+            min_steps: min_steps.get(),
+            max_steps: max_steps.get(),
+            stop_probability: stop_percent.get() / 100.0,
+            use_cache: use_cache.get(),
+        };
+        Some((
+            simulate_agent(&plan, pricing),
+            pricing.supports_reasoning.unwrap_or(false),
+        ))
+    });
+
+    let x_labels = Signal::derive(move || {
+        estimate
+            .get()
+            .map(|(est, _)| est.steps.iter().map(|s| s.step.to_string()).collect())
+            .unwrap_or_default()
+    });
+    let cost_series = Signal::derive(move || {
+        let steps = estimate.get().map(|(est, _)| est.steps).unwrap_or_default();
+        vec![
+            ChartSeries {
+                label: "Cumulative cost if the task stops here".to_string(),
+                color: "#3b82f6",
+                values: steps.iter().map(|s| s.cumulative_cost).collect(),
+            },
+            ChartSeries {
+                label: "Cost of this step".to_string(),
+                color: "#f59e0b",
+                values: steps.iter().map(|s| s.cost).collect(),
+            },
+        ]
+    });
+
+    view! {
+        <div class="grid grid-cols-3 gap-4">
+            <Card class="w-full">
+                <div class="p-5">
+                    <h3 class="text-xl font-semibold mb-4">"Agent Task"</h3>
+                    <Space vertical=true>
+                        <ModelSelect model_prices=model_prices selected=selected_model />
+                        <Grid cols=2 x_gap=12 y_gap=8>
+                            <GridItem>
+                                <div class="text-sm text-gray-600">"Initial context tokens"</div>
+                                <SpinButton<usize> value=initial_context_tokens step_page=500 min=0 />
+                            </GridItem>
+                            <GridItem>
+                                <div class="text-sm text-gray-600">"Thought tokens / step"</div>
+                                <SpinButton<usize> value=thought_tokens step_page=100 min=0 />
+                            </GridItem>
+                            <GridItem>
+                                <div class="text-sm text-gray-600">"Tool call tokens / step"</div>
+                                <SpinButton<usize> value=action_tokens step_page=50 min=0 />
+                            </GridItem>
+                            <GridItem>
+                                <div class="text-sm text-gray-600">"Tool result tokens / step"</div>
+                                <SpinButton<usize> value=tool_result_tokens step_page=500 min=0 />
+                            </GridItem>
+                            <GridItem>
+                                <div class="text-sm text-gray-600">"Minimum steps"</div>
+                                <SpinButton<usize> value=min_steps step_page=1 min=1 />
+                            </GridItem>
+                            <GridItem>
+                                <div class="text-sm text-gray-600">"Maximum steps"</div>
+                                <SpinButton<usize> value=max_steps step_page=5 min=1 max=500 />
+                            </GridItem>
+                            <GridItem>
+                                <div class="text-sm text-gray-600">"Stop chance per step (%)"</div>
+                                <SpinButton<f64> value=stop_percent step_page=5.0 min=0.0 max=100.0 />
+                            </GridItem>
+                        </Grid>
+                        <Switch checked=use_cache label="Apply prompt caching to the growing transcript" />
+                        {move || estimate.get().map(|(_, reasoning)| view! {
+                            <p class="text-xs text-gray-500">
+                                {if reasoning {
+                                    "Reasoning model: thoughts are billed at the reasoning price and not re-sent."
+                                } else {
+                                    "Thoughts are billed as output and stay in the transcript."
+                                }}
+                            </p>
+                        })}
+                    </Space>
+                </div>
+            </Card>
+
+            <Card class="w-full col-span-2">
+                <div class="p-5">
+                    <h3 class="text-xl font-semibold mb-4">"Cost per Task"</h3>
+                    {move || estimate.get().map(|(est, _)| view! {
+                        <Grid cols=4 x_gap=12>
+                            <GridItem>
+                                <Card class="bg-gray-100">
+                                    <div class="p-3 text-center">
+                                        <div class="text-xs text-gray-600 mb-1">"Expected steps"</div>
+                                        <div class="font-semibold">{format!("{:.1}", est.expected_steps)}</div>
+                                    </div>
+                                </Card>
+                            </GridItem>
+                            <GridItem>
+                                <Card class="bg-gray-100">
+                                    <div class="p-3 text-center">
+                                        <div class="text-xs text-gray-600 mb-1">"Expected cost"</div>
+                                        <div class="font-semibold text-blue-500">{format!("${:.4}", est.expected_cost)}</div>
+                                    </div>
+                                </Card>
+                            </GridItem>
+                            <GridItem>
+                                <Card class="bg-gray-100">
+                                    <div class="p-3 text-center">
+                                        <div class="text-xs text-gray-600 mb-1">{format!("p95 cost ({} steps)", est.p95_steps)}</div>
+                                        <div class="font-semibold">{format!("${:.4}", est.p95_cost)}</div>
+                                    </div>
+                                </Card>
+                            </GridItem>
+                            <GridItem>
+                                <Card class="bg-gray-100">
+                                    <div class="p-3 text-center">
+                                        <div class="text-xs text-gray-600 mb-1">"Worst case"</div>
+                                        <div class="font-semibold">{format!("${:.4}", est.max_cost)}</div>
+                                    </div>
+                                </Card>
+                            </GridItem>
+                        </Grid>
+                    })}
+                    <h4 class="font-semibold mt-4 mb-2">"Cost by step"</h4>
+                    <LineChart series=cost_series x_labels=x_labels y_format=format_dollars />
+                </div>
+            </Card>
+        </div>
+    }
+}
@@ -0,0 +1,72 @@
+use leptos::prelude::*;
+
+use crate::components::chart::{
+    x_axis, y_axis, y_of, ChartLegend, ChartSeries, HEIGHT, PAD_LEFT, PLOT_WIDTH, WIDTH,
+};
+
+/// Fraction of each slot left empty between bars.
+const BAR_GAP: f64 = 0.2;
+
+/// Minimal SVG stacked bar chart. Each series contributes one segment per
+/// x label, stacked in series order from the x axis up.
+#[component]
+pub fn StackedBarChart(
+    #[prop(into)] series: Signal<Vec<ChartSeries>>,
+    #[prop(into)] x_labels: Signal<Vec<String>>,
+    y_format: fn(f64) -> String,
+) -> impl IntoView {
+    let y_max = move || {
+        let n = x_labels.with(Vec::len);
+        series
+            .with(|series| {
+                (0..n)
+                    .map(|i| {
+                        series
+                            .iter()
+                            .map(|s| s.values.get(i).copied().unwrap_or(0.0))
+                            .sum::<f64>()
+                    })
+                    .fold(0.0_f64, f64::max)
+            })
+            .max(f64::EPSILON)
+    };
+    let slot = move |n: usize| PLOT_WIDTH / n.max(1) as f64;
+
+    view! {
+        <div>
+            <svg viewBox=format!("0 0 {} {}", WIDTH, HEIGHT) class="w-full">
+                {move || y_axis(y_max(), y_format)}
+                {move || x_axis(x_labels.get(), move |i, n| PAD_LEFT + slot(n) * (i as f64 + 0.5))}
+                {move || {
+                    let max = y_max();
+                    let n = x_labels.with(Vec::len);
+                    let width = slot(n) * (1.0 - BAR_GAP);
+                    let mut base = vec![0.0_f64; n];
+                    series.get().into_iter().flat_map(|s| {
+                        (0..n).filter_map(|i| {
+                            let value = s.values.get(i).copied().unwrap_or(0.0);
+                            if value <= 0.0 {
+                                return None;
+                            }
+                            let top = y_of(base[i] + value, max);
+                            let bottom = y_of(base[i], max);
+                            base[i] += value;
+                            Some(view! {
+                                <rect
+                                    x=PAD_LEFT + slot(n) * i as f64 + slot(n) * BAR_GAP / 2.0
+                                    y=top
+                                    width=width
+                                    height=bottom - top
+                                    fill=s.color
+                                >
+                                    <title>{format!("{}: {}", s.label, y_format(value))}</title>
+                                </rect>
+                            })
+                        }).collect::<Vec<_>>()
+                    }).collect_view()
+                }}
+            </svg>
+            <ChartLegend series=series />
+        </div>
+    }
+}
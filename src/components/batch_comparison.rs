@@ -0,0 +1,103 @@
+use leptos::prelude::*;
+use std::collections::HashMap;
+use thaw::*;
+
+use crate::models::ModelPricing;
+use crate::utils::batch::compare_batch_modes;
+use crate::utils::estimate_tokens_for_model;
+use crate::utils::projection::TrafficPlan;
+
+fn format_optional(value: Option<f64>, decimals: usize) -> String {
+    value
+        .map(|v| format!("${:.*}", decimals, v))
+        .unwrap_or_else(|| "N/A".to_string())
+}
+
+/// Real-time vs batch vs batch+cache cost of the current request at the
+/// projected monthly volume.
+#[component]
+pub fn BatchComparison(
+    model_prices: ReadSignal<HashMap<String, ModelPricing>>,
+    selected_model: ReadSignal<String>,
+    input_text: ReadSignal<String>,
+    output_text: ReadSignal<String>,
+    traffic_plan: RwSignal<TrafficPlan>,
+) -> impl IntoView {
+    let cached_percent = RwSignal::new(50.0_f64);
+
+    let modes = Memo::new(move |_| {
+        let model = selected_model.get();
+        let input_tokens = estimate_tokens_for_model(&input_text.get(), &model);
+        let output_tokens = estimate_tokens_for_model(&output_text.get(), &model);
+        let requests_per_month = traffic_plan.with(|plan| plan.requests_per_month());
+        model_prices.with(|prices| {
+            prices.get(&model).map(|pricing| {
+                compare_batch_modes(
+                    pricing,
+                    input_tokens,
+                    output_tokens,
+                    cached_percent.get() / 100.0,
+                    requests_per_month,
+                )
+            })
+        })
+    });
+
+    view! {
+        <Card class="w-full">
+            <div class="p-5">
+                <h3 class="text-xl font-semibold mb-4">"Batch vs Real-time"</h3>
+                <div class="grid grid-cols-3 gap-4">
+                    <div>
+                        <Space vertical=true>
+                            <div class="text-sm">
+                                <div class="text-gray-600 mb-1">"Cached share of input (%)"</div>
+                                <SpinButton<f64> value=cached_percent step_page=10.0 min=0.0 max=100.0 />
+                            </div>
+                            <div class="text-xs text-gray-500">
+                                "Batch cache reads assume the batch discount applies on top of the cache read price. Cache writes are not counted."
+                            </div>
+                        </Space>
+                    </div>
+                    <div class="col-span-2">
+                        {move || match modes.get() {
+                            None => view! {
+                                <div class="text-sm text-gray-500">"Select a model to compare batch pricing."</div>
+                            }.into_any(),
+                            Some(modes) => {
+                                let requests_per_month = traffic_plan.with(|plan| plan.requests_per_month());
+                                view! {
+                                    <div class="text-sm text-gray-600 mb-2">
+                                        {format!("At {:.0} requests per month", requests_per_month)}
+                                    </div>
+                                    <table class="w-full border-collapse text-sm">
+                                        <thead>
+                                            <tr class="bg-gray-200">
+                                                <th class="border border-gray-300 px-3 py-2 text-left font-semibold">"Mode"</th>
+                                                <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Cost / Request"</th>
+                                                <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Monthly Cost"</th>
+                                                <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Savings"</th>
+                                            </tr>
+                                        </thead>
+                                        <tbody>
+                                            {modes.into_iter().map(|mode| view! {
+                                                <tr class="hover:bg-gray-50">
+                                                    <td class="border border-gray-300 px-3 py-2">{mode.label}</td>
+                                                    <td class="border border-gray-300 px-3 py-2 text-right">{format_optional(mode.cost_per_request, 6)}</td>
+                                                    <td class="border border-gray-300 px-3 py-2 text-right">{format_optional(mode.monthly_cost, 2)}</td>
+                                                    <td class="border border-gray-300 px-3 py-2 text-right">
+                                                        {mode.savings.map(|s| format!("{:.1}%", s * 100.0)).unwrap_or_else(|| "N/A".to_string())}
+                                                    </td>
+                                                </tr>
+                                            }).collect_view()}
+                                        </tbody>
+                                    </table>
+                                }.into_any()
+                            }
+                        }}
+                    </div>
+                </div>
+            </div>
+        </Card>
+    }
+}
@@ -0,0 +1,150 @@
+use leptos::prelude::*;
+use std::collections::HashMap;
+use thaw::*;
+
+use crate::models::ModelPricing;
+use crate::utils::budget::solve_budget;
+use crate::utils::date::today;
+use crate::utils::projection::TrafficPlan;
+
+const MAX_ROWS: usize = 15;
+
+fn format_requests(requests: Option<f64>) -> String {
+    requests
+        .map(|r| format!("{:.0}", r))
+        .unwrap_or_else(|| "Unlimited".to_string())
+}
+
+#[component]
+pub fn BudgetSolver(
+    model_prices: ReadSignal<HashMap<String, ModelPricing>>,
+    selected_model: ReadSignal<String>,
+    set_selected_model: WriteSignal<String>,
+    input_text: ReadSignal<String>,
+    output_text: ReadSignal<String>,
+    traffic_plan: RwSignal<TrafficPlan>,
+) -> impl IntoView {
+    let monthly_budget = RwSignal::new(100.0_f64);
+    let today = today();
+
+    let rows = Memo::new(move |_| {
+        let requests_per_month = traffic_plan.with(|plan| plan.requests_per_month());
+        model_prices.with(|prices| {
+            solve_budget(
+                prices,
+                &input_text.get(),
+                &output_text.get(),
+                monthly_budget.get(),
+                requests_per_month,
+                &today,
+            )
+        })
+    });
+
+    view! {
+        <Card class="w-full">
+            <div class="p-5">
+                <h3 class="text-xl font-semibold mb-4">"Budget"</h3>
+                <div class="grid grid-cols-3 gap-4">
+                    <div>
+                        <Space vertical=true>
+                            <div class="text-sm">
+                                <div class="text-gray-600 mb-1">"Monthly budget ($)"</div>
+                                <SpinButton<f64> value=monthly_budget step_page=50.0 min=0.0 />
+                            </div>
+                            {move || {
+                                let model = selected_model.get();
+                                let budget = monthly_budget.get();
+                                let days = traffic_plan.with(|plan| plan.days_per_month);
+                                let requests_per_month = traffic_plan.with(|plan| plan.requests_per_month());
+                                rows.get().into_iter().find(|row| row.model == model).map(|row| {
+                                    let per_day = row.affordable_requests
+                                        .filter(|_| days > 0.0)
+                                        .map(|r| (r / days).floor());
+                                    let within = row.within_budget(budget);
+                                    view! {
+                                        <Card class="bg-gray-50">
+                                            <div class="p-4 text-sm space-y-2">
+                                                <div class="font-semibold font-mono">{row.model.clone()}</div>
+                                                <div class="flex justify-between">
+                                                    <span class="text-gray-600">"Affordable per month:"</span>
+                                                    <strong>{format_requests(row.affordable_requests)}</strong>
+                                                </div>
+                                                <div class="flex justify-between">
+                                                    <span class="text-gray-600">"Affordable per working day:"</span>
+                                                    <strong>{format_requests(per_day)}</strong>
+                                                </div>
+                                                <div class="flex justify-between">
+                                                    <span class="text-gray-600">
+                                                        {format!("{:.0} requests/month cost:", requests_per_month)}
+                                                    </span>
+                                                    <strong class=if within { "text-green-600" } else { "text-red-600" }>
+                                                        {format!("${:.2}", row.monthly_cost)}
+                                                    </strong>
+                                                </div>
+                                            </div>
+                                        </Card>
+                                    }
+                                })
+                            }}
+                        </Space>
+                    </div>
+                    <div class="col-span-2">
+                        {move || {
+                            let budget = monthly_budget.get();
+                            let rows = rows.get();
+                            let total = rows.len();
+                            let affordable: Vec<_> = rows.into_iter().filter(|row| row.within_budget(budget)).collect();
+                            view! {
+                                <div class="text-sm text-gray-600 mb-2">
+                                    {format!(
+                                        "{} of {} models stay under ${:.2}/month at your projected volume",
+                                        affordable.len(),
+                                        total,
+                                        budget,
+                                    )}
+                                </div>
+                                <table class="w-full border-collapse text-sm">
+                                    <thead>
+                                        <tr class="bg-gray-200">
+                                            <th class="border border-gray-300 px-3 py-2 text-left font-semibold">"Model"</th>
+                                            <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Cost / Request"</th>
+                                            <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Monthly Cost"</th>
+                                            <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Headroom"</th>
+                                            <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Affordable / Month"</th>
+                                            <th class="border border-gray-300 px-3 py-2"></th>
+                                        </tr>
+                                    </thead>
+                                    <tbody>
+                                        {affordable.into_iter().take(MAX_ROWS).map(|row| {
+                                            let model = row.model.clone();
+                                            let model_for_check = row.model.clone();
+                                            view! {
+                                                <tr class="hover:bg-gray-50">
+                                                    <td class="border border-gray-300 px-3 py-2 font-mono">{row.model}</td>
+                                                    <td class="border border-gray-300 px-3 py-2 text-right">{format!("${:.6}", row.cost_per_request)}</td>
+                                                    <td class="border border-gray-300 px-3 py-2 text-right">{format!("${:.2}", row.monthly_cost)}</td>
+                                                    <td class="border border-gray-300 px-3 py-2 text-right">{format!("${:.2}", budget - row.monthly_cost)}</td>
+                                                    <td class="border border-gray-300 px-3 py-2 text-right">{format_requests(row.affordable_requests)}</td>
+                                                    <td class="border border-gray-300 px-3 py-2 text-center">
+                                                        <Button
+                                                            size=ButtonSize::Small
+                                                            disabled=Signal::derive(move || selected_model.get() == model_for_check)
+                                                            on_click=move |_| set_selected_model.set(model.clone())
+                                                        >
+                                                            "Use"
+                                                        </Button>
+                                                    </td>
+                                                </tr>
+                                            }
+                                        }).collect_view()}
+                                    </tbody>
+                                </table>
+                            }
+                        }}
+                    </div>
+                </div>
+            </div>
+        </Card>
+    }
+}
@@ -0,0 +1,192 @@
+use leptos::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+use thaw::*;
+
+use crate::models::ModelPricing;
+use crate::utils::attribution::{
+    available_fields, build_chargeback, chargeback_csv, AttributionRule,
+};
+use crate::utils::download_file;
+use crate::utils::usage::{price_records, UsageRecord};
+
+#[derive(Debug, Clone, Copy)]
+struct RuleRow {
+    id: usize,
+    field: RwSignal<String>,
+    pattern: RwSignal<String>,
+    cost_center: RwSignal<String>,
+}
+
+impl RuleRow {
+    fn new(id: usize, field: &str) -> Self {
+        Self {
+            id,
+            field: RwSignal::new(field.to_string()),
+            pattern: RwSignal::new(String::new()),
+            cost_center: RwSignal::new(String::new()),
+        }
+    }
+
+    fn to_rule(self) -> AttributionRule {
+        AttributionRule {
+            field: self.field.get(),
+            pattern: self.pattern.get(),
+            cost_center: self.cost_center.get(),
+        }
+    }
+}
+
+/// Attribution rules that map request fields and metadata to cost centers,
+/// and the resulting chargeback per cost center and month.
+#[component]
+pub fn ChargebackReport(
+    model_prices: ReadSignal<HashMap<String, ModelPricing>>,
+    #[prop(into)] records: Signal<Vec<UsageRecord>>,
+) -> impl IntoView {
+    let (next_id, set_next_id) = signal(1_usize);
+    let rules = RwSignal::new(vec![RuleRow::new(0, "team")]);
+    let (export_error, set_export_error) = signal(Option::<String>::None);
+
+    let fields = Memo::new(move |_| records.with(|records| available_fields(records)));
+    let report = Memo::new(move |_| {
+        let rules: Vec<AttributionRule> = rules.get().into_iter().map(RuleRow::to_rule).collect();
+        model_prices.with(|prices| {
+            records
+                .with(|records| build_chargeback(records, &price_records(records, prices), &rules))
+        })
+    });
+
+    let add_rule = move |_| {
+        let id = next_id.get_untracked();
+        set_next_id.set(id + 1);
+        rules.update(|list| list.push(RuleRow::new(id, "team")));
+    };
+
+    let export_csv = move |_| {
+        let result = chargeback_csv(&report.get_untracked()).and_then(|csv| {
+            download_file("chargeback.csv", "text/csv", csv.as_bytes())
+                .map_err(|e| format!("{:?}", e))
+        });
+        set_export_error.set(result.err().map(|e| format!("Failed to export CSV: {}", e)));
+    };
+
+    view! {
+        <Card class="w-full">
+            <div class="p-5">
+                <div class="flex justify-between items-center mb-4">
+                    <h3 class="text-xl font-semibold">"Chargeback"</h3>
+                    <div class="flex gap-2">
+                        <Button on_click=add_rule>"Add rule"</Button>
+                        <Button
+                            disabled=Signal::derive(move || report.with(Vec::is_empty))
+                            on_click=export_csv
+                        >
+                            "Export CSV"
+                        </Button>
+                    </div>
+                </div>
+                <div class="text-sm text-gray-600 mb-3">
+                    "Rules are tried in order and the first match charges the request. Patterns are case-insensitive and accept * wildcards; leave the pattern empty to match any value, and the cost center empty to charge the value itself."
+                </div>
+                <div class="space-y-2 mb-4">
+                    <For
+                        each=move || rules.get()
+                        key=|row| row.id
+                        let:row
+                    >
+                        <div class="grid grid-cols-12 gap-3 items-center text-sm">
+                            <div class="col-span-3">
+                                <select
+                                    class="w-full p-2 border border-gray-300 rounded text-sm"
+                                    on:change=move |ev| row.field.set(event_target_value(&ev))
+                                >
+                                    {move || fields.get().into_iter().map(|field| {
+                                        let selected = field == row.field.get_untracked();
+                                        let label = field.clone();
+                                        view! { <option value=field selected=selected>{label}</option> }
+                                    }).collect_view()}
+                                </select>
+                            </div>
+                            <div class="col-span-4">
+                                <Input value=row.pattern placeholder="Any value" />
+                            </div>
+                            <div class="col-span-4">
+                                <Input value=row.cost_center placeholder="Same as value" />
+                            </div>
+                            <div class="col-span-1 text-right">
+                                <Button
+                                    size=ButtonSize::Small
+                                    on_click=move |_| rules.update(|list| list.retain(|r| r.id != row.id))
+                                >
+                                    "Remove"
+                                </Button>
+                            </div>
+                        </div>
+                    </For>
+                </div>
+                {move || export_error.get().map(|err| view! {
+                    <MessageBar intent=MessageBarIntent::Error>
+                        <MessageBarBody>{err}</MessageBarBody>
+                    </MessageBar>
+                })}
+                {move || {
+                    let rows = report.get();
+                    let mut months: Vec<String> = rows.iter().map(|row| row.month.clone()).collect();
+                    months.sort();
+                    months.dedup();
+                    let mut centers: BTreeMap<String, BTreeMap<String, f64>> = BTreeMap::new();
+                    for row in &rows {
+                        *centers
+                            .entry(row.cost_center.clone())
+                            .or_default()
+                            .entry(row.month.clone())
+                            .or_default() += row.cost;
+                    }
+                    let month_totals: Vec<f64> = months
+                        .iter()
+                        .map(|month| rows.iter().filter(|row| &row.month == month).map(|row| row.cost).sum())
+                        .collect();
+                    let total: f64 = rows.iter().map(|row| row.cost).sum();
+                    view! {
+                        <div class="overflow-x-auto">
+                            <table class="w-full text-sm">
+                                <thead>
+                                    <tr class="border-b border-gray-300 text-left">
+                                        <th class="py-2 pr-4">"Cost center"</th>
+                                        {months.iter().map(|month| view! {
+                                            <th class="py-2 pr-4 text-right">{month.clone()}</th>
+                                        }).collect_view()}
+                                        <th class="py-2 text-right">"Total"</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    {centers.into_iter().map(|(center, by_month)| {
+                                        let center_total: f64 = by_month.values().sum();
+                                        view! {
+                                            <tr class="border-b border-gray-200">
+                                                <td class="py-2 pr-4 font-mono">{center}</td>
+                                                {months.iter().map(|month| view! {
+                                                    <td class="py-2 pr-4 text-right">
+                                                        {by_month.get(month).map(|cost| format!("${:.2}", cost)).unwrap_or_default()}
+                                                    </td>
+                                                }).collect_view()}
+                                                <td class="py-2 text-right font-semibold">{format!("${:.2}", center_total)}</td>
+                                            </tr>
+                                        }
+                                    }).collect_view()}
+                                    <tr class="font-semibold">
+                                        <td class="py-2 pr-4">"Total"</td>
+                                        {month_totals.into_iter().map(|cost| view! {
+                                            <td class="py-2 pr-4 text-right">{format!("${:.2}", cost)}</td>
+                                        }).collect_view()}
+                                        <td class="py-2 text-right text-blue-500">{format!("${:.2}", total)}</td>
+                                    </tr>
+                                </tbody>
+                            </table>
+                        </div>
+                    }
+                }}
+            </div>
+        </Card>
+    }
+}
@@ -0,0 +1,75 @@
+use leptos::prelude::*;
+
+pub const WIDTH: f64 = 640.0;
+pub const HEIGHT: f64 = 260.0;
+pub const PAD_LEFT: f64 = 70.0;
+pub const PAD_RIGHT: f64 = 16.0;
+pub const PAD_TOP: f64 = 12.0;
+pub const PAD_BOTTOM: f64 = 28.0;
+pub const PLOT_WIDTH: f64 = WIDTH - PAD_LEFT - PAD_RIGHT;
+pub const PLOT_HEIGHT: f64 = HEIGHT - PAD_TOP - PAD_BOTTOM;
+const Y_TICKS: usize = 4;
+const MAX_X_LABELS: usize = 8;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartSeries {
+    pub label: String,
+    pub color: &'static str,
+    pub values: Vec<f64>,
+}
+
+/// Vertical position of `value` on a y axis running from zero to `max`.
+pub fn y_of(value: f64, max: f64) -> f64 {
+    PAD_TOP + PLOT_HEIGHT * (1.0 - value / max)
+}
+
+/// Horizontal grid lines with their formatted values, from zero to `max`.
+pub fn y_axis(max: f64, y_format: fn(f64) -> String) -> impl IntoView {
+    (0..=Y_TICKS)
+        .map(|tick| {
+            let value = max * tick as f64 / Y_TICKS as f64;
+            let y = y_of(value, max);
+            view! {
+                <line x1=PAD_LEFT y1=y x2=WIDTH - PAD_RIGHT y2=y stroke="#e5e7eb" />
+                <text x=PAD_LEFT - 6.0 y=y + 4.0 text-anchor="end" font-size="11" fill="#6b7280">
+                    {y_format(value)}
+                </text>
+            }
+        })
+        .collect_view()
+}
+
+/// Labels under the x axis, thinned to at most `MAX_X_LABELS` but always
+/// keeping the last one. `x_of` maps a label's index and the label count to
+/// its horizontal position.
+pub fn x_axis(labels: Vec<String>, x_of: impl Fn(usize, usize) -> f64) -> impl IntoView {
+    let n = labels.len();
+    let every = n.div_ceil(MAX_X_LABELS).max(1);
+    labels
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| i % every == 0 || *i + 1 == n)
+        .map(|(i, label)| {
+            view! {
+                <text x=x_of(i, n) y=HEIGHT - 8.0 text-anchor="middle" font-size="11" fill="#6b7280">
+                    {label}
+                </text>
+            }
+        })
+        .collect_view()
+}
+
+/// Colour key listing every series under a chart.
+#[component]
+pub fn ChartLegend(#[prop(into)] series: Signal<Vec<ChartSeries>>) -> impl IntoView {
+    view! {
+        <div class="flex flex-wrap justify-center gap-4 text-xs mt-1">
+            {move || series.get().into_iter().map(|s| view! {
+                <span class="flex items-center gap-1">
+                    <span class="inline-block w-3 h-3 rounded" style=format!("background-color: {}", s.color)></span>
+                    {s.label}
+                </span>
+            }).collect_view()}
+        </div>
+    }
+}
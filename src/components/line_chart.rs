@@ -0,0 +1,53 @@
+use leptos::prelude::*;
+
+use crate::components::chart::{
+    x_axis, y_axis, y_of, ChartLegend, ChartSeries, HEIGHT, PAD_LEFT, PLOT_WIDTH, WIDTH,
+};
+
+/// Minimal SVG line chart. Every series is plotted against the shared
+/// `x_labels`, with the y axis starting at zero.
+#[component]
+pub fn LineChart(
+    #[prop(into)] series: Signal<Vec<ChartSeries>>,
+    #[prop(into)] x_labels: Signal<Vec<String>>,
+    y_format: fn(f64) -> String,
+) -> impl IntoView {
+    let y_max = move || {
+        series
+            .get()
+            .iter()
+            .flat_map(|s| s.values.iter().copied())
+            .fold(0.0_f64, f64::max)
+            .max(f64::EPSILON)
+    };
+    let x_of = move |i: usize, n: usize| {
+        if n <= 1 {
+            PAD_LEFT + PLOT_WIDTH / 2.0
+        } else {
+            PAD_LEFT + PLOT_WIDTH * i as f64 / (n - 1) as f64
+        }
+    };
+
+    view! {
+        <div>
+            <svg viewBox=format!("0 0 {} {}", WIDTH, HEIGHT) class="w-full">
+                {move || y_axis(y_max(), y_format)}
+                {move || x_axis(x_labels.get(), x_of)}
+                {move || {
+                    let max = y_max();
+                    series.get().into_iter().map(|s| {
+                        let n = s.values.len();
+                        let points = s.values.iter().enumerate()
+                            .map(|(i, v)| format!("{:.1},{:.1}", x_of(i, n), y_of(*v, max)))
+                            .collect::<Vec<_>>()
+                            .join(" ");
+                        view! {
+                            <polyline points=points fill="none" stroke=s.color stroke-width="2" />
+                        }
+                    }).collect_view()
+                }}
+            </svg>
+            <ChartLegend series=series />
+        </div>
+    }
+}
@@ -0,0 +1,35 @@
+pub mod agent_simulator;
+pub mod bar_chart;
+pub mod batch_comparison;
+pub mod budget_solver;
+pub mod chargeback_report;
+pub mod chart;
+pub mod line_chart;
+pub mod model_comparison;
+pub mod model_query_input;
+pub mod model_recommender;
+pub mod model_select;
+pub mod output_uncertainty;
+pub mod page_header;
+pub mod spend_anomalies;
+pub mod spend_table;
+pub mod traffic_projection;
+pub mod usage_analytics;
+
+pub use agent_simulator::AgentSimulator;
+pub use bar_chart::StackedBarChart;
+pub use batch_comparison::BatchComparison;
+pub use budget_solver::BudgetSolver;
+pub use chargeback_report::ChargebackReport;
+pub use chart::ChartSeries;
+pub use line_chart::LineChart;
+pub use model_comparison::ModelComparison;
+pub use model_query_input::ModelQueryInput;
+pub use model_recommender::ModelRecommender;
+pub use model_select::ModelSelect;
+pub use output_uncertainty::OutputUncertainty;
+pub use page_header::PageHeader;
+pub use spend_anomalies::SpendAnomalies;
+pub use spend_table::SpendTable;
+pub use traffic_projection::TrafficProjectionCard;
+pub use usage_analytics::UsageAnalytics;
@@ -0,0 +1,139 @@
+use leptos::prelude::*;
+use std::collections::HashMap;
+use thaw::*;
+
+use crate::models::ModelPricing;
+use crate::utils::projection::TrafficPlan;
+use crate::utils::{estimate_tokens_for_model, tokenizer_name_for_model};
+
+#[derive(Debug, Clone)]
+struct ComparisonRow {
+    model: String,
+    tokenizer: &'static str,
+    input_tokens: usize,
+    output_tokens: usize,
+    input_cost: f64,
+    output_cost: f64,
+    total_cost: f64,
+    fits: Option<bool>,
+}
+
+#[component]
+pub fn ModelComparison(
+    model_prices: ReadSignal<HashMap<String, ModelPricing>>,
+    models: RwSignal<Vec<String>>,
+    input_text: ReadSignal<String>,
+    output_text: ReadSignal<String>,
+    traffic_plan: RwSignal<TrafficPlan>,
+) -> impl IntoView {
+    let (sort_ascending, set_sort_ascending) = signal(true);
+
+    let rows = move || {
+        let prices = model_prices.get();
+        let input = input_text.get();
+        let output = output_text.get();
+
+        let mut rows: Vec<ComparisonRow> = models
+            .get()
+            .into_iter()
+            .filter_map(|model| {
+                let pricing = prices.get(&model)?;
+                let input_tokens = estimate_tokens_for_model(&input, &model);
+                let output_tokens = estimate_tokens_for_model(&output, &model);
+                let (input_cost, output_cost) = pricing.request_cost(input_tokens, output_tokens);
+                Some(ComparisonRow {
+                    tokenizer: tokenizer_name_for_model(&model),
+                    input_tokens,
+                    output_tokens,
+                    input_cost,
+                    output_cost,
+                    total_cost: input_cost + output_cost,
+                    fits: pricing.fits_request(input_tokens, output_tokens),
+                    model,
+                })
+            })
+            .collect();
+
+        rows.sort_by(|a, b| a.total_cost.total_cmp(&b.total_cost));
+        if !sort_ascending.get() {
+            rows.reverse();
+        }
+        rows
+    };
+
+    view! {
+        <Card class="w-full">
+            <div class="p-5">
+                <h3 class="text-xl font-semibold mb-4">"Model Comparison"</h3>
+                {move || {
+                    let rows = rows();
+                    let requests_per_month = traffic_plan.with(|plan| plan.requests_per_month());
+                    if rows.is_empty() {
+                        view! {
+                            <p class="text-gray-500 text-sm">
+                                "Use \"Add to comparison\" to compare several models on the same input and output."
+                            </p>
+                        }.into_any()
+                    } else {
+                        view! {
+                            <div class="overflow-x-auto">
+                                <table class="w-full border-collapse text-sm">
+                                    <thead>
+                                        <tr class="bg-gray-200">
+                                            <th class="border border-gray-300 px-3 py-2 text-left font-semibold">"Model"</th>
+                                            <th class="border border-gray-300 px-3 py-2 text-left font-semibold">"Tokenizer"</th>
+                                            <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Input Tokens"</th>
+                                            <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Output Tokens"</th>
+                                            <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Input Cost"</th>
+                                            <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Output Cost"</th>
+                                            <th
+                                                class="border border-gray-300 px-3 py-2 text-right font-semibold cursor-pointer select-none"
+                                                on:click=move |_| set_sort_ascending.update(|asc| *asc = !*asc)
+                                            >
+                                                {move || if sort_ascending.get() { "Total Cost ▲" } else { "Total Cost ▼" }}
+                                            </th>
+                                            <th class="border border-gray-300 px-3 py-2 text-right font-semibold">{format!("Monthly Cost ({:.0} requests)", requests_per_month)}</th>
+                                            <th class="border border-gray-300 px-3 py-2 text-center font-semibold">"Context Fit"</th>
+                                            <th class="border border-gray-300 px-3 py-2"></th>
+                                        </tr>
+                                    </thead>
+                                    <tbody>
+                                        {rows.into_iter().map(|row| {
+                                            let model = row.model.clone();
+                                            let (fit_label, fit_class) = match row.fits {
+                                                Some(true) => ("Fits", "text-green-600"),
+                                                Some(false) => ("Exceeds limit", "text-red-600 font-semibold"),
+                                                None => ("Unknown", "text-gray-500"),
+                                            };
+                                            view! {
+                                                <tr class="hover:bg-gray-50">
+                                                    <td class="border border-gray-300 px-3 py-2 font-mono">{row.model}</td>
+                                                    <td class="border border-gray-300 px-3 py-2 text-gray-600">{row.tokenizer}</td>
+                                                    <td class="border border-gray-300 px-3 py-2 text-right">{row.input_tokens}</td>
+                                                    <td class="border border-gray-300 px-3 py-2 text-right">{row.output_tokens}</td>
+                                                    <td class="border border-gray-300 px-3 py-2 text-right">{format!("${:.6}", row.input_cost)}</td>
+                                                    <td class="border border-gray-300 px-3 py-2 text-right">{format!("${:.6}", row.output_cost)}</td>
+                                                    <td class="border border-gray-300 px-3 py-2 text-right font-semibold">{format!("${:.6}", row.total_cost)}</td>
+                                                    <td class="border border-gray-300 px-3 py-2 text-right">{format!("${:.2}", row.total_cost * requests_per_month)}</td>
+                                                    <td class=format!("border border-gray-300 px-3 py-2 text-center {}", fit_class)>{fit_label}</td>
+                                                    <td class="border border-gray-300 px-3 py-2 text-center">
+                                                        <Button
+                                                            size=ButtonSize::Small
+                                                            on_click=move |_| models.update(|list| list.retain(|m| m != &model))
+                                                        >
+                                                            "Remove"
+                                                        </Button>
+                                                    </td>
+                                                </tr>
+                                            }
+                                        }).collect_view()}
+                                    </tbody>
+                                </table>
+                            </div>
+                        }.into_any()
+                    }
+                }}
+            </div>
+        </Card>
+    }
+}
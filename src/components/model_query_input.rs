@@ -0,0 +1,40 @@
+use leptos::prelude::*;
+use thaw::*;
+
+use crate::utils::model_query::parse_model_query;
+
+/// Search box for model queries such as `provider:anthropic input<3/1M`,
+/// underlining the offending term of a query that does not parse.
+#[component]
+pub fn ModelQueryInput(
+    query: ReadSignal<String>,
+    set_query: WriteSignal<String>,
+    placeholder: &'static str,
+) -> impl IntoView {
+    let parse_error = Memo::new(move |_| parse_model_query(&query.get()).err());
+
+    view! {
+        <div>
+            <Input
+                class="w-full"
+                placeholder=placeholder
+                on:input=move |ev| {
+                    set_query.set(event_target_value(&ev));
+                }
+            />
+            {move || parse_error.get().map(|err| {
+                let text = query.get();
+                view! {
+                    <div class="mt-1 text-sm">
+                        <div class="font-mono whitespace-pre">
+                            {text[..err.start].to_string()}
+                            <span class="text-red-600 underline decoration-wavy">{text[err.start..err.end].to_string()}</span>
+                            {text[err.end..].to_string()}
+                        </div>
+                        <div class="text-red-600">{err.message}</div>
+                    </div>
+                }
+            })}
+        </div>
+    }
+}
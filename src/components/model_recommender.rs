@@ -0,0 +1,119 @@
+use leptos::prelude::*;
+use std::collections::{HashMap, HashSet};
+use thaw::*;
+
+use crate::models::{Capability, ModelPricing};
+use crate::utils::date::today;
+use crate::utils::recommend::{recommend_models, ModelRequirements};
+
+const MAX_RECOMMENDATIONS: usize = 10;
+
+#[component]
+pub fn ModelRecommender(
+    model_prices: ReadSignal<HashMap<String, ModelPricing>>,
+    selected_model: ReadSignal<String>,
+    set_selected_model: WriteSignal<String>,
+    input_text: ReadSignal<String>,
+    output_text: ReadSignal<String>,
+) -> impl IntoView {
+    let required_capabilities = RwSignal::new(HashSet::<String>::new());
+    let min_context_window = RwSignal::new(0_i64);
+    let today = today();
+
+    let recommendations = move || {
+        let requirements = ModelRequirements {
+            capabilities: required_capabilities
+                .get()
+                .iter()
+                .filter_map(|key| Capability::from_key(key))
+                .collect(),
+            min_context_window: min_context_window.get(),
+        };
+        model_prices.with(|prices| {
+            recommend_models(
+                prices,
+                &requirements,
+                &input_text.get(),
+                &output_text.get(),
+                &today,
+            )
+        })
+    };
+
+    view! {
+        <Card class="w-full">
+            <div class="p-5">
+                <h3 class="text-xl font-semibold mb-4">"Cheapest Capable Models"</h3>
+                <div class="grid grid-cols-3 gap-4">
+                    <div>
+                        <h4 class="font-semibold mb-2">"Required capabilities"</h4>
+                        <CheckboxGroup value=required_capabilities>
+                            <div class="grid grid-cols-1">
+                                {Capability::ALL.into_iter().map(|capability| view! {
+                                    <Checkbox value=capability.key() label=capability.label() />
+                                }).collect_view()}
+                            </div>
+                        </CheckboxGroup>
+                        <div class="mt-3 text-sm">
+                            <div class="text-gray-600 mb-1">"Minimum context window (tokens)"</div>
+                            <SpinButton<i64> value=min_context_window step_page=8_000 min=0 />
+                        </div>
+                    </div>
+                    <div class="col-span-2">
+                        {move || {
+                            let recommendations = recommendations();
+                            let total = recommendations.len();
+                            if total == 0 {
+                                view! {
+                                    <p class="text-gray-500 text-sm">"No model satisfies these requirements."</p>
+                                }.into_any()
+                            } else {
+                                view! {
+                                    <div class="text-sm text-gray-600 mb-2">
+                                        {format!("{} matching models, cheapest first", total)}
+                                    </div>
+                                    <table class="w-full border-collapse text-sm">
+                                        <thead>
+                                            <tr class="bg-gray-200">
+                                                <th class="border border-gray-300 px-3 py-2 text-left font-semibold">"#"</th>
+                                                <th class="border border-gray-300 px-3 py-2 text-left font-semibold">"Model"</th>
+                                                <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Context"</th>
+                                                <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Cost / Request"</th>
+                                                <th class="border border-gray-300 px-3 py-2"></th>
+                                            </tr>
+                                        </thead>
+                                        <tbody>
+                                            {recommendations.into_iter().take(MAX_RECOMMENDATIONS).enumerate().map(|(rank, rec)| {
+                                                let model = rec.model.clone();
+                                                let model_for_check = rec.model.clone();
+                                                view! {
+                                                    <tr class="hover:bg-gray-50">
+                                                        <td class="border border-gray-300 px-3 py-2">{rank + 1}</td>
+                                                        <td class="border border-gray-300 px-3 py-2 font-mono">{rec.model}</td>
+                                                        <td class="border border-gray-300 px-3 py-2 text-right">
+                                                            {rec.context_window.map(|w| w.to_string()).unwrap_or_else(|| "N/A".to_string())}
+                                                        </td>
+                                                        <td class="border border-gray-300 px-3 py-2 text-right">{format!("${:.6}", rec.cost)}</td>
+                                                        <td class="border border-gray-300 px-3 py-2 text-center">
+                                                            <Button
+                                                                size=ButtonSize::Small
+                                                                disabled=Signal::derive(move || selected_model.get() == model_for_check)
+                                                                on_click=move |_| set_selected_model.set(model.clone())
+                                                            >
+                                                                "Use"
+                                                            </Button>
+                                                        </td>
+                                                    </tr>
+                                                }
+                                            }).collect_view()}
+                                        </tbody>
+                                    </table>
+                                }.into_any()
+                            }
+                        }}
+                    </div>
+                </div>
+            </div>
+        </Card>
+    }
+}
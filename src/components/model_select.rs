@@ -0,0 +1,43 @@
+use leptos::prelude::*;
+use std::collections::HashMap;
+
+use crate::models::ModelPricing;
+
+/// Drop-down of catalogue models, either chat/completion models or, with
+/// `embedding` set, embedding models only.
+#[component]
+pub fn ModelSelect(
+    model_prices: ReadSignal<HashMap<String, ModelPricing>>,
+    selected: RwSignal<String>,
+    #[prop(optional)] embedding: bool,
+) -> impl IntoView {
+    let models = move || {
+        let mut models: Vec<String> = model_prices
+            .get()
+            .into_iter()
+            .filter(|(_, pricing)| pricing.is_embedding() == embedding)
+            .map(|(name, _)| name)
+            .collect();
+        models.sort();
+        models
+    };
+
+    view! {
+        <select
+            class="w-full p-2 border border-gray-300 rounded text-sm"
+            on:change=move |ev| {
+                selected.set(event_target_value(&ev));
+            }
+        >
+            {move || models().into_iter().map(|model| {
+                let model_clone = model.clone();
+                let model_clone2 = model.clone();
+                view! {
+                    <option value=model selected=move || selected.get() == model_clone>
+                        {model_clone2}
+                    </option>
+                }
+            }).collect_view()}
+        </select>
+    }
+}
@@ -0,0 +1,167 @@
+use leptos::prelude::*;
+use std::collections::HashMap;
+use thaw::*;
+
+use crate::models::ModelPricing;
+use crate::utils::estimate_tokens_for_model;
+use crate::utils::projection::TrafficPlan;
+use crate::utils::uncertainty::{
+    project_uncertainty, simulate_output_costs, OutputDistribution, Percentiles, SAMPLES,
+};
+
+/// Monte Carlo estimate of the cost when the output length is uncertain.
+#[component]
+pub fn OutputUncertainty(
+    model_prices: ReadSignal<HashMap<String, ModelPricing>>,
+    selected_model: ReadSignal<String>,
+    input_text: ReadSignal<String>,
+    traffic_plan: RwSignal<TrafficPlan>,
+) -> impl IntoView {
+    let (use_normal, set_use_normal) = signal(false);
+    let min_tokens = RwSignal::new(100.0_f64);
+    let typical_tokens = RwSignal::new(300.0_f64);
+    let max_tokens = RwSignal::new(1_000.0_f64);
+    let mean_tokens = RwSignal::new(300.0_f64);
+    let stddev_tokens = RwSignal::new(100.0_f64);
+
+    let estimate = Memo::new(move |_| {
+        let distribution = if use_normal.get() {
+            OutputDistribution::Normal {
+                mean: mean_tokens.get(),
+                stddev: stddev_tokens.get(),
+            }
+        } else {
+            OutputDistribution::Range {
+                min: min_tokens.get(),
+                typical: typical_tokens.get(),
+                max: max_tokens.get(),
+            }
+        };
+        let model = selected_model.get();
+        let input_tokens = estimate_tokens_for_model(&input_text.get(), &model);
+        model_prices.with(|prices| {
+            prices
+                .get(&model)
+                .map(|pricing| simulate_output_costs(&distribution, input_tokens, pricing))
+        })
+    });
+
+    let cost_cells = |cost: Percentiles, decimals: usize| {
+        [cost.mean, cost.p50, cost.p95, cost.worst]
+            .into_iter()
+            .map(|value| {
+                view! {
+                    <td class="border border-gray-300 px-3 py-2 text-right">
+                        {format!("${:.*}", decimals, value)}
+                    </td>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <Card class="w-full">
+            <div class="p-5">
+                <h3 class="text-xl font-semibold mb-4">"Output Length Uncertainty"</h3>
+                <div class="grid grid-cols-3 gap-4">
+                    <div>
+                        <Space vertical=true>
+                            <div class="text-sm">
+                                <div class="text-gray-600 mb-1">"Output length"</div>
+                                <select
+                                    class="w-full p-2 border border-gray-300 rounded text-sm"
+                                    on:change=move |ev| set_use_normal.set(event_target_value(&ev) == "normal")
+                                >
+                                    <option value="range" selected=move || !use_normal.get()>"Min / typical / max"</option>
+                                    <option value="normal" selected=move || use_normal.get()>"Mean / standard deviation"</option>
+                                </select>
+                            </div>
+                            {move || if use_normal.get() {
+                                view! {
+                                    <div class="text-sm">
+                                        <div class="text-gray-600 mb-1">"Mean tokens"</div>
+                                        <SpinButton<f64> value=mean_tokens step_page=50.0 min=0.0 />
+                                    </div>
+                                    <div class="text-sm">
+                                        <div class="text-gray-600 mb-1">"Standard deviation"</div>
+                                        <SpinButton<f64> value=stddev_tokens step_page=25.0 min=0.0 />
+                                    </div>
+                                }.into_any()
+                            } else {
+                                view! {
+                                    <div class="text-sm">
+                                        <div class="text-gray-600 mb-1">"Min tokens"</div>
+                                        <SpinButton<f64> value=min_tokens step_page=50.0 min=0.0 />
+                                    </div>
+                                    <div class="text-sm">
+                                        <div class="text-gray-600 mb-1">"Typical tokens"</div>
+                                        <SpinButton<f64> value=typical_tokens step_page=50.0 min=0.0 />
+                                    </div>
+                                    <div class="text-sm">
+                                        <div class="text-gray-600 mb-1">"Max tokens"</div>
+                                        <SpinButton<f64> value=max_tokens step_page=50.0 min=0.0 />
+                                    </div>
+                                }.into_any()
+                            }}
+                        </Space>
+                    </div>
+                    <div class="col-span-2">
+                        {move || match estimate.get() {
+                            None => view! {
+                                <div class="text-sm text-gray-500">"Select a model to run the simulation."</div>
+                            }.into_any(),
+                            Some(estimate) => {
+                                let volumes = traffic_plan.with(|plan| project_uncertainty(&estimate, plan));
+                                let tokens = estimate.output_tokens;
+                                view! {
+                                    <div class="text-sm text-gray-600 mb-2">
+                                        {format!("{} samples", SAMPLES)}
+                                        {estimate.output_cap.map(|cap| format!(
+                                            ", output capped at {} tokens ({:.1}% of samples hit the cap)",
+                                            cap,
+                                            estimate.capped_share * 100.0,
+                                        ))}
+                                    </div>
+                                    <table class="w-full border-collapse text-sm">
+                                        <thead>
+                                            <tr class="bg-gray-200">
+                                                <th class="border border-gray-300 px-3 py-2 text-left font-semibold"></th>
+                                                <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Mean"</th>
+                                                <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"P50"</th>
+                                                <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"P95"</th>
+                                                <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Worst Case"</th>
+                                            </tr>
+                                        </thead>
+                                        <tbody>
+                                            <tr class="hover:bg-gray-50">
+                                                <td class="border border-gray-300 px-3 py-2">"Output tokens"</td>
+                                                {[tokens.mean, tokens.p50, tokens.p95, tokens.worst].into_iter().map(|value| view! {
+                                                    <td class="border border-gray-300 px-3 py-2 text-right">{format!("{:.0}", value)}</td>
+                                                }).collect_view()}
+                                            </tr>
+                                            <tr class="hover:bg-gray-50">
+                                                <td class="border border-gray-300 px-3 py-2">"Per request"</td>
+                                                {cost_cells(estimate.cost_per_request, 6)}
+                                            </tr>
+                                            {volumes.into_iter().map(|volume| view! {
+                                                <tr class="hover:bg-gray-50">
+                                                    <td class="border border-gray-300 px-3 py-2">
+                                                        {format!("{} ({:.0} requests)", volume.label, volume.requests)}
+                                                    </td>
+                                                    {cost_cells(volume.cost, 2)}
+                                                </tr>
+                                            }).collect_view()}
+                                        </tbody>
+                                    </table>
+                                    <div class="text-xs text-gray-500 mt-2">
+                                        "Volume percentiles treat requests as independent, so they narrow around the mean as volume grows. The worst case assumes every request produces the longest possible output."
+                                    </div>
+                                }.into_any()
+                            }
+                        }}
+                    </div>
+                </div>
+            </div>
+        </Card>
+    }
+}
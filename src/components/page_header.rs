@@ -0,0 +1,47 @@
+use leptos::prelude::*;
+
+const NAV_LINKS: &[(&str, &str)] = &[
+    ("/", "Cost Calculator"),
+    ("/list-model-prices", "Model Price List"),
+    ("/token-tools", "Token Tools"),
+    ("/rag-planner", "RAG Planner"),
+    ("/conversation-simulator", "Conversation & Agent Simulator"),
+    ("/workload-mix", "Workload Mix"),
+    ("/routing-simulator", "Routing Simulator"),
+    ("/batch-estimator", "Batch Estimator"),
+    ("/request-pricer", "Request Pricer"),
+    ("/transcript", "Transcript"),
+    ("/usage", "Usage"),
+    ("/calibration", "Calibration"),
+    ("/info", "Info"),
+];
+
+#[component]
+pub fn PageHeader(title: &'static str, subtitle: &'static str) -> impl IntoView {
+    let last = NAV_LINKS.len() - 1;
+
+    view! {
+        <div class="text-center mb-8">
+            <h1 class="text-4xl font-bold mb-2">
+                {title}
+            </h1>
+            <p class="text-gray-600 text-lg">
+                {subtitle}
+            </p>
+            <div class="mt-4">
+                {NAV_LINKS.iter().enumerate().map(|(i, (href, label))| {
+                    let class = if i == last {
+                        "text-blue-500 hover:text-blue-700"
+                    } else {
+                        "mr-4 text-blue-500 hover:text-blue-700"
+                    };
+                    view! {
+                        <a href=*href class=class>
+                            {*label}
+                        </a>
+                    }
+                }).collect_view()}
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,137 @@
+use leptos::prelude::*;
+use std::collections::HashMap;
+use thaw::*;
+
+use crate::models::ModelPricing;
+use crate::utils::anomaly::{
+    detect_anomalies, AnomalyScope, AnomalySettings, Threshold, MIN_HISTORY,
+};
+use crate::utils::usage::UsageRecord;
+
+/// Flags days whose cost spikes above a rolling baseline, for all usage or
+/// per model or API key, and names the model and token side behind each spike.
+#[component]
+pub fn SpendAnomalies(
+    model_prices: ReadSignal<HashMap<String, ModelPricing>>,
+    #[prop(into)] records: Signal<Vec<UsageRecord>>,
+) -> impl IntoView {
+    let (scope, set_scope) = signal(AnomalyScope::Total);
+    let (use_percent, set_use_percent) = signal(false);
+    let window = RwSignal::new(7_usize);
+    let z_limit = RwSignal::new(3.0_f64);
+    let percent_limit = RwSignal::new(100.0_f64);
+    let min_cost = RwSignal::new(1.0_f64);
+
+    let anomalies = Memo::new(move |_| {
+        let settings = AnomalySettings {
+            scope: scope.get(),
+            window: window.get(),
+            threshold: if use_percent.get() {
+                Threshold::Percent(percent_limit.get())
+            } else {
+                Threshold::ZScore(z_limit.get())
+            },
+            min_cost: min_cost.get(),
+        };
+        model_prices
+            .with(|prices| records.with(|records| detect_anomalies(records, prices, &settings)))
+    });
+
+    view! {
+        <Card class="w-full">
+            <div class="p-5">
+                <h3 class="text-xl font-semibold mb-4">"Spend Anomalies"</h3>
+                <div class="flex flex-wrap items-center gap-3 text-sm mb-4">
+                    <select
+                        class="p-2 border border-gray-300 rounded text-sm"
+                        on:change=move |ev| set_scope.set(match event_target_value(&ev).as_str() {
+                            "model" => AnomalyScope::Model,
+                            "key" => AnomalyScope::Key,
+                            _ => AnomalyScope::Total,
+                        })
+                    >
+                        <option value="total" selected=move || scope.get() == AnomalyScope::Total>"All usage"</option>
+                        <option value="model" selected=move || scope.get() == AnomalyScope::Model>"Per model"</option>
+                        <option value="key" selected=move || scope.get() == AnomalyScope::Key>"Per API key"</option>
+                    </select>
+                    <span class="text-gray-600">"Baseline days"</span>
+                    <SpinButton<usize> value=window step_page=1 min=MIN_HISTORY max=90 />
+                    <select
+                        class="p-2 border border-gray-300 rounded text-sm"
+                        on:change=move |ev| set_use_percent.set(event_target_value(&ev) == "percent")
+                    >
+                        <option value="z" selected=move || !use_percent.get()>"Z-score ≥"</option>
+                        <option value="percent" selected=move || use_percent.get()>"Spike % ≥"</option>
+                    </select>
+                    {move || if use_percent.get() {
+                        view! { <SpinButton<f64> value=percent_limit step_page=25.0 min=0.0 /> }.into_any()
+                    } else {
+                        view! { <SpinButton<f64> value=z_limit step_page=0.5 min=0.0 /> }.into_any()
+                    }}
+                    <span class="text-gray-600">"Ignore days under $"</span>
+                    <SpinButton<f64> value=min_cost step_page=1.0 min=0.0 />
+                </div>
+                {move || {
+                    let anomalies = anomalies.get();
+                    if anomalies.is_empty() {
+                        view! {
+                            <div class="text-sm text-gray-500">
+                                {format!("No spikes at these thresholds. Days need {} days of history before they are judged.", MIN_HISTORY)}
+                            </div>
+                        }.into_any()
+                    } else {
+                        view! {
+                            <div class="overflow-x-auto">
+                                <table class="w-full text-sm">
+                                    <thead>
+                                        <tr class="border-b border-gray-300 text-left">
+                                            <th class="py-2 pr-4">"Date"</th>
+                                            <th class="py-2 pr-4">"Series"</th>
+                                            <th class="py-2 pr-4 text-right">"Cost"</th>
+                                            <th class="py-2 pr-4 text-right">"Baseline"</th>
+                                            <th class="py-2 pr-4 text-right">"Z-score"</th>
+                                            <th class="py-2 pr-4 text-right">"Change"</th>
+                                            <th class="py-2">"Driven by"</th>
+                                        </tr>
+                                    </thead>
+                                    <tbody>
+                                        {anomalies.into_iter().map(|anomaly| {
+                                            let z_score = anomaly
+                                                .z_score
+                                                .map(|z| format!("{:.1}", z))
+                                                .unwrap_or_else(|| "-".to_string());
+                                            let change = anomaly
+                                                .percent_change
+                                                .map(|change| format!("{:+.0}%", change))
+                                                .unwrap_or_else(|| "new".to_string());
+                                            let driver = anomaly.driver;
+                                            view! {
+                                                <tr class="border-b border-gray-200">
+                                                    <td class="py-2 pr-4">{anomaly.date}</td>
+                                                    <td class="py-2 pr-4 font-mono">{anomaly.group}</td>
+                                                    <td class="py-2 pr-4 text-right text-red-600 font-semibold">{format!("${:.2}", anomaly.cost)}</td>
+                                                    <td class="py-2 pr-4 text-right">{format!("${:.2}", anomaly.baseline)}</td>
+                                                    <td class="py-2 pr-4 text-right">{z_score}</td>
+                                                    <td class="py-2 pr-4 text-right">{change}</td>
+                                                    <td class="py-2">
+                                                        <span class="font-mono">{driver.model}</span>
+                                                        {format!(
+                                                            " +${:.2}, mostly {} tokens (+${:.2})",
+                                                            driver.increase,
+                                                            driver.direction.label(),
+                                                            driver.direction_increase,
+                                                        )}
+                                                    </td>
+                                                </tr>
+                                            }
+                                        }).collect_view()}
+                                    </tbody>
+                                </table>
+                            </div>
+                        }.into_any()
+                    }
+                }}
+            </div>
+        </Card>
+    }
+}
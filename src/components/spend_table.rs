@@ -0,0 +1,72 @@
+use leptos::prelude::*;
+use thaw::*;
+
+use crate::utils::usage::SpendRow;
+
+/// Spend grouped by one dimension (model, day, key, ...), with each group's
+/// share of the total. Logged and difference columns appear when the source
+/// recorded its own costs.
+#[component]
+pub fn SpendTable(
+    title: &'static str,
+    label_header: &'static str,
+    #[prop(into)] rows: Signal<Vec<SpendRow>>,
+    #[prop(optional)] note: Option<&'static str>,
+) -> impl IntoView {
+    let has_logged = move || rows.with(|rows| rows.iter().any(|row| row.logged_cost.is_some()));
+
+    view! {
+        <Card class="w-full">
+            <div class="p-5">
+                <h3 class="text-xl font-semibold mb-4">{title}</h3>
+                {note.map(|note| view! { <div class="text-sm text-gray-600 mb-2">{note}</div> })}
+                <div class="overflow-x-auto max-h-96">
+                    <table class="w-full border-collapse text-sm">
+                        <thead>
+                            <tr class="bg-gray-200">
+                                <th class="border border-gray-300 px-3 py-2 text-left font-semibold">{label_header}</th>
+                                <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Requests"</th>
+                                <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Input Tokens"</th>
+                                <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Output Tokens"</th>
+                                <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Cost"</th>
+                                {move || has_logged().then(|| view! {
+                                    <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Logged"</th>
+                                    <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Difference"</th>
+                                })}
+                                <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Share"</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {move || {
+                                let rows = rows.get();
+                                let show_logged = has_logged();
+                                let total: f64 = rows.iter().map(|row| row.cost).sum();
+                                rows.into_iter().map(|row| {
+                                    let share = if total > 0.0 { row.cost / total } else { 0.0 };
+                                    view! {
+                                        <tr class="hover:bg-gray-50">
+                                            <td class="border border-gray-300 px-3 py-2 font-mono">{row.label}</td>
+                                            <td class="border border-gray-300 px-3 py-2 text-right">{row.requests}</td>
+                                            <td class="border border-gray-300 px-3 py-2 text-right">{row.input_tokens}</td>
+                                            <td class="border border-gray-300 px-3 py-2 text-right">{row.output_tokens}</td>
+                                            <td class="border border-gray-300 px-3 py-2 text-right">{format!("${:.4}", row.cost)}</td>
+                                            {show_logged.then(|| view! {
+                                                <td class="border border-gray-300 px-3 py-2 text-right">
+                                                    {row.logged_cost.map(|c| format!("${:.4}", c)).unwrap_or_else(|| "N/A".to_string())}
+                                                </td>
+                                                <td class="border border-gray-300 px-3 py-2 text-right">
+                                                    {row.logged_cost.map(|c| format!("{:+.4}", row.cost - c)).unwrap_or_default()}
+                                                </td>
+                                            })}
+                                            <td class="border border-gray-300 px-3 py-2 text-right">{format!("{:.1}%", share * 100.0)}</td>
+                                        </tr>
+                                    }
+                                }).collect_view()
+                            }}
+                        </tbody>
+                    </table>
+                </div>
+            </div>
+        </Card>
+    }
+}
@@ -0,0 +1,163 @@
+use leptos::prelude::*;
+use thaw::*;
+
+use crate::components::{ChartSeries, LineChart};
+use crate::utils::projection::{project_traffic, TrafficPlan};
+
+const HOURS_PER_DAY: f64 = 24.0;
+
+fn format_dollars(value: f64) -> String {
+    format!("${:.2}", value)
+}
+
+/// Projection card driven by request volume, working days, growth and horizon.
+/// The resulting plan is written back to `plan` so other cards can reuse it.
+#[component]
+pub fn TrafficProjectionCard(
+    #[prop(into)] cost_per_request: Signal<f64>,
+    plan: RwSignal<TrafficPlan>,
+) -> impl IntoView {
+    let initial = plan.get_untracked();
+    let volume = RwSignal::new(initial.requests_per_day);
+    let (per_hour, set_per_hour) = signal(false);
+    let days_per_month = RwSignal::new(initial.days_per_month);
+    let growth_percent = RwSignal::new(initial.monthly_growth * 100.0);
+    let horizon_months = RwSignal::new(initial.horizon_months);
+
+    Effect::new(move |_| {
+        let requests_per_day = if per_hour.get() {
+            volume.get() * HOURS_PER_DAY
+        } else {
+            volume.get()
+        };
+        plan.set(TrafficPlan {
+            requests_per_day,
+            days_per_month: days_per_month.get(),
+            monthly_growth: growth_percent.get() / 100.0,
+            horizon_months: horizon_months.get(),
+        });
+    });
+
+    let projection = Memo::new(move |_| project_traffic(&plan.get(), cost_per_request.get()));
+
+    let x_labels = Signal::derive(move || {
+        projection
+            .get()
+            .months
+            .iter()
+            .map(|m| format!("M{}", m.month))
+            .collect::<Vec<_>>()
+    });
+    let series = Signal::derive(move || {
+        let months = projection.get().months;
+        vec![
+            ChartSeries {
+                label: "Monthly cost".to_string(),
+                color: "#3b82f6",
+                values: months.iter().map(|m| m.cost).collect(),
+            },
+            ChartSeries {
+                label: "Cumulative cost".to_string(),
+                color: "#8b5cf6",
+                values: months.iter().map(|m| m.cumulative_cost).collect(),
+            },
+        ]
+    });
+
+    view! {
+        <Card class="w-full">
+            <div class="p-5">
+                <h3 class="text-xl font-semibold mb-4">"Projections"</h3>
+                <div class="grid grid-cols-3 gap-4">
+                    <div>
+                        <Space vertical=true>
+                            <div class="text-sm">
+                                <div class="text-gray-600 mb-1">"Requests"</div>
+                                <div class="flex gap-2">
+                                    <SpinButton<f64> value=volume step_page=100.0 min=0.0 />
+                                    <select
+                                        class="p-2 border border-gray-300 rounded text-sm"
+                                        on:change=move |ev| set_per_hour.set(event_target_value(&ev) == "hour")
+                                    >
+                                        <option value="day" selected=move || !per_hour.get()>"per day"</option>
+                                        <option value="hour" selected=move || per_hour.get()>"per hour"</option>
+                                    </select>
+                                </div>
+                            </div>
+                            <div class="text-sm">
+                                <div class="text-gray-600 mb-1">"Working days per month"</div>
+                                <SpinButton<f64> value=days_per_month step_page=1.0 min=0.0 max=31.0 />
+                            </div>
+                            <div class="text-sm">
+                                <div class="text-gray-600 mb-1">"Monthly growth (%)"</div>
+                                <SpinButton<f64> value=growth_percent step_page=1.0 min=-100.0 />
+                            </div>
+                            <div class="text-sm">
+                                <div class="text-gray-600 mb-1">"Horizon (months)"</div>
+                                <SpinButton<usize> value=horizon_months step_page=6 min=1 max=120 />
+                            </div>
+                        </Space>
+                    </div>
+                    <div class="col-span-2">
+                        {move || {
+                            let projection = projection.get();
+                            view! {
+                                <Grid cols=3 x_gap=8>
+                                    <GridItem>
+                                        <Card class="bg-gray-100">
+                                            <div class="p-3 text-center">
+                                                <div class="text-xs text-gray-600 mb-1">"Daily"</div>
+                                                <div class="font-semibold">{format!("${:.2}", projection.daily_cost)}</div>
+                                            </div>
+                                        </Card>
+                                    </GridItem>
+                                    <GridItem>
+                                        <Card class="bg-gray-100">
+                                            <div class="p-3 text-center">
+                                                <div class="text-xs text-gray-600 mb-1">"Monthly"</div>
+                                                <div class="font-semibold">{format!("${:.2}", projection.monthly_cost)}</div>
+                                            </div>
+                                        </Card>
+                                    </GridItem>
+                                    <GridItem>
+                                        <Card class="bg-gray-100">
+                                            <div class="p-3 text-center">
+                                                <div class="text-xs text-gray-600 mb-1">"Annual"</div>
+                                                <div class="font-semibold">{format!("${:.2}", projection.annual_cost)}</div>
+                                            </div>
+                                        </Card>
+                                    </GridItem>
+                                </Grid>
+                            }
+                        }}
+                        <div class="mt-3">
+                            <LineChart series=series x_labels=x_labels y_format=format_dollars />
+                        </div>
+                        <div class="overflow-x-auto max-h-64 mt-3">
+                            <table class="w-full border-collapse text-sm">
+                                <thead>
+                                    <tr class="bg-gray-200">
+                                        <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Month"</th>
+                                        <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Requests"</th>
+                                        <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Cost"</th>
+                                        <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Cumulative"</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    {move || projection.get().months.into_iter().map(|m| view! {
+                                        <tr class="hover:bg-gray-50">
+                                            <td class="border border-gray-300 px-3 py-2 text-right">{m.month}</td>
+                                            <td class="border border-gray-300 px-3 py-2 text-right">{format!("{:.0}", m.requests)}</td>
+                                            <td class="border border-gray-300 px-3 py-2 text-right">{format!("${:.2}", m.cost)}</td>
+                                            <td class="border border-gray-300 px-3 py-2 text-right">{format!("${:.2}", m.cumulative_cost)}</td>
+                                        </tr>
+                                    }).collect_view()}
+                                </tbody>
+                            </table>
+                        </div>
+                    </div>
+                </div>
+            </div>
+        </Card>
+    }
+}
@@ -0,0 +1,199 @@
+use leptos::prelude::*;
+use std::collections::HashMap;
+use thaw::*;
+
+use crate::components::{ChartSeries, LineChart, StackedBarChart};
+use crate::models::ModelPricing;
+use crate::utils::analytics::{build_time_series, Granularity, StackBy};
+use crate::utils::usage::{group_spend, price_records, UsageRecord};
+
+const STACK_COLORS: [&str; 10] = [
+    "#3b82f6", "#8b5cf6", "#ec4899", "#f97316", "#10b981", "#eab308", "#06b6d4", "#ef4444",
+    "#6366f1", "#84cc16",
+];
+const OTHER_COLOR: &str = "#9ca3af";
+
+fn format_dollars(value: f64) -> String {
+    format!("${:.2}", value)
+}
+
+fn format_tokens(value: f64) -> String {
+    if value >= 1_000_000.0 {
+        format!("{:.1}M", value / 1_000_000.0)
+    } else if value >= 1_000.0 {
+        format!("{:.0}k", value / 1_000.0)
+    } else {
+        format!("{:.0}", value)
+    }
+}
+
+/// Time-series charts over imported usage: cost stacked by model or provider,
+/// the most expensive models, and token volume against the effective price.
+#[component]
+pub fn UsageAnalytics(
+    model_prices: ReadSignal<HashMap<String, ModelPricing>>,
+    #[prop(into)] records: Signal<Vec<UsageRecord>>,
+) -> impl IntoView {
+    let (weekly, set_weekly) = signal(false);
+    let (by_provider, set_by_provider) = signal(false);
+    let top_n = RwSignal::new(5_usize);
+
+    let costs = Memo::new(move |_| {
+        model_prices.with(|prices| records.with(|records| price_records(records, prices)))
+    });
+    let series = Memo::new(move |_| {
+        let granularity = if weekly.get() {
+            Granularity::Week
+        } else {
+            Granularity::Day
+        };
+        let stack_by = if by_provider.get() {
+            StackBy::Provider
+        } else {
+            StackBy::Model
+        };
+        model_prices.with(|prices| {
+            records.with(|records| {
+                build_time_series(
+                    records,
+                    &costs.get(),
+                    prices,
+                    granularity,
+                    stack_by,
+                    top_n.get(),
+                )
+            })
+        })
+    });
+
+    let x_labels = Signal::derive(move || series.get().periods);
+    let cost_series = Signal::derive(move || {
+        let top = top_n.get();
+        series
+            .get()
+            .stacks
+            .into_iter()
+            .enumerate()
+            .map(|(i, (label, values))| ChartSeries {
+                label,
+                color: if i < top {
+                    STACK_COLORS[i % STACK_COLORS.len()]
+                } else {
+                    OTHER_COLOR
+                },
+                values,
+            })
+            .collect::<Vec<_>>()
+    });
+    let token_series = Signal::derive(move || {
+        let series = series.get();
+        vec![
+            ChartSeries {
+                label: "Input tokens".to_string(),
+                color: "#3b82f6",
+                values: series.input_tokens,
+            },
+            ChartSeries {
+                label: "Output tokens".to_string(),
+                color: "#ec4899",
+                values: series.output_tokens,
+            },
+        ]
+    });
+    let rate_series = Signal::derive(move || {
+        let series = series.get();
+        let values = series
+            .cost
+            .iter()
+            .zip(series.input_tokens.iter().zip(&series.output_tokens))
+            .map(|(cost, (input, output))| {
+                let tokens = input + output;
+                if tokens > 0.0 {
+                    cost / tokens * 1_000_000.0
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        vec![ChartSeries {
+            label: "Cost per 1M tokens".to_string(),
+            color: "#10b981",
+            values,
+        }]
+    });
+    let top_models = Memo::new(move |_| {
+        let rows = records.with(|records| group_spend(records, &costs.get(), |r| r.model.clone()));
+        rows.into_iter().take(top_n.get()).collect::<Vec<_>>()
+    });
+
+    view! {
+        <Card class="w-full">
+            <div class="p-5">
+                <div class="flex justify-between items-center mb-4">
+                    <h3 class="text-xl font-semibold">"Analytics"</h3>
+                    <div class="flex items-center gap-3 text-sm">
+                        <select
+                            class="p-2 border border-gray-300 rounded text-sm"
+                            on:change=move |ev| set_weekly.set(event_target_value(&ev) == "week")
+                        >
+                            <option value="day" selected=move || !weekly.get()>"Daily"</option>
+                            <option value="week" selected=move || weekly.get()>"Weekly"</option>
+                        </select>
+                        <select
+                            class="p-2 border border-gray-300 rounded text-sm"
+                            on:change=move |ev| set_by_provider.set(event_target_value(&ev) == "provider")
+                        >
+                            <option value="model" selected=move || !by_provider.get()>"By model"</option>
+                            <option value="provider" selected=move || by_provider.get()>"By provider"</option>
+                        </select>
+                        <span class="text-gray-600">"Top"</span>
+                        <SpinButton<usize> value=top_n step_page=1 min=1 max=10 />
+                    </div>
+                </div>
+                {move || if series.with(|s| s.periods.is_empty()) {
+                    view! {
+                        <div class="text-sm text-gray-500">"Import usage with dates to see charts."</div>
+                    }.into_any()
+                } else {
+                    view! {
+                        <div class="grid grid-cols-2 gap-4">
+                            <div>
+                                <h4 class="font-semibold mb-2">"Cost"</h4>
+                                <StackedBarChart series=cost_series x_labels=x_labels y_format=format_dollars />
+                            </div>
+                            <div>
+                                <h4 class="font-semibold mb-2">"Most Expensive Models"</h4>
+                                {move || {
+                                    let rows = top_models.get();
+                                    let max = rows.first().map(|row| row.cost).unwrap_or(0.0).max(f64::EPSILON);
+                                    rows.into_iter().map(|row| view! {
+                                        <div class="mb-2 text-sm">
+                                            <div class="flex justify-between">
+                                                <span class="font-mono">{row.label.clone()}</span>
+                                                <span>{format!("${:.2}", row.cost)}</span>
+                                            </div>
+                                            <div class="h-2 bg-gray-200 rounded">
+                                                <div
+                                                    class="h-2 bg-blue-500 rounded"
+                                                    style=format!("width: {:.1}%", row.cost / max * 100.0)
+                                                ></div>
+                                            </div>
+                                        </div>
+                                    }).collect_view()
+                                }}
+                            </div>
+                            <div>
+                                <h4 class="font-semibold mb-2">"Tokens"</h4>
+                                <LineChart series=token_series x_labels=x_labels y_format=format_tokens />
+                            </div>
+                            <div>
+                                <h4 class="font-semibold mb-2">"Effective Price"</h4>
+                                <LineChart series=rate_series x_labels=x_labels y_format=format_dollars />
+                            </div>
+                        </div>
+                    }.into_any()
+                }}
+            </div>
+        </Card>
+    }
+}
@@ -1,3 +1,4 @@
+mod components;
 mod models;
 mod pages;
 mod utils;
@@ -5,7 +6,11 @@ mod utils;
 use leptos::prelude::*;
 use leptos_router::components::{Route, Router, Routes};
 use leptos_router::StaticSegment;
-use pages::{HomePage, InfoPage, ModelPriceListPage};
+use pages::{
+    BatchEstimatorPage, CalibrationPage, ConversationSimulatorPage, HomePage, InfoPage,
+    ModelPriceListPage, RagPlannerPage, RequestPricerPage, RoutingSimulatorPage, TokenToolsPage,
+    TranscriptPage, UsagePage, WorkloadMixPage,
+};
 use thaw::*;
 
 #[component]
@@ -16,6 +21,16 @@ fn App() -> impl IntoView {
                 <Routes fallback=|| "Not found.">
                     <Route path=StaticSegment("") view=HomePage />
                     <Route path=StaticSegment("list-model-prices") view=ModelPriceListPage />
+                    <Route path=StaticSegment("token-tools") view=TokenToolsPage />
+                    <Route path=StaticSegment("rag-planner") view=RagPlannerPage />
+                    <Route path=StaticSegment("conversation-simulator") view=ConversationSimulatorPage />
+                    <Route path=StaticSegment("workload-mix") view=WorkloadMixPage />
+                    <Route path=StaticSegment("routing-simulator") view=RoutingSimulatorPage />
+                    <Route path=StaticSegment("batch-estimator") view=BatchEstimatorPage />
+                    <Route path=StaticSegment("request-pricer") view=RequestPricerPage />
+                    <Route path=StaticSegment("transcript") view=TranscriptPage />
+                    <Route path=StaticSegment("usage") view=UsagePage />
+                    <Route path=StaticSegment("calibration") view=CalibrationPage />
                     <Route path=StaticSegment("info") view=InfoPage />
                 </Routes>
             </Router>
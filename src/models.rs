@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SearchContextCost {
     #[serde(default)]
     pub search_context_size_high: Option<f64>,
@@ -10,21 +10,37 @@ pub struct SearchContextCost {
     pub search_context_size_medium: Option<f64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ModelPricing {
     #[serde(default)]
     pub input_cost_per_token: Option<f64>,
     #[serde(default)]
     pub output_cost_per_token: Option<f64>,
     #[serde(default)]
+    pub input_cost_per_token_batches: Option<f64>,
+    #[serde(default)]
+    pub output_cost_per_token_batches: Option<f64>,
+    #[serde(default)]
+    pub input_cost_per_token_above_200k_tokens: Option<f64>,
+    #[serde(default)]
+    pub output_cost_per_token_above_200k_tokens: Option<f64>,
+    #[serde(default)]
     pub input_cost_per_audio_token: Option<f64>,
     #[serde(default)]
+    pub output_cost_per_audio_token: Option<f64>,
+    #[serde(default)]
     pub output_cost_per_reasoning_token: Option<f64>,
     #[serde(default)]
     pub cache_creation_input_token_cost: Option<f64>,
     #[serde(default)]
+    pub cache_creation_input_token_cost_above_1hr: Option<f64>,
+    #[serde(default)]
+    pub cache_creation_input_token_cost_above_200k_tokens: Option<f64>,
+    #[serde(default)]
     pub cache_read_input_token_cost: Option<f64>,
     #[serde(default)]
+    pub cache_read_input_token_cost_above_200k_tokens: Option<f64>,
+    #[serde(default)]
     pub input_cost_per_pixel: Option<f64>,
     #[serde(default)]
     pub output_cost_per_pixel: Option<f64>,
@@ -87,3 +103,167 @@ pub struct ModelPricing {
     #[serde(default)]
     pub supported_regions: Option<Vec<String>>,
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Capability {
+    Vision,
+    FunctionCalling,
+    ParallelFunctionCalling,
+    ResponseSchema,
+    Reasoning,
+    PromptCaching,
+    SystemMessages,
+    WebSearch,
+    PdfInput,
+    AudioInput,
+    AudioOutput,
+    AssistantPrefill,
+    ToolChoice,
+}
+
+impl Capability {
+    pub const ALL: [Capability; 13] = [
+        Capability::Vision,
+        Capability::FunctionCalling,
+        Capability::ParallelFunctionCalling,
+        Capability::ResponseSchema,
+        Capability::Reasoning,
+        Capability::PromptCaching,
+        Capability::SystemMessages,
+        Capability::WebSearch,
+        Capability::PdfInput,
+        Capability::AudioInput,
+        Capability::AudioOutput,
+        Capability::AssistantPrefill,
+        Capability::ToolChoice,
+    ];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Capability::Vision => "vision",
+            Capability::FunctionCalling => "function_calling",
+            Capability::ParallelFunctionCalling => "parallel_function_calling",
+            Capability::ResponseSchema => "response_schema",
+            Capability::Reasoning => "reasoning",
+            Capability::PromptCaching => "prompt_caching",
+            Capability::SystemMessages => "system_messages",
+            Capability::WebSearch => "web_search",
+            Capability::PdfInput => "pdf_input",
+            Capability::AudioInput => "audio_input",
+            Capability::AudioOutput => "audio_output",
+            Capability::AssistantPrefill => "assistant_prefill",
+            Capability::ToolChoice => "tool_choice",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Capability::Vision => "Vision",
+            Capability::FunctionCalling => "Function calling",
+            Capability::ParallelFunctionCalling => "Parallel function calling",
+            Capability::ResponseSchema => "Response schema",
+            Capability::Reasoning => "Reasoning",
+            Capability::PromptCaching => "Prompt caching",
+            Capability::SystemMessages => "System messages",
+            Capability::WebSearch => "Web search",
+            Capability::PdfInput => "PDF input",
+            Capability::AudioInput => "Audio input",
+            Capability::AudioOutput => "Audio output",
+            Capability::AssistantPrefill => "Assistant prefill",
+            Capability::ToolChoice => "Tool choice",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Capability> {
+        Capability::ALL.into_iter().find(|c| c.key() == key)
+    }
+}
+
+impl ModelPricing {
+    pub fn supports(&self, capability: Capability) -> bool {
+        let flag = match capability {
+            Capability::Vision => self.supports_vision,
+            Capability::FunctionCalling => self.supports_function_calling,
+            Capability::ParallelFunctionCalling => self.supports_parallel_function_calling,
+            Capability::ResponseSchema => self.supports_response_schema,
+            Capability::Reasoning => self.supports_reasoning,
+            Capability::PromptCaching => self.supports_prompt_caching,
+            Capability::SystemMessages => self.supports_system_messages,
+            Capability::WebSearch => self.supports_web_search,
+            Capability::PdfInput => self.supports_pdf_input,
+            Capability::AudioInput => self.supports_audio_input,
+            Capability::AudioOutput => self.supports_audio_output,
+            Capability::AssistantPrefill => self.supports_assistant_prefill,
+            Capability::ToolChoice => self.supports_tool_choice,
+        };
+        flag.unwrap_or(false)
+    }
+
+    /// Whether the model's `deprecation_date` (YYYY-MM-DD) is on or before `today`.
+    pub fn is_deprecated(&self, today: &str) -> bool {
+        self.deprecation_date
+            .as_deref()
+            .is_some_and(|date| date <= today)
+    }
+
+    pub fn is_embedding(&self) -> bool {
+        self.mode.as_deref() == Some("embedding")
+    }
+
+    /// Input and output cost of a single request with the given token counts.
+    pub fn request_cost(&self, input_tokens: usize, output_tokens: usize) -> (f64, f64) {
+        (
+            self.input_cost_per_token.unwrap_or(0.0) * input_tokens as f64,
+            self.output_cost_per_token.unwrap_or(0.0) * output_tokens as f64,
+        )
+    }
+
+    pub fn has_batch_pricing(&self) -> bool {
+        self.input_cost_per_token_batches.is_some() || self.output_cost_per_token_batches.is_some()
+    }
+
+    /// Input and output cost of a request sent through the provider's batch API,
+    /// or `None` when the catalogue lists no batch prices for the model. A side
+    /// without its own batch price is billed at the real-time price.
+    pub fn batch_request_cost(
+        &self,
+        input_tokens: usize,
+        output_tokens: usize,
+    ) -> Option<(f64, f64)> {
+        if !self.has_batch_pricing() {
+            return None;
+        }
+        let input_price = self
+            .input_cost_per_token_batches
+            .or(self.input_cost_per_token)
+            .unwrap_or(0.0);
+        let output_price = self
+            .output_cost_per_token_batches
+            .or(self.output_cost_per_token)
+            .unwrap_or(0.0);
+        Some((
+            input_price * input_tokens as f64,
+            output_price * output_tokens as f64,
+        ))
+    }
+
+    /// Largest prompt the model accepts, falling back to `max_tokens` when the
+    /// catalogue has no separate input limit.
+    pub fn context_window(&self) -> Option<i64> {
+        self.max_input_tokens.or(self.max_tokens)
+    }
+
+    /// Whether a request fits the model's input and output limits, or `None`
+    /// when the catalogue lists no limits for it.
+    pub fn fits_request(&self, input_tokens: usize, output_tokens: usize) -> Option<bool> {
+        let input_limit = self.context_window();
+        let output_limit = self.max_output_tokens;
+        if input_limit.is_none() && output_limit.is_none() {
+            return None;
+        }
+        Some(
+            input_limit.is_none_or(|max| input_tokens as i64 <= max)
+                && output_limit.is_none_or(|max| output_tokens as i64 <= max),
+        )
+    }
+}
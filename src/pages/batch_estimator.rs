@@ -0,0 +1,252 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use std::collections::HashMap;
+use thaw::*;
+use web_sys::FileList;
+
+use crate::components::PageHeader;
+use crate::models::ModelPricing;
+use crate::utils::batch::estimate_batch_file;
+use crate::utils::upload::{file_list_to_vec, read_file_text};
+use crate::utils::{fetch_model_prices, preload_tokenizer};
+
+/// Lines shown in the per-line table; totals always cover the whole file.
+const MAX_ROWS: usize = 500;
+
+#[component]
+pub fn BatchEstimatorPage() -> impl IntoView {
+    let (model_prices, set_model_prices) = signal(HashMap::<String, ModelPricing>::new());
+    let (loading, set_loading) = signal(true);
+    let (error, set_error) = signal(Option::<String>::None);
+
+    let batch_text = RwSignal::new(String::new());
+    let (file_name, set_file_name) = signal(Option::<String>::None);
+    let (upload_error, set_upload_error) = signal(Option::<String>::None);
+
+    Effect::new(move |_| {
+        spawn_local(async move {
+            preload_tokenizer();
+        });
+    });
+
+    Effect::new(move |_| {
+        spawn_local(async move {
+            match fetch_model_prices().await {
+                Ok(prices) => {
+                    set_model_prices.set(prices);
+                    set_loading.set(false);
+                }
+                Err(e) => {
+                    set_error.set(Some(format!("Failed to load model prices: {}", e)));
+                    set_loading.set(false);
+                }
+            }
+        });
+    });
+
+    let on_upload = move |files: FileList| {
+        let files = file_list_to_vec(&files);
+        spawn_local(async move {
+            set_upload_error.set(None);
+            if let Some(file) = files.into_iter().next() {
+                match read_file_text(&file).await {
+                    Ok(text) => {
+                        set_file_name.set(Some(file.name()));
+                        batch_text.set(text);
+                    }
+                    Err(e) => set_upload_error.set(Some(e)),
+                }
+            }
+        });
+    };
+
+    let estimate = Memo::new(move |_| {
+        model_prices.with(|prices| batch_text.with(|text| estimate_batch_file(text, prices)))
+    });
+
+    view! {
+        <div class="min-h-screen bg-gray-100 p-6">
+            <div class="max-w-7xl mx-auto">
+                <PageHeader
+                    title="Batch Estimator"
+                    subtitle="Price an OpenAI Batch input file before submitting it"
+                />
+
+                {move || {
+                    if loading.get() {
+                        view! {
+                            <div class="text-center py-12">
+                                <Spinner size=SpinnerSize::Huge />
+                                <p class="mt-4 text-gray-600">"Loading model prices..."</p>
+                            </div>
+                        }.into_any()
+                    } else if let Some(err) = error.get() {
+                        view! {
+                            <MessageBar intent=MessageBarIntent::Error>
+                                <MessageBarBody>
+                                    <div>
+                                        <strong>"Error: "</strong>
+                                        {err}
+                                    </div>
+                                </MessageBarBody>
+                            </MessageBar>
+                        }.into_any()
+                    } else {
+                        view! {
+                            <div class="space-y-4">
+                                <Card class="w-full">
+                                    <div class="p-5">
+                                        <h3 class="text-xl font-semibold mb-4">"Batch File"</h3>
+                                        <Space vertical=true>
+                                            <div class="flex items-center gap-3">
+                                                <Upload accept=".jsonl" custom_request=on_upload>
+                                                    <Button>"Upload .jsonl"</Button>
+                                                </Upload>
+                                                <Button
+                                                    disabled=Signal::derive(move || batch_text.get().is_empty())
+                                                    on_click=move |_| {
+                                                        batch_text.set(String::new());
+                                                        set_file_name.set(None);
+                                                    }
+                                                >
+                                                    "Clear"
+                                                </Button>
+                                                <span class="text-sm font-mono text-gray-600">{move || file_name.get()}</span>
+                                            </div>
+                                            {move || upload_error.get().map(|err| view! {
+                                                <MessageBar intent=MessageBarIntent::Warning>
+                                                    <MessageBarBody>{err}</MessageBarBody>
+                                                </MessageBar>
+                                            })}
+                                            <Textarea
+                                                class="w-full h-40 font-mono"
+                                                value=batch_text
+                                                placeholder="Or paste batch lines here, one {\"custom_id\", \"method\", \"url\", \"body\"} object per line..."
+                                            />
+                                        </Space>
+                                    </div>
+                                </Card>
+
+                                <Card class="w-full">
+                                    <div class="p-5">
+                                        <h3 class="text-xl font-semibold mb-4">"Estimate"</h3>
+                                        {move || {
+                                            let estimate = estimate.get();
+                                            let realtime = estimate.input_cost + estimate.worst_output_cost;
+                                            let line_count = estimate.lines.len();
+                                            let error_count = estimate.errors.len();
+                                            let errors = estimate.errors;
+                                            let lines = estimate.lines;
+                                            view! {
+                                                <Grid cols=4 x_gap=12>
+                                                    <GridItem>
+                                                        <Card class="bg-gray-100">
+                                                            <div class="p-3 text-center">
+                                                                <div class="text-xs text-gray-600 mb-1">"Valid / invalid lines"</div>
+                                                                <div class="font-semibold">{format!("{} / {}", line_count, error_count)}</div>
+                                                            </div>
+                                                        </Card>
+                                                    </GridItem>
+                                                    <GridItem>
+                                                        <Card class="bg-gray-100">
+                                                            <div class="p-3 text-center">
+                                                                <div class="text-xs text-gray-600 mb-1">"Input / max output tokens"</div>
+                                                                <div class="font-semibold">
+                                                                    {format!("{} / {}", estimate.total_input_tokens, estimate.total_max_output_tokens)}
+                                                                </div>
+                                                            </div>
+                                                        </Card>
+                                                    </GridItem>
+                                                    <GridItem>
+                                                        <Card class="bg-gray-100">
+                                                            <div class="p-3 text-center">
+                                                                <div class="text-xs text-gray-600 mb-1">"Worst case, real-time"</div>
+                                                                <div class="font-semibold">{format!("${:.4}", realtime)}</div>
+                                                                <div class="text-xs text-gray-500">
+                                                                    {format!("input ${:.4} + output ${:.4}", estimate.input_cost, estimate.worst_output_cost)}
+                                                                </div>
+                                                            </div>
+                                                        </Card>
+                                                    </GridItem>
+                                                    <GridItem>
+                                                        <Card class="bg-gray-100">
+                                                            <div class="p-3 text-center">
+                                                                <div class="text-xs text-gray-600 mb-1">"Worst case, batch"</div>
+                                                                <div class="font-semibold text-blue-500">{format!("${:.4}", estimate.batch_cost)}</div>
+                                                                <div class="text-xs text-gray-500">
+                                                                    {(estimate.lines_without_batch_price > 0).then(|| format!(
+                                                                        "{} lines have no batch price and count at real-time",
+                                                                        estimate.lines_without_batch_price,
+                                                                    ))}
+                                                                </div>
+                                                            </div>
+                                                        </Card>
+                                                    </GridItem>
+                                                </Grid>
+
+                                                {(error_count > 0).then(move || view! {
+                                                    <div class="mt-4">
+                                                        <MessageBar intent=MessageBarIntent::Warning>
+                                                            <MessageBarBody>
+                                                                <div class="max-h-40 overflow-auto text-sm">
+                                                                    {errors.into_iter().map(|err| view! {
+                                                                        <div>{format!("Line {}: {}", err.line, err.message)}</div>
+                                                                    }).collect_view()}
+                                                                </div>
+                                                            </MessageBarBody>
+                                                        </MessageBar>
+                                                    </div>
+                                                })}
+
+                                                {(line_count > MAX_ROWS).then(|| view! {
+                                                    <div class="text-sm text-gray-600 mt-4">
+                                                        {format!("Showing the first {} of {} lines", MAX_ROWS, line_count)}
+                                                    </div>
+                                                })}
+                                                <div class="overflow-x-auto max-h-96 mt-4">
+                                                    <table class="w-full border-collapse text-sm">
+                                                        <thead>
+                                                            <tr class="bg-gray-200">
+                                                                <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Line"</th>
+                                                                <th class="border border-gray-300 px-3 py-2 text-left font-semibold">"Custom ID"</th>
+                                                                <th class="border border-gray-300 px-3 py-2 text-left font-semibold">"Model"</th>
+                                                                <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Input Tokens"</th>
+                                                                <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Max Output"</th>
+                                                                <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Worst Case"</th>
+                                                                <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Batch"</th>
+                                                            </tr>
+                                                        </thead>
+                                                        <tbody>
+                                                            {lines.into_iter().take(MAX_ROWS).map(|line| view! {
+                                                                <tr class="hover:bg-gray-50">
+                                                                    <td class="border border-gray-300 px-3 py-2 text-right">{line.line}</td>
+                                                                    <td class="border border-gray-300 px-3 py-2 font-mono">{line.custom_id}</td>
+                                                                    <td class="border border-gray-300 px-3 py-2 font-mono">{line.model}</td>
+                                                                    <td class="border border-gray-300 px-3 py-2 text-right">{line.input_tokens}</td>
+                                                                    <td class="border border-gray-300 px-3 py-2 text-right">
+                                                                        {line.max_output_tokens}
+                                                                        {line.limit_from_model.then_some(" (model limit)")}
+                                                                    </td>
+                                                                    <td class="border border-gray-300 px-3 py-2 text-right">
+                                                                        {format!("${:.6}", line.input_cost + line.worst_output_cost)}
+                                                                    </td>
+                                                                    <td class="border border-gray-300 px-3 py-2 text-right">
+                                                                        {line.batch_cost.map(|c| format!("${:.6}", c)).unwrap_or_else(|| "N/A".to_string())}
+                                                                    </td>
+                                                                </tr>
+                                                            }).collect_view()}
+                                                        </tbody>
+                                                    </table>
+                                                </div>
+                                            }
+                                        }}
+                                    </div>
+                                </Card>
+                            </div>
+                        }.into_any()
+                    }
+                }}
+            </div>
+        </div>
+    }
+}
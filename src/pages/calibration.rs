@@ -0,0 +1,242 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use thaw::*;
+use web_sys::FileList;
+
+use crate::components::PageHeader;
+use crate::utils::calibration::{
+    calibrate, load_calibration, parse_calibration_log, save_calibration, Calibration,
+    CalibrationSample, CorrectionFactor, MIN_SAMPLES,
+};
+use crate::utils::preload_tokenizer;
+use crate::utils::upload::{file_list_to_vec, read_file_text};
+
+/// Unreadable entries listed per file; the rest are only counted.
+const MAX_ERRORS: usize = 20;
+
+#[component]
+pub fn CalibrationPage() -> impl IntoView {
+    let imports = RwSignal::new(Vec::<(String, usize, Vec<String>)>::new());
+    let samples = RwSignal::new(Vec::<CalibrationSample>::new());
+    let saved = RwSignal::new(load_calibration());
+    let (upload_error, set_upload_error) = signal(Option::<String>::None);
+    let (save_error, set_save_error) = signal(Option::<String>::None);
+
+    Effect::new(move |_| {
+        spawn_local(async move {
+            preload_tokenizer();
+        });
+    });
+
+    let on_upload = move |files: FileList| {
+        let files = file_list_to_vec(&files);
+        spawn_local(async move {
+            set_upload_error.set(None);
+            for file in files {
+                let name = file.name();
+                let parsed = read_file_text(&file)
+                    .await
+                    .and_then(|text| parse_calibration_log(&name, &text));
+                match parsed {
+                    Ok(import) => {
+                        imports
+                            .update(|list| list.push((name, import.samples.len(), import.errors)));
+                        samples.update(|list| list.extend(import.samples));
+                    }
+                    Err(e) => set_upload_error.set(Some(format!("{}: {}", name, e))),
+                }
+            }
+        });
+    };
+
+    let report = Memo::new(move |_| samples.with(|samples| calibrate(samples)));
+
+    let store = move |calibration: Calibration| {
+        let result = save_calibration(&calibration);
+        saved.set(calibration);
+        set_save_error.set(
+            result
+                .err()
+                .map(|e| format!("Failed to save calibration: {:?}", e)),
+        );
+    };
+
+    view! {
+        <div class="min-h-screen bg-gray-100 p-6">
+            <div class="max-w-7xl mx-auto">
+                <PageHeader
+                    title="Tokenizer Calibration"
+                    subtitle="Compare token estimates with provider-reported usage and correct the calculator"
+                />
+
+                <div class="space-y-4">
+                    <Card class="w-full">
+                        <div class="p-5">
+                            <h3 class="text-xl font-semibold mb-4">"Request Logs"</h3>
+                            <Space vertical=true>
+                                <div class="text-sm text-gray-600">
+                                    "JSONL, JSON or CSV logs whose entries carry the request (messages, system, contents or prompt) and the provider's usage block: {request, response} pairs, request bodies with a usage block, or LiteLLM spend logs with messages and prompt_tokens."
+                                </div>
+                                <div class="flex items-center gap-3">
+                                    <Upload multiple=true accept=".jsonl,.json,.csv" custom_request=on_upload>
+                                        <Button>"Import logs"</Button>
+                                    </Upload>
+                                    <Button
+                                        disabled=Signal::derive(move || imports.get().is_empty())
+                                        on_click=move |_| {
+                                            imports.set(Vec::new());
+                                            samples.set(Vec::new());
+                                        }
+                                    >
+                                        "Clear"
+                                    </Button>
+                                </div>
+                                {move || upload_error.get().map(|err| view! {
+                                    <MessageBar intent=MessageBarIntent::Warning>
+                                        <MessageBarBody>{err}</MessageBarBody>
+                                    </MessageBar>
+                                })}
+                                {move || imports.get().into_iter().map(|(name, count, errors)| {
+                                    let hidden = errors.len().saturating_sub(MAX_ERRORS);
+                                    let error_count = errors.len();
+                                    view! {
+                                        <div class="border-b border-gray-200 py-1 text-sm">
+                                            <div class="flex justify-between">
+                                                <span class="font-mono">{name}</span>
+                                                <span class="text-gray-600">
+                                                    {format!("{} samples · {} skipped", count, error_count)}
+                                                </span>
+                                            </div>
+                                            <div class="text-xs text-red-600">
+                                                {errors.into_iter().take(MAX_ERRORS).map(|e| view! { <div>{e}</div> }).collect_view()}
+                                                {(hidden > 0).then(|| format!("...and {} more", hidden))}
+                                            </div>
+                                        </div>
+                                    }
+                                }).collect_view()}
+                            </Space>
+                        </div>
+                    </Card>
+
+                    <Card class="w-full">
+                        <div class="p-5">
+                            <h3 class="text-xl font-semibold mb-4">"Calibration Report"</h3>
+                            {move || save_error.get().map(|err| view! {
+                                <MessageBar intent=MessageBarIntent::Error>
+                                    <MessageBarBody>{err}</MessageBarBody>
+                                </MessageBar>
+                            })}
+                            {move || {
+                                let rows = report.get();
+                                if rows.is_empty() {
+                                    return view! {
+                                        <div class="text-sm text-gray-500">"Import logs to compare estimates with billed prompt tokens."</div>
+                                    }.into_any();
+                                }
+                                view! {
+                                    <div class="overflow-x-auto">
+                                        <table class="w-full text-sm">
+                                            <thead>
+                                                <tr class="border-b border-gray-300 text-left">
+                                                    <th class="py-2 pr-4">"Model family"</th>
+                                                    <th class="py-2 pr-4 text-right">"Requests"</th>
+                                                    <th class="py-2 pr-4 text-right">"Estimated"</th>
+                                                    <th class="py-2 pr-4 text-right">"Reported"</th>
+                                                    <th class="py-2 pr-4 text-right">"Error"</th>
+                                                    <th class="py-2 pr-4 text-right">"Factor"</th>
+                                                    <th class="py-2 pr-4 text-right">"Corrected error"</th>
+                                                    <th class="py-2"></th>
+                                                </tr>
+                                            </thead>
+                                            <tbody>
+                                                {rows.into_iter().map(|row| {
+                                                    let family = row.family.clone();
+                                                    let correction = CorrectionFactor {
+                                                        factor: row.factor,
+                                                        samples: row.samples,
+                                                    };
+                                                    let is_saved = {
+                                                        let family = family.clone();
+                                                        move || saved.with(|calibration| calibration.get(&family) == Some(&correction))
+                                                    };
+                                                    let button_label = {
+                                                        let is_saved = is_saved.clone();
+                                                        move || if is_saved() { "Applied" } else { "Apply in calculator" }
+                                                    };
+                                                    view! {
+                                                        <tr class="border-b border-gray-200">
+                                                            <td class="py-2 pr-4 font-mono">
+                                                                {row.family}
+                                                                {(row.samples < MIN_SAMPLES).then(|| view! {
+                                                                    <span class="ml-2 text-xs text-orange-600">"few samples"</span>
+                                                                })}
+                                                            </td>
+                                                            <td class="py-2 pr-4 text-right">{row.samples}</td>
+                                                            <td class="py-2 pr-4 text-right">{row.estimated_tokens}</td>
+                                                            <td class="py-2 pr-4 text-right">{row.reported_tokens}</td>
+                                                            <td class="py-2 pr-4 text-right">{format!("{:.1}%", row.error_percent)}</td>
+                                                            <td class="py-2 pr-4 text-right font-semibold">{format!("×{:.3}", row.factor)}</td>
+                                                            <td class="py-2 pr-4 text-right">{format!("{:.1}%", row.corrected_error_percent)}</td>
+                                                            <td class="py-2 text-right">
+                                                                <Button
+                                                                    size=ButtonSize::Small
+                                                                    disabled=Signal::derive(is_saved)
+                                                                    on_click=move |_| {
+                                                                        let mut calibration = saved.get_untracked();
+                                                                        calibration.insert(family.clone(), correction);
+                                                                        store(calibration);
+                                                                    }
+                                                                >
+                                                                    {button_label}
+                                                                </Button>
+                                                            </td>
+                                                        </tr>
+                                                    }
+                                                }).collect_view()}
+                                            </tbody>
+                                        </table>
+                                    </div>
+                                }.into_any()
+                            }}
+                        </div>
+                    </Card>
+
+                    <Card class="w-full">
+                        <div class="p-5">
+                            <h3 class="text-xl font-semibold mb-4">"Applied Corrections"</h3>
+                            {move || {
+                                let calibration = saved.get();
+                                if calibration.is_empty() {
+                                    return view! {
+                                        <div class="text-sm text-gray-500">"The calculator uses raw tokenizer estimates for every model."</div>
+                                    }.into_any();
+                                }
+                                calibration.into_iter().map(|(family, correction)| {
+                                    let remove_family = family.clone();
+                                    view! {
+                                        <div class="flex justify-between items-center border-b border-gray-200 py-2 text-sm">
+                                            <span>
+                                                <span class="font-mono">{family}</span>
+                                                {format!(" ×{:.3} from {} requests", correction.factor, correction.samples)}
+                                            </span>
+                                            <Button
+                                                size=ButtonSize::Small
+                                                on_click=move |_| {
+                                                    let mut calibration = saved.get_untracked();
+                                                    calibration.remove(&remove_family);
+                                                    store(calibration);
+                                                }
+                                            >
+                                                "Remove"
+                                            </Button>
+                                        </div>
+                                    }
+                                }).collect_view().into_any()
+                            }}
+                        </div>
+                    </Card>
+                </div>
+            </div>
+        </div>
+    }
+}
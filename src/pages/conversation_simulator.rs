@@ -0,0 +1,307 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use std::collections::HashMap;
+use thaw::*;
+
+use crate::components::{AgentSimulator, ChartSeries, LineChart, ModelSelect, PageHeader};
+use crate::models::ModelPricing;
+use crate::utils::conversation::{simulate_conversation, ConversationPlan};
+use crate::utils::{estimate_tokens_for_model, fetch_model_prices, preload_tokenizer};
+
+fn format_dollars(value: f64) -> String {
+    format!("${:.4}", value)
+}
+
+fn format_count(value: f64) -> String {
+    format!("{:.0}", value)
+}
+
+#[component]
+pub fn ConversationSimulatorPage() -> impl IntoView {
+    let (model_prices, set_model_prices) = signal(HashMap::<String, ModelPricing>::new());
+    let (loading, set_loading) = signal(true);
+    let (error, set_error) = signal(Option::<String>::None);
+
+    let mode = RwSignal::new(String::from("chat"));
+    let selected_model = RwSignal::new(String::from("claude-sonnet-4-5"));
+    let (system_prompt, set_system_prompt) = signal(String::new());
+    let turns = RwSignal::new(20_usize);
+    let user_tokens = RwSignal::new(150_usize);
+    let assistant_tokens = RwSignal::new(400_usize);
+    let use_cache = RwSignal::new(false);
+
+    Effect::new(move |_| {
+        spawn_local(async move {
+            preload_tokenizer();
+        });
+    });
+
+    Effect::new(move |_| {
+        spawn_local(async move {
+            match fetch_model_prices().await {
+                Ok(prices) => {
+                    set_model_prices.set(prices);
+                    set_loading.set(false);
+                }
+                Err(e) => {
+                    set_error.set(Some(format!("Failed to load model prices: {}", e)));
+                    set_loading.set(false);
+                }
+            }
+        });
+    });
+
+    let system_tokens =
+        Memo::new(move |_| estimate_tokens_for_model(&system_prompt.get(), &selected_model.get()));
+
+    let simulate = move |cache: bool| {
+        let prices = model_prices.get();
+        let pricing = prices.get(&selected_model.get())?;
+        let plan = ConversationPlan {
+            turns: turns.get(),
+            system_tokens: system_tokens.get(),
+            user_tokens: user_tokens.get(),
+            assistant_tokens: assistant_tokens.get(),
+            use_cache: cache,
+        };
+        Some(simulate_conversation(&plan, pricing))
+    };
+    let simulation = Memo::new(move |_| simulate(use_cache.get()));
+    let baseline = Memo::new(move |_| simulate(false));
+
+    let has_cache_prices = move || {
+        model_prices.with(|prices| {
+            prices
+                .get(&selected_model.get())
+                .is_some_and(|p| p.cache_read_input_token_cost.is_some())
+        })
+    };
+
+    let x_labels = Signal::derive(move || {
+        simulation
+            .get()
+            .unwrap_or_default()
+            .iter()
+            .map(|t| t.turn.to_string())
+            .collect::<Vec<_>>()
+    });
+
+    let cost_series = Signal::derive(move || {
+        let turns = simulation.get().unwrap_or_default();
+        let mut series = vec![ChartSeries {
+            label: if use_cache.get() {
+                "With cache".to_string()
+            } else {
+                "Cumulative cost".to_string()
+            },
+            color: "#3b82f6",
+            values: turns.iter().map(|t| t.cumulative_cost).collect(),
+        }];
+        if use_cache.get() {
+            series.push(ChartSeries {
+                label: "Without cache".to_string(),
+                color: "#ef4444",
+                values: baseline
+                    .get()
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|t| t.cumulative_cost)
+                    .collect(),
+            });
+        }
+        series
+    });
+
+    let token_series = Signal::derive(move || {
+        let turns = simulation.get().unwrap_or_default();
+        vec![
+            ChartSeries {
+                label: "Cumulative tokens".to_string(),
+                color: "#8b5cf6",
+                values: turns.iter().map(|t| t.cumulative_tokens as f64).collect(),
+            },
+            ChartSeries {
+                label: "Input tokens per turn".to_string(),
+                color: "#10b981",
+                values: turns.iter().map(|t| t.input_tokens as f64).collect(),
+            },
+        ]
+    });
+
+    view! {
+        <div class="min-h-screen bg-gray-100 p-6">
+            <div class="max-w-7xl mx-auto">
+                <PageHeader
+                    title="Conversation & Agent Cost Simulator"
+                    subtitle="See how re-sent history and tool results make multi-step sessions grow in cost"
+                />
+
+                {move || {
+                    if loading.get() {
+                        view! {
+                            <div class="text-center py-12">
+                                <Spinner size=SpinnerSize::Huge />
+                                <p class="mt-4 text-gray-600">"Loading model prices..."</p>
+                            </div>
+                        }.into_any()
+                    } else if let Some(err) = error.get() {
+                        view! {
+                            <MessageBar intent=MessageBarIntent::Error>
+                                <MessageBarBody>
+                                    <div>
+                                        <strong>"Error: "</strong>
+                                        {err}
+                                    </div>
+                                </MessageBarBody>
+                            </MessageBar>
+                        }.into_any()
+                    } else {
+                        view! {
+                            <TabList selected_value=mode class="mb-4">
+                                <Tab value="chat">"Chat"</Tab>
+                                <Tab value="agent">"Agent"</Tab>
+                            </TabList>
+
+                            {move || {
+                                if mode.get() == "agent" {
+                                    view! {
+                                        <AgentSimulator model_prices=model_prices selected_model=selected_model />
+                                    }.into_any()
+                                } else {
+                                    view! {
+                                        <div class="grid grid-cols-3 gap-4">
+                                            <Card class="w-full">
+                                                <div class="p-5">
+                                                    <h3 class="text-xl font-semibold mb-4">"Session"</h3>
+                                                    <Space vertical=true>
+                                                        <ModelSelect model_prices=model_prices selected=selected_model />
+                                                        <div class="text-sm">
+                                                            <div class="text-gray-600 mb-1">"Turns"</div>
+                                                            <SpinButton<usize> value=turns step_page=5 min=1 max=500 />
+                                                        </div>
+                                                        <div class="text-sm">
+                                                            <div class="text-gray-600 mb-1">"Average user message (tokens)"</div>
+                                                            <SpinButton<usize> value=user_tokens step_page=50 min=0 />
+                                                        </div>
+                                                        <div class="text-sm">
+                                                            <div class="text-gray-600 mb-1">"Average assistant message (tokens)"</div>
+                                                            <SpinButton<usize> value=assistant_tokens step_page=50 min=0 />
+                                                        </div>
+                                                        <div class="text-sm">
+                                                            <div class="text-gray-600 mb-1">
+                                                                {move || format!("System prompt ({} tokens)", system_tokens.get())}
+                                                            </div>
+                                                            <Textarea
+                                                                class="w-full"
+                                                                placeholder="Optional system prompt..."
+                                                                on:input=move |ev| {
+                                                                    set_system_prompt.set(event_target_value(&ev));
+                                                                }
+                                                            />
+                                                        </div>
+                                                        <Switch checked=use_cache label="Apply prompt caching to the repeated prefix" />
+                                                        {move || (use_cache.get() && !has_cache_prices()).then(|| view! {
+                                                            <MessageBar intent=MessageBarIntent::Warning>
+                                                                <MessageBarBody>
+                                                                    "This model has no cache prices in the catalogue; regular input prices are used."
+                                                                </MessageBarBody>
+                                                            </MessageBar>
+                                                        })}
+                                                    </Space>
+                                                </div>
+                                            </Card>
+
+                                            <Card class="w-full col-span-2">
+                                                <div class="p-5">
+                                                    <h3 class="text-xl font-semibold mb-4">"Session Cost"</h3>
+                                                    {move || {
+                                                        let turns = simulation.get().unwrap_or_default();
+                                                        let last = turns.last().cloned().unwrap_or_default();
+                                                        let baseline_cost = baseline.get().unwrap_or_default().last().map(|t| t.cumulative_cost).unwrap_or(0.0);
+                                                        let single_shot = turns.first().map(|t| t.cost).unwrap_or(0.0) * turns.len() as f64;
+                                                        view! {
+                                                            <Grid cols=3 x_gap=12>
+                                                                <GridItem>
+                                                                    <Card class="bg-gray-100">
+                                                                        <div class="p-3 text-center">
+                                                                            <div class="text-xs text-gray-600 mb-1">"Total tokens"</div>
+                                                                            <div class="font-semibold">{last.cumulative_tokens}</div>
+                                                                        </div>
+                                                                    </Card>
+                                                                </GridItem>
+                                                                <GridItem>
+                                                                    <Card class="bg-gray-100">
+                                                                        <div class="p-3 text-center">
+                                                                            <div class="text-xs text-gray-600 mb-1">"Session cost"</div>
+                                                                            <div class="font-semibold text-blue-500">{format!("${:.4}", last.cumulative_cost)}</div>
+                                                                        </div>
+                                                                    </Card>
+                                                                </GridItem>
+                                                                <GridItem>
+                                                                    <Card class="bg-gray-100">
+                                                                        <div class="p-3 text-center">
+                                                                            <div class="text-xs text-gray-600 mb-1">
+                                                                                {if use_cache.get() { "Cache savings" } else { "vs. independent requests" }}
+                                                                            </div>
+                                                                            <div class="font-semibold">
+                                                                                {if use_cache.get() {
+                                                                                    format!("${:.4}", baseline_cost - last.cumulative_cost)
+                                                                                } else {
+                                                                                    format!("{:.1}x", if single_shot > 0.0 { last.cumulative_cost / single_shot } else { 0.0 })
+                                                                                }}
+                                                                            </div>
+                                                                        </div>
+                                                                    </Card>
+                                                                </GridItem>
+                                                            </Grid>
+                                                        }
+                                                    }}
+                                                    <h4 class="font-semibold mt-4 mb-2">"Cumulative cost"</h4>
+                                                    <LineChart series=cost_series x_labels=x_labels y_format=format_dollars />
+                                                    <h4 class="font-semibold mt-4 mb-2">"Tokens"</h4>
+                                                    <LineChart series=token_series x_labels=x_labels y_format=format_count />
+                                                </div>
+                                            </Card>
+
+                                            <Card class="w-full col-span-3">
+                                                <div class="p-5">
+                                                    <h3 class="text-xl font-semibold mb-4">"Per Turn"</h3>
+                                                    <div class="overflow-x-auto max-h-96">
+                                                        <table class="w-full border-collapse text-sm">
+                                                            <thead>
+                                                                <tr class="bg-gray-200">
+                                                                    <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Turn"</th>
+                                                                    <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Input Tokens"</th>
+                                                                    <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Cached Tokens"</th>
+                                                                    <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Output Tokens"</th>
+                                                                    <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Turn Cost"</th>
+                                                                    <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Cumulative Cost"</th>
+                                                                </tr>
+                                                            </thead>
+                                                            <tbody>
+                                                                {move || simulation.get().unwrap_or_default().into_iter().map(|t| view! {
+                                                                    <tr class="hover:bg-gray-50">
+                                                                        <td class="border border-gray-300 px-3 py-2 text-right">{t.turn}</td>
+                                                                        <td class="border border-gray-300 px-3 py-2 text-right">{t.input_tokens}</td>
+                                                                        <td class="border border-gray-300 px-3 py-2 text-right">{t.cached_tokens}</td>
+                                                                        <td class="border border-gray-300 px-3 py-2 text-right">{t.output_tokens}</td>
+                                                                        <td class="border border-gray-300 px-3 py-2 text-right">{format!("${:.6}", t.cost)}</td>
+                                                                        <td class="border border-gray-300 px-3 py-2 text-right">{format!("${:.6}", t.cumulative_cost)}</td>
+                                                                    </tr>
+                                                                }).collect_view()}
+                                                            </tbody>
+                                                        </table>
+                                                    </div>
+                                                </div>
+                                            </Card>
+                                        </div>
+                                    }.into_any()
+                                }
+                            }}
+                        }.into_any()
+                    }
+                }}
+            </div>
+        </div>
+    }
+}
@@ -4,14 +4,28 @@ use leptos_use::use_debounce_fn_with_arg;
 use std::collections::HashMap;
 use thaw::*;
 
+use crate::components::{
+    BatchComparison, BudgetSolver, ModelComparison, ModelQueryInput, ModelRecommender,
+    OutputUncertainty, PageHeader, TrafficProjectionCard,
+};
 use crate::models::ModelPricing;
-use crate::utils::{estimate_tokens, fetch_model_prices, preload_tokenizer};
+use crate::utils::calibration::{apply_correction, correction_for, load_calibration};
+use crate::utils::date::today;
+use crate::utils::model_query::{parse_model_query, ModelQuery};
+use crate::utils::model_search::compare_ranked;
+use crate::utils::projection::TrafficPlan;
+use crate::utils::{estimate_tokens_for_model, fetch_model_prices, preload_tokenizer};
 
 #[component]
 pub fn HomePage() -> impl IntoView {
     let (model_prices, set_model_prices) = signal(HashMap::<String, ModelPricing>::new());
     let (selected_model, set_selected_model) = signal(String::from("claude-sonnet-4-5"));
     let (model_search, set_model_search) = signal(String::new());
+    let comparison_models = RwSignal::new(Vec::<String>::new());
+    let traffic_plan = RwSignal::new(TrafficPlan::default());
+    let use_batch = RwSignal::new(false);
+    let calibration = load_calibration();
+    let use_calibration = RwSignal::new(true);
     let (input_text, set_input_text) = signal(String::new());
     let (output_text, set_output_text) = signal(String::new());
     let (debounced_input_text, set_debounced_input_text) = signal(String::new());
@@ -64,23 +78,33 @@ pub fn HomePage() -> impl IntoView {
         });
     });
 
+    let correction = Memo::new(move |_| correction_for(&calibration, &selected_model.get()));
+
     let calculation = move || {
         let input = debounced_input_text.get();
         let output = debounced_output_text.get();
         let model_name = selected_model.get();
 
-        let input_tokens = estimate_tokens(&input);
-        let output_tokens = estimate_tokens(&output);
+        let mut input_tokens = estimate_tokens_for_model(&input, &model_name);
+        let output_tokens = estimate_tokens_for_model(&output, &model_name);
+        // Factors are learned from billed prompt tokens, so only the input
+        // estimate is corrected.
+        if let Some((_, correction)) = correction.get().filter(|_| use_calibration.get()) {
+            input_tokens = apply_correction(input_tokens, correction.factor);
+        }
         let total_tokens = input_tokens + output_tokens;
 
         let prices = model_prices.get();
         let model_pricing = prices.get(&model_name);
 
         let (input_cost, output_cost, total_cost) = if let Some(pricing) = model_pricing {
-            let input_cost = pricing.input_cost_per_token.unwrap_or(0.0) * input_tokens as f64;
-            let output_cost = pricing.output_cost_per_token.unwrap_or(0.0) * output_tokens as f64;
-            let total = input_cost + output_cost;
-            (input_cost, output_cost, total)
+            let batch_cost = use_batch
+                .get()
+                .then(|| pricing.batch_request_cost(input_tokens, output_tokens))
+                .flatten();
+            let (input_cost, output_cost) =
+                batch_cost.unwrap_or_else(|| pricing.request_cost(input_tokens, output_tokens));
+            (input_cost, output_cost, input_cost + output_cost)
         } else {
             (0.0, 0.0, 0.0)
         };
@@ -95,45 +119,58 @@ pub fn HomePage() -> impl IntoView {
         )
     };
 
-    let filtered_models = move || {
-        let search = model_search.get().to_lowercase();
-        let prices = model_prices.get();
-        let mut model_list: Vec<String> = if search.is_empty() {
-            prices.keys().cloned().collect()
-        } else {
+    // While a query has a syntax error, keep filtering by the last valid one.
+    let model_query = Memo::new(move |previous: Option<&ModelQuery>| {
+        parse_model_query(&model_search.get())
+            .unwrap_or_else(|_| previous.cloned().unwrap_or_default())
+    });
+    let today = today();
+
+    // Models grouped by provider. Searching lists the groups and their
+    // models best match first; otherwise both are alphabetical.
+    let grouped_models = Memo::new(move |_| {
+        let query = model_query.get();
+        let mut ranked: Vec<(u32, String, String)> = model_prices.with(|prices| {
             prices
-                .keys()
-                .filter(|k| k.to_lowercase().contains(&search))
-                .cloned()
+                .iter()
+                .filter(|(_, pricing)| !pricing.is_embedding())
+                .filter_map(|(k, pricing)| {
+                    let rank = query.rank(k, pricing, &today)?;
+                    let provider = pricing
+                        .litellm_provider
+                        .clone()
+                        .unwrap_or_else(|| "other".to_string());
+                    Some((rank, k.clone(), provider))
+                })
                 .collect()
-        };
-        model_list.sort();
-        model_list
-    };
+        });
+        if query.has_text() {
+            ranked.sort_by(|a, b| compare_ranked((a.0, &a.1), (b.0, &b.1)));
+        } else {
+            ranked.sort_by(|a, b| a.1.cmp(&b.1));
+        }
+
+        let mut groups: Vec<(String, Vec<String>)> = Vec::new();
+        for (_, model, provider) in ranked {
+            match groups.iter_mut().find(|(name, _)| *name == provider) {
+                Some((_, models)) => models.push(model),
+                None => groups.push((provider, vec![model])),
+            }
+        }
+        if !query.has_text() {
+            groups.sort_by(|a, b| a.0.cmp(&b.0));
+        }
+        groups
+    });
 
     view! {
         <ConfigProvider>
             <div class="min-h-screen bg-gray-100 p-6">
                 <div class="max-w-7xl mx-auto">
-                    <div class="text-center mb-8">
-                        <h1 class="text-4xl font-bold mb-2">
-                            "LLM Cost Estimator"
-                        </h1>
-                        <p class="text-gray-600 text-lg">
-                            "Calculate token usage and costs for various LLM models"
-                        </p>
-                        <div class="mt-4">
-                            <a href="/" class="mr-4 text-blue-500 hover:text-blue-700">
-                                "Cost Calculator"
-                            </a>
-                            <a href="/list-model-prices" class="mr-4 text-blue-500 hover:text-blue-700">
-                                "Model Price List"
-                            </a>
-                            <a href="/info" class="text-blue-500 hover:text-blue-700">
-                                "Info"
-                            </a>
-                        </div>
-                    </div>
+                    <PageHeader
+                        title="LLM Cost Estimator"
+                        subtitle="Calculate token usage and costs for various LLM models"
+                    />
 
                     {move || {
                         if loading.get() {
@@ -184,12 +221,10 @@ pub fn HomePage() -> impl IntoView {
                                                     "Select Model"
                                                 </h3>
                                                 <Space vertical=true>
-                                                    <Input
-                                                        class="w-full"
-                                                        placeholder="Search models..."
-                                                        on:input=move |ev| {
-                                                            set_model_search.set(event_target_value(&ev));
-                                                        }
+                                                    <ModelQueryInput
+                                                        query=model_search
+                                                        set_query=set_model_search
+                                                        placeholder="Search models, or filter like vision input<3/1M ctx>=200k"
                                                     />
                                                     <select
                                                         class="w-full h-80 p-2 border border-gray-300 rounded text-sm"
@@ -199,14 +234,18 @@ pub fn HomePage() -> impl IntoView {
                                                         }
                                                     >
                                                         {move || {
-                                                            filtered_models().into_iter().map(|model| {
-                                                                let model_clone = model.clone();
-                                                                let model_clone2 = model.clone();
-                                                                view! {
-                                                                    <option value=model selected=move || selected_model.get() == model_clone>
-                                                                        {model_clone2}
-                                                                    </option>
-                                                                }
+                                                            grouped_models.get().into_iter().map(|(provider, models)| view! {
+                                                                <optgroup label=provider>
+                                                                    {models.into_iter().map(|model| {
+                                                                        let model_clone = model.clone();
+                                                                        let model_clone2 = model.clone();
+                                                                        view! {
+                                                                            <option value=model selected=move || selected_model.get() == model_clone>
+                                                                                {model_clone2}
+                                                                            </option>
+                                                                        }
+                                                                    }).collect_view()}
+                                                                </optgroup>
                                                             }).collect_view()
                                                         }}
                                                     </select>
@@ -232,6 +271,20 @@ pub fn HomePage() -> impl IntoView {
                                                                                     <strong>{format!("${:.6}", pricing.output_cost_per_token.unwrap_or(0.0))}</strong>
                                                                                 </div>
                                                                             </GridItem>
+                                                                            {pricing.has_batch_pricing().then(|| view! {
+                                                                                <GridItem>
+                                                                                    <div class="text-sm">
+                                                                                        <span class="text-gray-600">"Batch Input: "</span>
+                                                                                        <strong>{format!("${:.6}", pricing.input_cost_per_token_batches.or(pricing.input_cost_per_token).unwrap_or(0.0))}</strong>
+                                                                                    </div>
+                                                                                </GridItem>
+                                                                                <GridItem>
+                                                                                    <div class="text-sm">
+                                                                                        <span class="text-gray-600">"Batch Output: "</span>
+                                                                                        <strong>{format!("${:.6}", pricing.output_cost_per_token_batches.or(pricing.output_cost_per_token).unwrap_or(0.0))}</strong>
+                                                                                    </div>
+                                                                                </GridItem>
+                                                                            })}
                                                                             {pricing.max_tokens.map(|max| view! {
                                                                                 <GridItem>
                                                                                     <div class="text-sm">
@@ -241,6 +294,22 @@ pub fn HomePage() -> impl IntoView {
                                                                                 </GridItem>
                                                                             })}
                                                                         </Grid>
+                                                                        <div class="mt-3">
+                                                                            <Button
+                                                                                size=ButtonSize::Small
+                                                                                disabled=Signal::derive(move || comparison_models.get().contains(&selected_model.get()))
+                                                                                on_click=move |_| {
+                                                                                    let model = selected_model.get_untracked();
+                                                                                    comparison_models.update(|list| {
+                                                                                        if !list.contains(&model) {
+                                                                                            list.push(model);
+                                                                                        }
+                                                                                    });
+                                                                                }
+                                                                            >
+                                                                                "Add to comparison"
+                                                                            </Button>
+                                                                        </div>
                                                                     </div>
                                                                 </Card>
                                                             }.into_any()
@@ -311,10 +380,44 @@ pub fn HomePage() -> impl IntoView {
                                                                 </GridItem>
                                                             </Grid>
 
+                                                            {move || correction.get().map(|(family, correction)| view! {
+                                                                <div class="text-xs text-gray-600">
+                                                                    <div class="flex items-center justify-between">
+                                                                        <span>
+                                                                            {format!(
+                                                                                "Input calibrated ×{:.3} for the {} family from {} logged requests",
+                                                                                correction.factor,
+                                                                                family,
+                                                                                correction.samples,
+                                                                            )}
+                                                                        </span>
+                                                                        <Switch checked=use_calibration label="Apply" />
+                                                                    </div>
+                                                                    // The cards below the traffic projection tokenize
+                                                                    // the raw text for every model they compare.
+                                                                    {move || use_calibration.get().then(|| view! {
+                                                                        <div class="mt-1 text-gray-500">
+                                                                            "Applies to this breakdown and the traffic projection; the output range, batch, budget, recommendation and comparison cards are uncalibrated estimates."
+                                                                        </div>
+                                                                    })}
+                                                                </div>
+                                                            })}
+
                                                             <Divider />
 
                                                             <div>
-                                                                <h4 class="font-semibold mb-3">"Cost Breakdown"</h4>
+                                                                <div class="flex justify-between items-center mb-3">
+                                                                    <h4 class="font-semibold">"Cost Breakdown"</h4>
+                                                                    {move || {
+                                                                        let available = model_prices
+                                                                            .with(|prices| prices.get(&selected_model.get()).is_some_and(|p| p.has_batch_pricing()));
+                                                                        if available {
+                                                                            view! { <Switch checked=use_batch label="Batch pricing" /> }.into_any()
+                                                                        } else {
+                                                                            view! { <span class="text-xs text-gray-500">"No batch pricing listed"</span> }.into_any()
+                                                                        }
+                                                                    }}
+                                                                </div>
                                                                 <Space vertical=true>
                                                                     <div class="flex justify-between text-sm">
                                                                         <span class="text-gray-600">"Input Cost:"</span>
@@ -333,46 +436,6 @@ pub fn HomePage() -> impl IntoView {
                                                                     </div>
                                                                 </Space>
                                                             </div>
-
-                                                            <Divider />
-
-                                                            <div>
-                                                                <h4 class="font-semibold mb-3">"Projections"</h4>
-                                                                <Grid cols=2 x_gap=8 y_gap=8>
-                                                                    <GridItem>
-                                                                        <Card class="bg-gray-100">
-                                                                            <div class="p-3 text-center">
-                                                                                <div class="text-xs text-gray-600 mb-1">"100 requests"</div>
-                                                                                <div class="font-semibold">{format!("${:.4}", total_cost * 100.0)}</div>
-                                                                            </div>
-                                                                        </Card>
-                                                                    </GridItem>
-                                                                    <GridItem>
-                                                                        <Card class="bg-gray-100">
-                                                                            <div class="p-3 text-center">
-                                                                                <div class="text-xs text-gray-600 mb-1">"1,000 requests"</div>
-                                                                                <div class="font-semibold">{format!("${:.2}", total_cost * 1000.0)}</div>
-                                                                            </div>
-                                                                        </Card>
-                                                                    </GridItem>
-                                                                    <GridItem>
-                                                                        <Card class="bg-gray-100">
-                                                                            <div class="p-3 text-center">
-                                                                                <div class="text-xs text-gray-600 mb-1">"10,000 requests"</div>
-                                                                                <div class="font-semibold">{format!("${:.2}", total_cost * 10000.0)}</div>
-                                                                            </div>
-                                                                        </Card>
-                                                                    </GridItem>
-                                                                    <GridItem>
-                                                                        <Card class="bg-gray-100">
-                                                                            <div class="p-3 text-center">
-                                                                                <div class="text-xs text-gray-600 mb-1">"100,000 requests"</div>
-                                                                                <div class="font-semibold">{format!("${:.2}", total_cost * 100000.0)}</div>
-                                                                            </div>
-                                                                        </Card>
-                                                                    </GridItem>
-                                                                </Grid>
-                                                            </div>
                                                         </Space>
                                                     }
                                                 }}
@@ -380,6 +443,63 @@ pub fn HomePage() -> impl IntoView {
                                         </Card>
                                     </div>
                                 </div>
+
+                                <div class="mt-4">
+                                    <TrafficProjectionCard
+                                        cost_per_request=Signal::derive(move || calculation().5)
+                                        plan=traffic_plan
+                                    />
+                                </div>
+
+                                <div class="mt-4">
+                                    <OutputUncertainty
+                                        model_prices=model_prices
+                                        selected_model=selected_model
+                                        input_text=debounced_input_text
+                                        traffic_plan=traffic_plan
+                                    />
+                                </div>
+
+                                <div class="mt-4">
+                                    <BatchComparison
+                                        model_prices=model_prices
+                                        selected_model=selected_model
+                                        input_text=debounced_input_text
+                                        output_text=debounced_output_text
+                                        traffic_plan=traffic_plan
+                                    />
+                                </div>
+
+                                <div class="mt-4">
+                                    <BudgetSolver
+                                        model_prices=model_prices
+                                        selected_model=selected_model
+                                        set_selected_model=set_selected_model
+                                        input_text=debounced_input_text
+                                        output_text=debounced_output_text
+                                        traffic_plan=traffic_plan
+                                    />
+                                </div>
+
+                                <div class="mt-4">
+                                    <ModelRecommender
+                                        model_prices=model_prices
+                                        selected_model=selected_model
+                                        set_selected_model=set_selected_model
+                                        input_text=debounced_input_text
+                                        output_text=debounced_output_text
+                                    />
+                                </div>
+
+                                <div class="mt-4">
+                                    <ModelComparison
+                                        model_prices=model_prices
+                                        models=comparison_models
+                                        input_text=debounced_input_text
+                                        output_text=debounced_output_text
+                                        traffic_plan=traffic_plan
+                                    />
+                                </div>
                             }.into_any()
                         }
                     }}
@@ -1,6 +1,8 @@
 use leptos::prelude::*;
 use thaw::*;
 
+use crate::components::PageHeader;
+
 const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
 const APP_NAME: &str = env!("CARGO_PKG_NAME");
 const LEPTOS_VERSION: &str = env!("LEPTOS_VERSION");
@@ -11,25 +13,10 @@ pub fn InfoPage() -> impl IntoView {
     view! {
         <div class="min-h-screen bg-gray-100 p-6">
             <div class="max-w-4xl mx-auto">
-                <div class="text-center mb-8">
-                    <h1 class="text-4xl font-bold mb-2">
-                        "Application Info"
-                    </h1>
-                    <p class="text-gray-600 text-lg">
-                        "Version and dependency information"
-                    </p>
-                    <div class="mt-4">
-                        <a href="/" class="mr-4 text-blue-500 hover:text-blue-700">
-                            "Cost Calculator"
-                        </a>
-                        <a href="/list-model-prices" class="mr-4 text-blue-500 hover:text-blue-700">
-                            "Model Price List"
-                        </a>
-                        <a href="/info" class="text-blue-500 hover:text-blue-700">
-                            "Info"
-                        </a>
-                    </div>
-                </div>
+                <PageHeader
+                    title="Application Info"
+                    subtitle="Version and dependency information"
+                />
 
                 <div class="space-y-6">
                     <Card>
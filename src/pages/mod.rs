@@ -1,7 +1,27 @@
+pub mod batch_estimator;
+pub mod calibration;
+pub mod conversation_simulator;
 pub mod home;
 pub mod info;
 pub mod model_price_list;
+pub mod rag_planner;
+pub mod request_pricer;
+pub mod routing_simulator;
+pub mod token_tools;
+pub mod transcript;
+pub mod usage;
+pub mod workload_mix;
 
+pub use batch_estimator::BatchEstimatorPage;
+pub use calibration::CalibrationPage;
+pub use conversation_simulator::ConversationSimulatorPage;
 pub use home::HomePage;
 pub use info::InfoPage;
 pub use model_price_list::ModelPriceListPage;
+pub use rag_planner::RagPlannerPage;
+pub use request_pricer::RequestPricerPage;
+pub use routing_simulator::RoutingSimulatorPage;
+pub use token_tools::TokenToolsPage;
+pub use transcript::TranscriptPage;
+pub use usage::UsagePage;
+pub use workload_mix::WorkloadMixPage;
@@ -1,10 +1,48 @@
+use leptos::ev::MouseEvent;
 use leptos::prelude::*;
 use leptos::task::spawn_local;
-use std::collections::HashMap;
+use leptos_router::hooks::{use_location, use_navigate, use_query_map};
+use leptos_router::NavigateOptions;
+use std::collections::{HashMap, HashSet};
 use thaw::*;
 
+use crate::components::{ModelQueryInput, PageHeader};
 use crate::models::ModelPricing;
+use crate::utils::date::today;
 use crate::utils::fetch_model_prices;
+use crate::utils::model_query::{parse_model_query, ModelQuery};
+use crate::utils::model_search::compare_ranked;
+use crate::utils::price_list::{
+    facet_counts, format_sort, parse_sort, sort_models, toggle_sort, FacetValue, PriceListFilters,
+    SortColumn,
+};
+
+fn format_price(price: Option<f64>) -> String {
+    price
+        .map(|p| format!("${:.8}", p))
+        .unwrap_or_else(|| "N/A".to_string())
+}
+
+/// One facet of the filter sidebar: a checkbox per value with its count.
+#[component]
+fn FacetGroup(
+    title: &'static str,
+    selected: RwSignal<HashSet<String>>,
+    #[prop(into)] values: Signal<Vec<FacetValue>>,
+) -> impl IntoView {
+    view! {
+        <div>
+            <h4 class="font-semibold mb-1">{title}</h4>
+            <div class="max-h-48 overflow-y-auto">
+                <CheckboxGroup value=selected>
+                    {move || values.get().into_iter().map(|facet| view! {
+                        <Checkbox value=facet.value label=format!("{} ({})", facet.label, facet.count) />
+                    }).collect_view()}
+                </CheckboxGroup>
+            </div>
+        </div>
+    }
+}
 
 #[component]
 pub fn ModelPriceListPage() -> impl IntoView {
@@ -13,6 +51,36 @@ pub fn ModelPriceListPage() -> impl IntoView {
     let (loading, set_loading) = signal(true);
     let (error, set_error) = signal(Option::<String>::None);
 
+    let providers = RwSignal::new(HashSet::<String>::new());
+    let modes = RwSignal::new(HashSet::<String>::new());
+    let capabilities = RwSignal::new(HashSet::<String>::new());
+    let context_ranges = RwSignal::new(HashSet::<String>::new());
+    let price_ranges = RwSignal::new(HashSet::<String>::new());
+    let hide_deprecated = RwSignal::new(false);
+    let today = today();
+
+    // The sort lives in the `sort` query parameter so a sorted view can be
+    // bookmarked and shared.
+    let query = use_query_map();
+    let location = use_location();
+    let navigate = StoredValue::new(use_navigate());
+    let sort_keys = Memo::new(move |_| parse_sort(&query.read().get("sort").unwrap_or_default()));
+    let on_sort = move |column: SortColumn, ev: MouseEvent| {
+        let keys = toggle_sort(&sort_keys.get_untracked(), column, ev.shift_key());
+        navigate.get_value()(
+            &format!(
+                "{}?sort={}",
+                location.pathname.get_untracked(),
+                format_sort(&keys)
+            ),
+            NavigateOptions {
+                replace: true,
+                scroll: false,
+                ..NavigateOptions::default()
+            },
+        );
+    };
+
     Effect::new(move |_| {
         spawn_local(async move {
             match fetch_model_prices().await {
@@ -28,43 +96,79 @@ pub fn ModelPriceListPage() -> impl IntoView {
         });
     });
 
-    let filtered_models = move || {
-        let search = search_query.get().to_lowercase();
-        let prices = model_prices.get();
-        let mut model_list: Vec<(String, ModelPricing)> = if search.is_empty() {
-            prices.into_iter().collect()
-        } else {
-            prices
-                .into_iter()
-                .filter(|(k, _)| k.to_lowercase().contains(&search))
-                .collect()
-        };
-        model_list.sort_by(|a, b| a.0.cmp(&b.0));
+    // While a query has a syntax error, keep filtering by the last valid one.
+    let model_query = Memo::new(move |previous: Option<&ModelQuery>| {
+        parse_model_query(&search_query.get())
+            .unwrap_or_else(|_| previous.cloned().unwrap_or_default())
+    });
+
+    let searched_models = Memo::new({
+        let today = today.clone();
+        move |_| {
+            let query = model_query.get();
+            model_prices.with(|prices| {
+                let mut ranked: Vec<(u32, String, ModelPricing)> = prices
+                    .iter()
+                    .filter_map(|(k, pricing)| {
+                        let rank = query.rank(k, pricing, &today)?;
+                        Some((rank, k.clone(), pricing.clone()))
+                    })
+                    .collect();
+                ranked.sort_by(|a, b| compare_ranked((a.0, &a.1), (b.0, &b.1)));
+                ranked
+                    .into_iter()
+                    .map(|(_, k, pricing)| (k, pricing))
+                    .collect::<Vec<_>>()
+            })
+        }
+    });
+
+    let filters = Memo::new(move |_| PriceListFilters {
+        providers: providers.get(),
+        modes: modes.get(),
+        capabilities: capabilities.get(),
+        context_ranges: context_ranges.get(),
+        price_ranges: price_ranges.get(),
+        hide_deprecated: hide_deprecated.get(),
+    });
+
+    let facets = Memo::new({
+        let today = today.clone();
+        move |_| {
+            searched_models
+                .with(|models| filters.with(|filters| facet_counts(models, filters, &today)))
+        }
+    });
+
+    let filtered_models = Memo::new(move |_| {
+        let filters = filters.get();
+        let mut model_list: Vec<(String, ModelPricing)> = searched_models
+            .get()
+            .into_iter()
+            .filter(|(_, pricing)| filters.matches(pricing, &today))
+            .collect();
+        // Searching lists the best matches first until a column is sorted.
+        let sort_keys = sort_keys.get();
+        if !sort_keys.is_empty() || !model_query.with(ModelQuery::has_text) {
+            sort_models(&mut model_list, &sort_keys);
+        }
         model_list
+    });
+
+    let clear_filters = move |_| {
+        for facet in [providers, modes, capabilities, context_ranges, price_ranges] {
+            facet.set(HashSet::new());
+        }
+        hide_deprecated.set(false);
     };
 
     view! {
         <div class="min-h-screen bg-gray-100 p-6">
             <div class="max-w-7xl mx-auto">
-                <div class="text-center mb-8">
-                    <h1 class="text-4xl font-bold mb-2">
-                        "Model Price List"
-                    </h1>
-                    <p class="text-gray-600 text-lg">
-                        "Browse and compare pricing for various LLM models"
-                    </p>
-                    <div class="mt-4">
-                        <a href="/" class="mr-4 text-blue-500 hover:text-blue-700">
-                            "Cost Calculator"
-                        </a>
-                        <a href="/list-model-prices" class="mr-4 text-blue-500 hover:text-blue-700">
-                            "Model Price List"
-                        </a>
-                        <a href="/info" class="text-blue-500 hover:text-blue-700">
-                            "Info"
-                        </a>
-                    </div>
-                </div>
+                <PageHeader
+                    title="Model Price List"
+                    subtitle="Browse and compare pricing for various LLM models"
+                />
 
                 {move || {
                     if loading.get() {
@@ -87,77 +191,129 @@ pub fn ModelPriceListPage() -> impl IntoView {
                         }.into_any()
                     } else {
                         view! {
-                            <Card class="bg-white shadow-md rounded-lg w-full">
-                                <div class="p-6">
-                                    <div class="mb-6">
-                                        <Input
-                                            class="w-full"
-                                            placeholder="Search models by name..."
-                                            on:input=move |ev| {
-                                                set_search_query.set(event_target_value(&ev));
-                                            }
-                                        />
+                            <div class="grid grid-cols-4 gap-4 items-start">
+                                <Card class="bg-white shadow-md rounded-lg w-full">
+                                    <div class="p-5">
+                                        <div class="flex justify-between items-center mb-4">
+                                            <h3 class="text-xl font-semibold">"Filters"</h3>
+                                            <Button
+                                                size=ButtonSize::Small
+                                                disabled=Signal::derive(move || filters.with(PriceListFilters::is_empty))
+                                                on_click=clear_filters
+                                            >
+                                                "Clear"
+                                            </Button>
+                                        </div>
+                                        <Space vertical=true>
+                                            <Checkbox
+                                                checked=hide_deprecated
+                                                label=Signal::derive(move || format!("Hide deprecated ({})", facets.with(|f| f.deprecated)))
+                                            />
+                                            <FacetGroup title="Provider" selected=providers values=Signal::derive(move || facets.get().providers) />
+                                            <FacetGroup title="Mode" selected=modes values=Signal::derive(move || facets.get().modes) />
+                                            <FacetGroup title="Capabilities" selected=capabilities values=Signal::derive(move || facets.get().capabilities) />
+                                            <FacetGroup title="Context window" selected=context_ranges values=Signal::derive(move || facets.get().context_ranges) />
+                                            <FacetGroup title="Input price" selected=price_ranges values=Signal::derive(move || facets.get().price_ranges) />
+                                        </Space>
                                     </div>
+                                </Card>
+                                <Card class="bg-white shadow-md rounded-lg w-full col-span-3">
+                                    <div class="p-6">
+                                        <div class="mb-6">
+                                            <ModelQueryInput
+                                                query=search_query
+                                                set_query=set_search_query
+                                                placeholder="Search models, or filter like provider:anthropic vision input<3/1M ctx>=200k -deprecated"
+                                            />
+                                        </div>
 
-                                    <div class="overflow-x-auto">
-                                        <table class="w-full border-collapse">
-                                            <thead>
-                                                <tr class="bg-gray-200">
-                                                    <th class="border border-gray-300 px-4 py-2 text-left font-semibold">"Model Name"</th>
-                                                    <th class="border border-gray-300 px-4 py-2 text-right font-semibold">"Input Cost ($/token)"</th>
-                                                    <th class="border border-gray-300 px-4 py-2 text-right font-semibold">"Output Cost ($/token)"</th>
-                                                    <th class="border border-gray-300 px-4 py-2 text-right font-semibold">"Max Tokens"</th>
-                                                    <th class="border border-gray-300 px-4 py-2 text-center font-semibold">"Provider"</th>
-                                                    <th class="border border-gray-300 px-4 py-2 text-center font-semibold">"Mode"</th>
-                                                </tr>
-                                            </thead>
-                                            <tbody>
-                                                {move || {
-                                                    let models = filtered_models();
-                                                    if models.is_empty() {
-                                                        view! {
-                                                            <tr>
-                                                                <td colspan="6" class="border border-gray-300 px-4 py-8 text-center text-gray-500">
-                                                                    "No models found"
-                                                                </td>
-                                                            </tr>
-                                                        }.into_any()
-                                                    } else {
-                                                        models.into_iter().map(|(model_name, pricing)| {
+                                        <div class="overflow-x-auto">
+                                            <table class="w-full border-collapse">
+                                                <thead>
+                                                    <tr class="bg-gray-200">
+                                                        {SortColumn::ALL.into_iter().map(|column| {
+                                                            let align = match column {
+                                                                SortColumn::Model => "text-left",
+                                                                SortColumn::Provider | SortColumn::Mode => "text-center",
+                                                                _ => "text-right",
+                                                            };
                                                             view! {
-                                                                <tr class="hover:bg-gray-50">
-                                                                    <td class="border border-gray-300 px-4 py-2 font-mono text-sm">
-                                                                        {model_name}
-                                                                    </td>
-                                                                    <td class="border border-gray-300 px-4 py-2 text-right">
-                                                                        {format!("${:.8}", pricing.input_cost_per_token.unwrap_or(0.0))}
-                                                                    </td>
-                                                                    <td class="border border-gray-300 px-4 py-2 text-right">
-                                                                        {format!("${:.8}", pricing.output_cost_per_token.unwrap_or(0.0))}
-                                                                    </td>
-                                                                    <td class="border border-gray-300 px-4 py-2 text-right">
-                                                                        {pricing.max_tokens.map(|t| t.to_string()).unwrap_or_else(|| "N/A".to_string())}
-                                                                    </td>
-                                                                    <td class="border border-gray-300 px-4 py-2 text-center text-sm">
-                                                                        {pricing.litellm_provider.unwrap_or_else(|| "N/A".to_string())}
-                                                                    </td>
-                                                                    <td class="border border-gray-300 px-4 py-2 text-center text-sm">
-                                                                        {pricing.mode.unwrap_or_else(|| "N/A".to_string())}
+                                                                <th
+                                                                    class=format!("border border-gray-300 px-4 py-2 font-semibold cursor-pointer select-none hover:bg-gray-300 {}", align)
+                                                                    title="Click to sort, shift-click to add a sort column"
+                                                                    on:click=move |ev| on_sort(column, ev)
+                                                                >
+                                                                    {column.label()}
+                                                                    {move || {
+                                                                        let keys = sort_keys.get();
+                                                                        keys.iter().position(|key| key.column == column).map(|index| {
+                                                                            let arrow = if keys[index].descending { " ▼" } else { " ▲" };
+                                                                            if keys.len() > 1 {
+                                                                                format!("{}{}", arrow, index + 1)
+                                                                            } else {
+                                                                                arrow.to_string()
+                                                                            }
+                                                                        })
+                                                                    }}
+                                                                </th>
+                                                            }
+                                                        }).collect_view()}
+                                                    </tr>
+                                                </thead>
+                                                <tbody>
+                                                    {move || {
+                                                        let models = filtered_models.get();
+                                                        if models.is_empty() {
+                                                            view! {
+                                                                <tr>
+                                                                    <td colspan="8" class="border border-gray-300 px-4 py-8 text-center text-gray-500">
+                                                                        "No models found"
                                                                     </td>
                                                                 </tr>
-                                                            }
-                                                        }).collect_view().into_any()
-                                                    }
-                                                }}
-                                            </tbody>
-                                        </table>
-                                    </div>
+                                                            }.into_any()
+                                                        } else {
+                                                            models.into_iter().map(|(model_name, pricing)| {
+                                                                view! {
+                                                                    <tr class="hover:bg-gray-50">
+                                                                        <td class="border border-gray-300 px-4 py-2 font-mono text-sm">
+                                                                            {model_name}
+                                                                        </td>
+                                                                        <td class="border border-gray-300 px-4 py-2 text-right">
+                                                                            {format_price(pricing.input_cost_per_token)}
+                                                                        </td>
+                                                                        <td class="border border-gray-300 px-4 py-2 text-right">
+                                                                            {format_price(pricing.output_cost_per_token)}
+                                                                        </td>
+                                                                        <td class="border border-gray-300 px-4 py-2 text-right">
+                                                                            {pricing.max_tokens.map(|t| t.to_string()).unwrap_or_else(|| "N/A".to_string())}
+                                                                        </td>
+                                                                        <td class="border border-gray-300 px-4 py-2 text-center text-sm">
+                                                                            {pricing.litellm_provider.unwrap_or_else(|| "N/A".to_string())}
+                                                                        </td>
+                                                                        <td class="border border-gray-300 px-4 py-2 text-center text-sm">
+                                                                            {pricing.mode.unwrap_or_else(|| "N/A".to_string())}
+                                                                        </td>
+                                                                        <td class="border border-gray-300 px-4 py-2 text-right">
+                                                                            {format_price(pricing.cache_read_input_token_cost)}
+                                                                        </td>
+                                                                        <td class="border border-gray-300 px-4 py-2 text-right">
+                                                                            {format_price(pricing.cache_creation_input_token_cost)}
+                                                                        </td>
+                                                                    </tr>
+                                                                }
+                                                            }).collect_view().into_any()
+                                                        }
+                                                    }}
+                                                </tbody>
+                                            </table>
+                                        </div>
 
-                                    <div class="mt-4 text-sm text-gray-600 text-center">
-                                        {move || format!("Showing {} models", filtered_models().len())}
+                                        <div class="mt-4 text-sm text-gray-600 text-center">
+                                            {move || format!("Showing {} models", filtered_models.with(Vec::len))}
+                                        </div>
                                     </div>
-                                </div>
-                            </Card>
+                                </Card>
+                            </div>
                         }.into_any()
                     }
                 }}
@@ -0,0 +1,309 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use std::collections::HashMap;
+use thaw::*;
+
+use crate::components::{ModelSelect, PageHeader};
+use crate::models::ModelPricing;
+use crate::utils::rag::{estimate_rag_costs, RagPlan, DEFAULT_VECTOR_STORE_COST_PER_GB_PER_DAY};
+use crate::utils::upload::{file_list_to_vec, read_file_text};
+use crate::utils::{estimate_tokens, fetch_model_prices, preload_tokenizer};
+
+#[component]
+pub fn RagPlannerPage() -> impl IntoView {
+    let (model_prices, set_model_prices) = signal(HashMap::<String, ModelPricing>::new());
+    let (loading, set_loading) = signal(true);
+    let (error, set_error) = signal(Option::<String>::None);
+
+    let embedding_model = RwSignal::new(String::from("text-embedding-3-small"));
+    let generation_model = RwSignal::new(String::from("gpt-4o-mini"));
+    let (documents, set_documents) = signal(Vec::<(String, usize)>::new());
+    let (upload_error, set_upload_error) = signal(Option::<String>::None);
+
+    let corpus_tokens = RwSignal::new(1_000_000_usize);
+    let chunk_size = RwSignal::new(512_usize);
+    let chunk_overlap = RwSignal::new(64_usize);
+    let embedding_dimensions = RwSignal::new(1536_usize);
+    let vector_store_rate = RwSignal::new(DEFAULT_VECTOR_STORE_COST_PER_GB_PER_DAY);
+    let top_k = RwSignal::new(5_usize);
+    let query_tokens = RwSignal::new(50_usize);
+    let prompt_overhead_tokens = RwSignal::new(200_usize);
+    let output_tokens = RwSignal::new(300_usize);
+    let queries_per_month = RwSignal::new(10_000_usize);
+
+    Effect::new(move |_| {
+        spawn_local(async move {
+            preload_tokenizer();
+        });
+    });
+
+    Effect::new(move |_| {
+        spawn_local(async move {
+            match fetch_model_prices().await {
+                Ok(prices) => {
+                    set_model_prices.set(prices);
+                    set_loading.set(false);
+                }
+                Err(e) => {
+                    set_error.set(Some(format!("Failed to load model prices: {}", e)));
+                    set_loading.set(false);
+                }
+            }
+        });
+    });
+
+    // Follow the catalogue's vector storage rate whenever the embedding model changes.
+    Effect::new(move |_| {
+        let rate = model_prices.with(|prices| {
+            prices
+                .get(&embedding_model.get())
+                .and_then(|p| p.vector_store_cost_per_gb_per_day)
+        });
+        vector_store_rate.set(rate.unwrap_or(DEFAULT_VECTOR_STORE_COST_PER_GB_PER_DAY));
+    });
+
+    let on_upload = move |files: FileList| {
+        let files = file_list_to_vec(&files);
+        spawn_local(async move {
+            set_upload_error.set(None);
+            for file in files {
+                match read_file_text(&file).await {
+                    Ok(text) => {
+                        let tokens = estimate_tokens(&text);
+                        set_documents.update(|docs| docs.push((file.name(), tokens)));
+                    }
+                    Err(e) => set_upload_error.set(Some(e)),
+                }
+            }
+        });
+    };
+
+    let estimate = move || {
+        let prices = model_prices.get();
+        let embedding = prices.get(&embedding_model.get())?;
+        let generation = prices.get(&generation_model.get())?;
+
+        let docs = documents.get();
+        let document_tokens = if docs.is_empty() {
+            vec![corpus_tokens.get()]
+        } else {
+            docs.iter().map(|(_, tokens)| *tokens).collect()
+        };
+
+        let plan = RagPlan {
+            document_tokens,
+            chunk_size: chunk_size.get(),
+            chunk_overlap: chunk_overlap.get(),
+            embedding_dimensions: embedding_dimensions.get(),
+            top_k: top_k.get(),
+            query_tokens: query_tokens.get(),
+            prompt_overhead_tokens: prompt_overhead_tokens.get(),
+            output_tokens: output_tokens.get(),
+            queries_per_month: queries_per_month.get(),
+            vector_store_cost_per_gb_per_day: vector_store_rate.get(),
+        };
+        Some(estimate_rag_costs(&plan, embedding, generation))
+    };
+
+    view! {
+        <div class="min-h-screen bg-gray-100 p-6">
+            <div class="max-w-7xl mx-auto">
+                <PageHeader
+                    title="RAG Cost Planner"
+                    subtitle="Estimate ingestion, storage and per-query costs of a retrieval pipeline"
+                />
+
+                {move || {
+                    if loading.get() {
+                        view! {
+                            <div class="text-center py-12">
+                                <Spinner size=SpinnerSize::Huge />
+                                <p class="mt-4 text-gray-600">"Loading model prices..."</p>
+                            </div>
+                        }.into_any()
+                    } else if let Some(err) = error.get() {
+                        view! {
+                            <MessageBar intent=MessageBarIntent::Error>
+                                <MessageBarBody>
+                                    <div>
+                                        <strong>"Error: "</strong>
+                                        {err}
+                                    </div>
+                                </MessageBarBody>
+                            </MessageBar>
+                        }.into_any()
+                    } else {
+                        view! {
+                            <div class="grid grid-cols-2 gap-4">
+                                <Card class="w-full">
+                                    <div class="p-5">
+                                        <h3 class="text-xl font-semibold mb-4">"Corpus"</h3>
+                                        <Space vertical=true>
+                                            <div class="flex items-center gap-3 text-sm">
+                                                <span class="text-gray-600">"Corpus size (tokens):"</span>
+                                                <SpinButton<usize>
+                                                    value=corpus_tokens
+                                                    step_page=100_000
+                                                    min=0
+                                                    disabled=Signal::derive(move || !documents.get().is_empty())
+                                                />
+                                            </div>
+                                            <div class="flex items-center gap-3">
+                                                <Upload multiple=true accept=".txt,.md,.csv,.json,.jsonl,.html" custom_request=on_upload>
+                                                    <Button>"Upload documents"</Button>
+                                                </Upload>
+                                                <Button
+                                                    disabled=Signal::derive(move || documents.get().is_empty())
+                                                    on_click=move |_| set_documents.set(Vec::new())
+                                                >
+                                                    "Clear"
+                                                </Button>
+                                            </div>
+                                            {move || upload_error.get().map(|err| view! {
+                                                <MessageBar intent=MessageBarIntent::Warning>
+                                                    <MessageBarBody>{err}</MessageBarBody>
+                                                </MessageBar>
+                                            })}
+                                            <div class="max-h-40 overflow-auto text-sm">
+                                                {move || documents.get().into_iter().map(|(name, tokens)| view! {
+                                                    <div class="flex justify-between border-b border-gray-200 py-1">
+                                                        <span class="font-mono">{name}</span>
+                                                        <span>{format!("{} tokens", tokens)}</span>
+                                                    </div>
+                                                }).collect_view()}
+                                            </div>
+                                        </Space>
+                                    </div>
+                                </Card>
+
+                                <Card class="w-full">
+                                    <div class="p-5">
+                                        <h3 class="text-xl font-semibold mb-4">"Chunking & Embedding"</h3>
+                                        <Space vertical=true>
+                                            <div class="flex items-center gap-3 text-sm">
+                                                <span class="text-gray-600">"Chunk size:"</span>
+                                                <SpinButton<usize> value=chunk_size step_page=64 min=1 />
+                                                <span class="text-gray-600">"Overlap:"</span>
+                                                <SpinButton<usize> value=chunk_overlap step_page=16 min=0 />
+                                            </div>
+                                            <ModelSelect model_prices=model_prices selected=embedding_model embedding=true />
+                                            <div class="flex items-center gap-3 text-sm">
+                                                <span class="text-gray-600">"Dimensions:"</span>
+                                                <SpinButton<usize> value=embedding_dimensions step_page=256 min=1 />
+                                            </div>
+                                            <div class="flex items-center gap-3 text-sm">
+                                                <span class="text-gray-600">"Vector storage ($/GB/day):"</span>
+                                                <SpinButton<f64> value=vector_store_rate step_page=0.01 min=0.0 />
+                                            </div>
+                                        </Space>
+                                    </div>
+                                </Card>
+
+                                <Card class="w-full">
+                                    <div class="p-5">
+                                        <h3 class="text-xl font-semibold mb-4">"Queries"</h3>
+                                        <Space vertical=true>
+                                            <ModelSelect model_prices=model_prices selected=generation_model />
+                                            <Grid cols=2 x_gap=12 y_gap=8>
+                                                <GridItem>
+                                                    <div class="text-sm text-gray-600">"Top-k chunks"</div>
+                                                    <SpinButton<usize> value=top_k step_page=1 min=0 />
+                                                </GridItem>
+                                                <GridItem>
+                                                    <div class="text-sm text-gray-600">"Query tokens"</div>
+                                                    <SpinButton<usize> value=query_tokens step_page=10 min=0 />
+                                                </GridItem>
+                                                <GridItem>
+                                                    <div class="text-sm text-gray-600">"Prompt overhead tokens"</div>
+                                                    <SpinButton<usize> value=prompt_overhead_tokens step_page=50 min=0 />
+                                                </GridItem>
+                                                <GridItem>
+                                                    <div class="text-sm text-gray-600">"Output tokens"</div>
+                                                    <SpinButton<usize> value=output_tokens step_page=50 min=0 />
+                                                </GridItem>
+                                                <GridItem>
+                                                    <div class="text-sm text-gray-600">"Queries per month"</div>
+                                                    <SpinButton<usize> value=queries_per_month step_page=1000 min=0 />
+                                                </GridItem>
+                                            </Grid>
+                                        </Space>
+                                    </div>
+                                </Card>
+
+                                <Card class="w-full">
+                                    <div class="p-5">
+                                        <h3 class="text-xl font-semibold mb-4">"Cost Estimation"</h3>
+                                        {move || match estimate() {
+                                            None => view! {
+                                                <p class="text-gray-500">"Select an embedding and a generation model."</p>
+                                            }.into_any(),
+                                            Some(Err(err)) => view! {
+                                                <MessageBar intent=MessageBarIntent::Warning>
+                                                    <MessageBarBody>{err}</MessageBarBody>
+                                                </MessageBar>
+                                            }.into_any(),
+                                            Some(Ok(est)) => view! {
+                                                <Space vertical=true>
+                                                    <h4 class="font-semibold">"Ingestion (one-time)"</h4>
+                                                    <div class="flex justify-between text-sm">
+                                                        <span class="text-gray-600">"Chunks:"</span>
+                                                        <strong>{est.chunks}</strong>
+                                                    </div>
+                                                    <div class="flex justify-between text-sm">
+                                                        <span class="text-gray-600">"Embedded tokens:"</span>
+                                                        <strong>{est.embedded_tokens}</strong>
+                                                    </div>
+                                                    <div class="flex justify-between text-sm">
+                                                        <span class="text-gray-600">"Embedding cost:"</span>
+                                                        <strong>{format!("${:.4}", est.ingestion_cost)}</strong>
+                                                    </div>
+                                                    <Divider />
+                                                    <h4 class="font-semibold">"Vector Storage"</h4>
+                                                    <div class="flex justify-between text-sm">
+                                                        <span class="text-gray-600">"Index size:"</span>
+                                                        <strong>{format!("{:.4} GB", est.storage_gb)}</strong>
+                                                    </div>
+                                                    <div class="flex justify-between text-sm">
+                                                        <span class="text-gray-600">"Storage cost per month:"</span>
+                                                        <strong>{format!("${:.4}", est.storage_cost_per_month)}</strong>
+                                                    </div>
+                                                    <Divider />
+                                                    <h4 class="font-semibold">"Per Query"</h4>
+                                                    <div class="flex justify-between text-sm">
+                                                        <span class="text-gray-600">"Query embedding:"</span>
+                                                        <strong>{format!("${:.8}", est.query_embedding_cost)}</strong>
+                                                    </div>
+                                                    <div class="flex justify-between text-sm">
+                                                        <span class="text-gray-600">
+                                                            {format!("Generation input ({} tokens):", est.generation_input_tokens)}
+                                                        </span>
+                                                        <strong>{format!("${:.6}", est.generation_input_cost)}</strong>
+                                                    </div>
+                                                    <div class="flex justify-between text-sm">
+                                                        <span class="text-gray-600">"Generation output:"</span>
+                                                        <strong>{format!("${:.6}", est.generation_output_cost)}</strong>
+                                                    </div>
+                                                    <div class="flex justify-between text-sm">
+                                                        <span class="font-semibold">"Cost per query:"</span>
+                                                        <strong>{format!("${:.6}", est.cost_per_query)}</strong>
+                                                    </div>
+                                                    <Divider />
+                                                    <div class="flex justify-between">
+                                                        <span class="font-semibold">"Monthly total:"</span>
+                                                        <span class="text-xl font-bold text-blue-500">
+                                                            {format!("${:.2}", est.total_cost_per_month)}
+                                                        </span>
+                                                    </div>
+                                                </Space>
+                                            }.into_any(),
+                                        }}
+                                    </div>
+                                </Card>
+                            </div>
+                        }.into_any()
+                    }
+                }}
+            </div>
+        </div>
+    }
+}
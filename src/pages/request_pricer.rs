@@ -0,0 +1,176 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use std::collections::HashMap;
+use thaw::*;
+
+use crate::components::PageHeader;
+use crate::models::ModelPricing;
+use crate::utils::api_usage::{parse_api_call, price_usage};
+use crate::utils::{fetch_model_prices, preload_tokenizer};
+
+#[component]
+pub fn RequestPricerPage() -> impl IntoView {
+    let (model_prices, set_model_prices) = signal(HashMap::<String, ModelPricing>::new());
+    let (loading, set_loading) = signal(true);
+    let (error, set_error) = signal(Option::<String>::None);
+
+    let request_text = RwSignal::new(String::new());
+    let response_text = RwSignal::new(String::new());
+
+    Effect::new(move |_| {
+        spawn_local(async move {
+            preload_tokenizer();
+        });
+    });
+
+    Effect::new(move |_| {
+        spawn_local(async move {
+            match fetch_model_prices().await {
+                Ok(prices) => {
+                    set_model_prices.set(prices);
+                    set_loading.set(false);
+                }
+                Err(e) => {
+                    set_error.set(Some(format!("Failed to load model prices: {}", e)));
+                    set_loading.set(false);
+                }
+            }
+        });
+    });
+
+    let priced = Memo::new(move |_| {
+        let call = parse_api_call(&request_text.get(), &response_text.get())?;
+        let cost = model_prices.with(|prices| price_usage(&call.model, &call.usage, prices))?;
+        Ok::<_, String>((call, cost))
+    });
+
+    view! {
+        <div class="min-h-screen bg-gray-100 p-6">
+            <div class="max-w-7xl mx-auto">
+                <PageHeader
+                    title="Request Pricer"
+                    subtitle="Price a real API call from its request and response JSON"
+                />
+
+                {move || {
+                    if loading.get() {
+                        view! {
+                            <div class="text-center py-12">
+                                <Spinner size=SpinnerSize::Huge />
+                                <p class="mt-4 text-gray-600">"Loading model prices..."</p>
+                            </div>
+                        }.into_any()
+                    } else if let Some(err) = error.get() {
+                        view! {
+                            <MessageBar intent=MessageBarIntent::Error>
+                                <MessageBarBody>
+                                    <div>
+                                        <strong>"Error: "</strong>
+                                        {err}
+                                    </div>
+                                </MessageBarBody>
+                            </MessageBar>
+                        }.into_any()
+                    } else {
+                        view! {
+                            <div class="space-y-4">
+                                <Grid cols=2 x_gap=16>
+                                    <GridItem>
+                                        <Card class="w-full">
+                                            <div class="p-5">
+                                                <h3 class="text-xl font-semibold mb-4">"Request"</h3>
+                                                <Textarea
+                                                    class="w-full h-64 font-mono"
+                                                    value=request_text
+                                                    placeholder="Optional: the request body, e.g. {\"model\": ..., \"messages\": [...]}"
+                                                />
+                                            </div>
+                                        </Card>
+                                    </GridItem>
+                                    <GridItem>
+                                        <Card class="w-full">
+                                            <div class="p-5">
+                                                <h3 class="text-xl font-semibold mb-4">"Response"</h3>
+                                                <Textarea
+                                                    class="w-full h-64 font-mono"
+                                                    value=response_text
+                                                    placeholder="The response JSON with its usage or usageMetadata block"
+                                                />
+                                            </div>
+                                        </Card>
+                                    </GridItem>
+                                </Grid>
+
+                                <Card class="w-full">
+                                    <div class="p-5">
+                                        <h3 class="text-xl font-semibold mb-4">"Cost"</h3>
+                                        {move || {
+                                            if response_text.with(|text| text.trim().is_empty()) {
+                                                return view! {
+                                                    <div class="text-sm text-gray-500">"Paste an OpenAI, Anthropic or Gemini response to price it."</div>
+                                                }.into_any();
+                                            }
+                                            match priced.get() {
+                                                Err(err) => view! {
+                                                    <MessageBar intent=MessageBarIntent::Warning>
+                                                        <MessageBarBody>{err}</MessageBarBody>
+                                                    </MessageBar>
+                                                }.into_any(),
+                                                Ok((call, cost)) => {
+                                                    let reported_prompt = call.usage.prompt_tokens();
+                                                    view! {
+                                                        <Space vertical=true>
+                                                            <div class="text-sm text-gray-600">
+                                                                {format!("{} · {} · priced as ", call.provider.label(), call.model)}
+                                                                <span class="font-mono">{cost.model_key.clone()}</span>
+                                                                {cost.long_context.then_some(" (long-context prices)")}
+                                                            </div>
+                                                            {call.estimated_prompt_tokens.map(|estimated| view! {
+                                                                <div class="text-sm text-gray-600">
+                                                                    {format!(
+                                                                        "Request estimate: {} prompt tokens against {} billed",
+                                                                        estimated, reported_prompt,
+                                                                    )}
+                                                                </div>
+                                                            })}
+                                                            <table class="w-full text-sm">
+                                                                <thead>
+                                                                    <tr class="border-b border-gray-300 text-left">
+                                                                        <th class="py-2 pr-4">"Component"</th>
+                                                                        <th class="py-2 pr-4 text-right">"Tokens"</th>
+                                                                        <th class="py-2 pr-4 text-right">"Price / 1M"</th>
+                                                                        <th class="py-2 text-right">"Cost"</th>
+                                                                    </tr>
+                                                                </thead>
+                                                                <tbody>
+                                                                    {cost.components.into_iter().map(|component| view! {
+                                                                        <tr class="border-b border-gray-200">
+                                                                            <td class="py-2 pr-4">{component.label}</td>
+                                                                            <td class="py-2 pr-4 text-right">{component.tokens}</td>
+                                                                            <td class="py-2 pr-4 text-right">{format!("${:.2}", component.price_per_token * 1_000_000.0)}</td>
+                                                                            <td class="py-2 text-right">{format!("${:.6}", component.cost)}</td>
+                                                                        </tr>
+                                                                    }).collect_view()}
+                                                                    <tr class="font-semibold">
+                                                                        <td class="py-2 pr-4">"Total"</td>
+                                                                        <td class="py-2 pr-4"></td>
+                                                                        <td class="py-2 pr-4"></td>
+                                                                        <td class="py-2 text-right text-blue-500">{format!("${:.6}", cost.total)}</td>
+                                                                    </tr>
+                                                                </tbody>
+                                                            </table>
+                                                        </Space>
+                                                    }.into_any()
+                                                }
+                                            }
+                                        }}
+                                    </div>
+                                </Card>
+                            </div>
+                        }.into_any()
+                    }
+                }}
+            </div>
+        </div>
+    }
+}
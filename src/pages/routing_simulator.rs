@@ -0,0 +1,273 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use std::collections::HashMap;
+use thaw::*;
+
+use crate::components::{ModelSelect, PageHeader};
+use crate::models::ModelPricing;
+use crate::utils::fetch_model_prices;
+use crate::utils::routing::{simulate_cascade, CascadePlan, CascadeTier};
+
+#[derive(Debug, Clone, Copy)]
+struct TierRow {
+    id: usize,
+    model: RwSignal<String>,
+    escalation_percent: RwSignal<f64>,
+    resend_prompt: RwSignal<bool>,
+}
+
+impl TierRow {
+    fn new(id: usize, model: &str, escalation_percent: f64) -> Self {
+        Self {
+            id,
+            model: RwSignal::new(model.to_string()),
+            escalation_percent: RwSignal::new(escalation_percent),
+            resend_prompt: RwSignal::new(true),
+        }
+    }
+
+    fn to_tier(self) -> CascadeTier {
+        CascadeTier {
+            model: self.model.get(),
+            escalation_rate: self.escalation_percent.get() / 100.0,
+            resend_prompt: self.resend_prompt.get(),
+        }
+    }
+}
+
+#[component]
+pub fn RoutingSimulatorPage() -> impl IntoView {
+    let (model_prices, set_model_prices) = signal(HashMap::<String, ModelPricing>::new());
+    let (loading, set_loading) = signal(true);
+    let (error, set_error) = signal(Option::<String>::None);
+
+    let prompt_tokens = RwSignal::new(1_000_usize);
+    let output_tokens = RwSignal::new(300_usize);
+    let handoff_tokens = RwSignal::new(200_usize);
+    let requests_per_month = RwSignal::new(100_000.0_f64);
+    let (next_id, set_next_id) = signal(2_usize);
+    let tiers = RwSignal::new(vec![
+        TierRow::new(0, "gpt-4o-mini", 20.0),
+        TierRow::new(1, "claude-sonnet-4-5", 0.0),
+    ]);
+
+    Effect::new(move |_| {
+        spawn_local(async move {
+            match fetch_model_prices().await {
+                Ok(prices) => {
+                    set_model_prices.set(prices);
+                    set_loading.set(false);
+                }
+                Err(e) => {
+                    set_error.set(Some(format!("Failed to load model prices: {}", e)));
+                    set_loading.set(false);
+                }
+            }
+        });
+    });
+
+    let add_tier = move |_| {
+        let id = next_id.get_untracked();
+        set_next_id.set(id + 1);
+        tiers.update(|list| list.push(TierRow::new(id, "gpt-4o", 0.0)));
+    };
+
+    let estimate = move || {
+        let plan = CascadePlan {
+            tiers: tiers.get().into_iter().map(TierRow::to_tier).collect(),
+            prompt_tokens: prompt_tokens.get(),
+            output_tokens: output_tokens.get(),
+            handoff_tokens: handoff_tokens.get(),
+        };
+        model_prices.with(|prices| simulate_cascade(&plan, prices))
+    };
+
+    let is_last = move |id: usize| tiers.with(|list| list.last().is_some_and(|t| t.id == id));
+    let is_first = move |id: usize| tiers.with(|list| list.first().is_some_and(|t| t.id == id));
+
+    view! {
+        <div class="min-h-screen bg-gray-100 p-6">
+            <div class="max-w-7xl mx-auto">
+                <PageHeader
+                    title="Routing Simulator"
+                    subtitle="Compare a cheap-first model cascade with sending everything to the top model"
+                />
+
+                {move || {
+                    if loading.get() {
+                        view! {
+                            <div class="text-center py-12">
+                                <Spinner size=SpinnerSize::Huge />
+                                <p class="mt-4 text-gray-600">"Loading model prices..."</p>
+                            </div>
+                        }.into_any()
+                    } else if let Some(err) = error.get() {
+                        view! {
+                            <MessageBar intent=MessageBarIntent::Error>
+                                <MessageBarBody>
+                                    <div>
+                                        <strong>"Error: "</strong>
+                                        {err}
+                                    </div>
+                                </MessageBarBody>
+                            </MessageBar>
+                        }.into_any()
+                    } else {
+                        view! {
+                            <div class="space-y-4">
+                                <Card class="w-full">
+                                    <div class="p-5">
+                                        <Grid cols=4 x_gap=12>
+                                            <GridItem>
+                                                <div class="text-sm text-gray-600 mb-1">"Prompt tokens"</div>
+                                                <SpinButton<usize> value=prompt_tokens step_page=100 min=0 />
+                                            </GridItem>
+                                            <GridItem>
+                                                <div class="text-sm text-gray-600 mb-1">"Output tokens"</div>
+                                                <SpinButton<usize> value=output_tokens step_page=100 min=0 />
+                                            </GridItem>
+                                            <GridItem>
+                                                <div class="text-sm text-gray-600 mb-1">"Hand-off tokens"</div>
+                                                <SpinButton<usize> value=handoff_tokens step_page=50 min=0 />
+                                            </GridItem>
+                                            <GridItem>
+                                                <div class="text-sm text-gray-600 mb-1">"Requests per month"</div>
+                                                <SpinButton<f64> value=requests_per_month step_page=10_000.0 min=0.0 />
+                                            </GridItem>
+                                        </Grid>
+                                    </div>
+                                </Card>
+
+                                <Card class="w-full">
+                                    <div class="p-5">
+                                        <div class="flex justify-between items-center mb-4">
+                                            <h3 class="text-xl font-semibold">"Cascade"</h3>
+                                            <Button on_click=add_tier>"Add tier"</Button>
+                                        </div>
+                                        <div class="space-y-3">
+                                            <For
+                                                each=move || tiers.get()
+                                                key=|row| row.id
+                                                let:row
+                                            >
+                                                <div class="grid grid-cols-12 gap-3 items-center border-b border-gray-200 pb-3">
+                                                    <div class="col-span-5">
+                                                        <ModelSelect model_prices=model_prices selected=row.model />
+                                                    </div>
+                                                    <div class="col-span-3 text-sm">
+                                                        {move || if is_last(row.id) {
+                                                            view! { <span class="text-gray-500">"Final tier"</span> }.into_any()
+                                                        } else {
+                                                            view! {
+                                                                <div class="flex items-center gap-2">
+                                                                    <span class="text-gray-600">"Escalate %"</span>
+                                                                    <SpinButton<f64> value=row.escalation_percent step_page=5.0 min=0.0 max=100.0 />
+                                                                </div>
+                                                            }.into_any()
+                                                        }}
+                                                    </div>
+                                                    <div class="col-span-3">
+                                                        {move || (!is_first(row.id)).then(|| view! {
+                                                            <Switch checked=row.resend_prompt label="Re-send original prompt" />
+                                                        })}
+                                                    </div>
+                                                    <div class="col-span-1 text-right">
+                                                        <Button
+                                                            size=ButtonSize::Small
+                                                            disabled=Signal::derive(move || tiers.with(|list| list.len() <= 1))
+                                                            on_click=move |_| tiers.update(|list| list.retain(|t| t.id != row.id))
+                                                        >
+                                                            "Remove"
+                                                        </Button>
+                                                    </div>
+                                                </div>
+                                            </For>
+                                        </div>
+                                    </div>
+                                </Card>
+
+                                <Card class="w-full">
+                                    <div class="p-5">
+                                        <h3 class="text-xl font-semibold mb-4">"Blended Cost"</h3>
+                                        {move || {
+                                            let estimate = estimate();
+                                            let volume = requests_per_month.get();
+                                            let savings = estimate.savings();
+                                            let monthly_savings = (estimate.top_model_cost_per_request
+                                                - estimate.blended_cost_per_request)
+                                                * volume;
+                                            let savings_class = if savings >= 0.0 {
+                                                "font-semibold text-green-600"
+                                            } else {
+                                                "font-semibold text-red-600"
+                                            };
+                                            view! {
+                                                <Grid cols=3 x_gap=12>
+                                                    <GridItem>
+                                                        <Card class="bg-gray-100">
+                                                            <div class="p-3 text-center">
+                                                                <div class="text-xs text-gray-600 mb-1">"Cascade / request"</div>
+                                                                <div class="font-semibold text-blue-500">{format!("${:.6}", estimate.blended_cost_per_request)}</div>
+                                                                <div class="text-xs text-gray-500">{format!("${:.2} / month", estimate.blended_cost_per_request * volume)}</div>
+                                                            </div>
+                                                        </Card>
+                                                    </GridItem>
+                                                    <GridItem>
+                                                        <Card class="bg-gray-100">
+                                                            <div class="p-3 text-center">
+                                                                <div class="text-xs text-gray-600 mb-1">"Top model only / request"</div>
+                                                                <div class="font-semibold">{format!("${:.6}", estimate.top_model_cost_per_request)}</div>
+                                                                <div class="text-xs text-gray-500">{format!("${:.2} / month", estimate.top_model_cost_per_request * volume)}</div>
+                                                            </div>
+                                                        </Card>
+                                                    </GridItem>
+                                                    <GridItem>
+                                                        <Card class="bg-gray-100">
+                                                            <div class="p-3 text-center">
+                                                                <div class="text-xs text-gray-600 mb-1">"Savings"</div>
+                                                                <div class=savings_class>
+                                                                    {format!("{:.1}%", savings * 100.0)}
+                                                                </div>
+                                                                <div class="text-xs text-gray-500">
+                                                                    {format!("${:.2} / month", monthly_savings)}
+                                                                </div>
+                                                            </div>
+                                                        </Card>
+                                                    </GridItem>
+                                                </Grid>
+                                                <table class="w-full border-collapse text-sm mt-4">
+                                                    <thead>
+                                                        <tr class="bg-gray-200">
+                                                            <th class="border border-gray-300 px-3 py-2 text-left font-semibold">"Tier"</th>
+                                                            <th class="border border-gray-300 px-3 py-2 text-left font-semibold">"Model"</th>
+                                                            <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Traffic Reaching Tier"</th>
+                                                            <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Input Tokens"</th>
+                                                            <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Cost / Call"</th>
+                                                            <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Cost / Request"</th>
+                                                        </tr>
+                                                    </thead>
+                                                    <tbody>
+                                                        {estimate.tiers.into_iter().enumerate().map(|(index, tier)| view! {
+                                                            <tr class="hover:bg-gray-50">
+                                                                <td class="border border-gray-300 px-3 py-2">{index + 1}</td>
+                                                                <td class="border border-gray-300 px-3 py-2 font-mono">{tier.model}</td>
+                                                                <td class="border border-gray-300 px-3 py-2 text-right">{format!("{:.1}%", tier.traffic_share * 100.0)}</td>
+                                                                <td class="border border-gray-300 px-3 py-2 text-right">{tier.input_tokens}</td>
+                                                                <td class="border border-gray-300 px-3 py-2 text-right">{format!("${:.6}", tier.cost_per_call)}</td>
+                                                                <td class="border border-gray-300 px-3 py-2 text-right">{format!("${:.6}", tier.cost_per_request)}</td>
+                                                            </tr>
+                                                        }).collect_view()}
+                                                    </tbody>
+                                                </table>
+                                            }
+                                        }}
+                                    </div>
+                                </Card>
+                            </div>
+                        }.into_any()
+                    }
+                }}
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,218 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use leptos_use::use_debounce_fn_with_arg;
+use std::io::{Cursor, Write};
+use thaw::*;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::components::PageHeader;
+use crate::utils::{
+    chunk_by_tokens, download_file, estimate_tokens, preload_tokenizer, truncate_to_tokens,
+    TokenChunk,
+};
+
+fn chunks_to_jsonl(chunks: &[TokenChunk]) -> Result<String, serde_json::Error> {
+    let mut jsonl = String::new();
+    for chunk in chunks {
+        jsonl.push_str(&serde_json::to_string(chunk)?);
+        jsonl.push('\n');
+    }
+    Ok(jsonl)
+}
+
+fn chunks_to_zip(chunks: &[TokenChunk]) -> zip::result::ZipResult<Vec<u8>> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    for chunk in chunks {
+        writer.start_file(format!("chunk_{:04}.txt", chunk.index + 1), options)?;
+        writer.write_all(chunk.text.as_bytes())?;
+    }
+    Ok(writer.finish()?.into_inner())
+}
+
+#[component]
+pub fn TokenToolsPage() -> impl IntoView {
+    let (source_text, set_source_text) = signal(String::new());
+    let (debounced_source_text, set_debounced_source_text) = signal(String::new());
+    let max_tokens = RwSignal::new(1000_usize);
+    let chunk_size = RwSignal::new(512_usize);
+    let chunk_overlap = RwSignal::new(64_usize);
+    let (export_error, set_export_error) = signal(Option::<String>::None);
+
+    let debounce_source = use_debounce_fn_with_arg(
+        move |text: String| {
+            set_debounced_source_text.set(text);
+        },
+        300.0,
+    );
+
+    Effect::new(move |_| {
+        let text = source_text.get();
+        debounce_source(text);
+    });
+
+    Effect::new(move |_| {
+        spawn_local(async move {
+            preload_tokenizer();
+        });
+    });
+
+    let source_tokens = Memo::new(move |_| estimate_tokens(&debounced_source_text.get()));
+
+    let truncated = Memo::new(move |_| {
+        let text = truncate_to_tokens(&debounced_source_text.get(), max_tokens.get());
+        let tokens = estimate_tokens(&text);
+        (text, tokens)
+    });
+
+    let chunks = Memo::new(move |_| {
+        chunk_by_tokens(
+            &debounced_source_text.get(),
+            chunk_size.get(),
+            chunk_overlap.get(),
+        )
+    });
+
+    let export_jsonl = move |_| {
+        let result = chunks_to_jsonl(&chunks.get_untracked().unwrap_or_default())
+            .map_err(|e| e.to_string())
+            .and_then(|jsonl| {
+                download_file("chunks.jsonl", "application/jsonl", jsonl.as_bytes())
+                    .map_err(|e| format!("{:?}", e))
+            });
+        set_export_error.set(
+            result
+                .err()
+                .map(|e| format!("Failed to export JSONL: {}", e)),
+        );
+    };
+
+    let export_zip = move |_| {
+        let result = chunks_to_zip(&chunks.get_untracked().unwrap_or_default())
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| {
+                download_file("chunks.zip", "application/zip", &bytes)
+                    .map_err(|e| format!("{:?}", e))
+            });
+        set_export_error.set(result.err().map(|e| format!("Failed to export zip: {}", e)));
+    };
+
+    view! {
+        <div class="min-h-screen bg-gray-100 p-6">
+            <div class="max-w-7xl mx-auto">
+                <PageHeader
+                    title="Token Tools"
+                    subtitle="Truncate and chunk documents by exact token counts"
+                />
+
+                <div class="space-y-4">
+                    <Card class="w-full">
+                        <div class="p-5">
+                            <div class="flex justify-between items-center mb-4">
+                                <h3 class="text-xl font-semibold">"Source Text"</h3>
+                                <span class="text-sm text-gray-600">
+                                    {move || format!("{} tokens", source_tokens.get())}
+                                </span>
+                            </div>
+                            <Textarea
+                                size=TextareaSize::Large
+                                class="w-full h-60"
+                                placeholder="Paste the document to truncate or chunk..."
+                                on:input=move |ev| {
+                                    set_source_text.set(event_target_value(&ev));
+                                }
+                            />
+                        </div>
+                    </Card>
+
+                    <div class="grid grid-cols-2 gap-4">
+                        <Card class="w-full">
+                            <div class="p-5">
+                                <h3 class="text-xl font-semibold mb-4">"Truncate"</h3>
+                                <Space vertical=true>
+                                    <div class="flex items-center gap-3 text-sm">
+                                        <span class="text-gray-600">"Max tokens:"</span>
+                                        <SpinButton<usize> value=max_tokens step_page=100 min=1 />
+                                    </div>
+                                    <div class="text-sm">
+                                        <span class="text-gray-600">"Result: "</span>
+                                        <strong>
+                                            {move || format!("{} / {} tokens", truncated.get().1, source_tokens.get())}
+                                        </strong>
+                                    </div>
+                                    <pre class="w-full h-60 overflow-auto p-3 bg-gray-50 border border-gray-300 rounded text-sm whitespace-pre-wrap">
+                                        {move || truncated.get().0}
+                                    </pre>
+                                </Space>
+                            </div>
+                        </Card>
+
+                        <Card class="w-full">
+                            <div class="p-5">
+                                <h3 class="text-xl font-semibold mb-4">"Chunk"</h3>
+                                <Space vertical=true>
+                                    <div class="flex items-center gap-3 text-sm">
+                                        <span class="text-gray-600">"Chunk size:"</span>
+                                        <SpinButton<usize> value=chunk_size step_page=64 min=1 />
+                                        <span class="text-gray-600">"Overlap:"</span>
+                                        <SpinButton<usize> value=chunk_overlap step_page=16 min=0 />
+                                    </div>
+                                    <div class="flex justify-between items-center text-sm">
+                                        <strong>{move || format!("{} chunks", chunks.get().map(|c| c.len()).unwrap_or(0))}</strong>
+                                        <Space>
+                                            <Button
+                                                disabled=Signal::derive(move || !chunks.with(|c| c.as_ref().is_ok_and(|c| !c.is_empty())))
+                                                on_click=export_jsonl
+                                            >
+                                                "Export JSONL"
+                                            </Button>
+                                            <Button
+                                                disabled=Signal::derive(move || !chunks.with(|c| c.as_ref().is_ok_and(|c| !c.is_empty())))
+                                                on_click=export_zip
+                                            >
+                                                "Export Zip"
+                                            </Button>
+                                        </Space>
+                                    </div>
+                                    {move || chunks.get().err().map(|err| view! {
+                                        <MessageBar intent=MessageBarIntent::Warning>
+                                            <MessageBarBody>{err}</MessageBarBody>
+                                        </MessageBar>
+                                    })}
+                                    {move || export_error.get().map(|err| view! {
+                                        <MessageBar intent=MessageBarIntent::Error>
+                                            <MessageBarBody>{err}</MessageBarBody>
+                                        </MessageBar>
+                                    })}
+                                    <div class="h-60 overflow-auto space-y-2">
+                                        {move || {
+                                            chunks.get().unwrap_or_default().into_iter().map(|chunk| {
+                                                view! {
+                                                    <div class="border border-gray-300 rounded bg-gray-50">
+                                                        <div class="flex justify-between px-3 py-1 bg-gray-200 text-xs text-gray-700">
+                                                            <span>{format!("Chunk {}", chunk.index + 1)}</span>
+                                                            <span>
+                                                                {format!(
+                                                                    "tokens {}–{} ({})",
+                                                                    chunk.start_token,
+                                                                    chunk.end_token,
+                                                                    chunk.token_count,
+                                                                )}
+                                                            </span>
+                                                        </div>
+                                                        <pre class="p-3 text-sm whitespace-pre-wrap">{chunk.text}</pre>
+                                                    </div>
+                                                }
+                                            }).collect_view()
+                                        }}
+                                    </div>
+                                </Space>
+                            </div>
+                        </Card>
+                    </div>
+                </div>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,277 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use std::collections::HashMap;
+use thaw::*;
+use web_sys::FileList;
+
+use crate::components::{ChartSeries, LineChart, PageHeader};
+use crate::models::ModelPricing;
+use crate::utils::fetch_model_prices;
+use crate::utils::transcript::{parse_transcript, price_transcript};
+use crate::utils::upload::{file_list_to_vec, read_file_text};
+
+fn format_dollars(value: f64) -> String {
+    format!("${:.4}", value)
+}
+
+#[component]
+pub fn TranscriptPage() -> impl IntoView {
+    let (model_prices, set_model_prices) = signal(HashMap::<String, ModelPricing>::new());
+    let (loading, set_loading) = signal(true);
+    let (error, set_error) = signal(Option::<String>::None);
+
+    let transcript_text = RwSignal::new(String::new());
+    let (file_name, set_file_name) = signal(Option::<String>::None);
+    let (upload_error, set_upload_error) = signal(Option::<String>::None);
+
+    Effect::new(move |_| {
+        spawn_local(async move {
+            match fetch_model_prices().await {
+                Ok(prices) => {
+                    set_model_prices.set(prices);
+                    set_loading.set(false);
+                }
+                Err(e) => {
+                    set_error.set(Some(format!("Failed to load model prices: {}", e)));
+                    set_loading.set(false);
+                }
+            }
+        });
+    });
+
+    let on_upload = move |files: FileList| {
+        let files = file_list_to_vec(&files);
+        spawn_local(async move {
+            set_upload_error.set(None);
+            if let Some(file) = files.into_iter().next() {
+                match read_file_text(&file).await {
+                    Ok(text) => {
+                        set_file_name.set(Some(file.name()));
+                        transcript_text.set(text);
+                    }
+                    Err(e) => set_upload_error.set(Some(e)),
+                }
+            }
+        });
+    };
+
+    let transcript = Memo::new(move |_| transcript_text.with(|text| parse_transcript(text)));
+    let cost = Memo::new(move |_| {
+        model_prices
+            .with(|prices| transcript.with(|transcript| price_transcript(transcript, prices)))
+    });
+
+    let x_labels = Signal::derive(move || {
+        cost.with(|cost| {
+            (1..=cost.turns.len())
+                .map(|turn| turn.to_string())
+                .collect::<Vec<_>>()
+        })
+    });
+    let cost_series = Signal::derive(move || {
+        cost.with(|cost| {
+            vec![
+                ChartSeries {
+                    label: "Cumulative cost".to_string(),
+                    color: "#3b82f6",
+                    values: cost.turns.iter().map(|t| t.cumulative_cost).collect(),
+                },
+                ChartSeries {
+                    label: "Turn cost".to_string(),
+                    color: "#f59e0b",
+                    values: cost.turns.iter().map(|t| t.cost).collect(),
+                },
+            ]
+        })
+    });
+
+    view! {
+        <div class="min-h-screen bg-gray-100 p-6">
+            <div class="max-w-7xl mx-auto">
+                <PageHeader
+                    title="Transcript Cost"
+                    subtitle="Price an agent or chat session from its JSONL transcript"
+                />
+
+                {move || {
+                    if loading.get() {
+                        view! {
+                            <div class="text-center py-12">
+                                <Spinner size=SpinnerSize::Huge />
+                                <p class="mt-4 text-gray-600">"Loading model prices..."</p>
+                            </div>
+                        }.into_any()
+                    } else if let Some(err) = error.get() {
+                        view! {
+                            <MessageBar intent=MessageBarIntent::Error>
+                                <MessageBarBody>
+                                    <div>
+                                        <strong>"Error: "</strong>
+                                        {err}
+                                    </div>
+                                </MessageBarBody>
+                            </MessageBar>
+                        }.into_any()
+                    } else {
+                        view! {
+                            <div class="space-y-4">
+                                <Card class="w-full">
+                                    <div class="p-5">
+                                        <h3 class="text-xl font-semibold mb-4">"Transcript"</h3>
+                                        <Space vertical=true>
+                                            <div class="flex items-center gap-3">
+                                                <Upload accept=".jsonl" custom_request=on_upload>
+                                                    <Button>"Upload .jsonl"</Button>
+                                                </Upload>
+                                                <Button
+                                                    disabled=Signal::derive(move || transcript_text.get().is_empty())
+                                                    on_click=move |_| {
+                                                        transcript_text.set(String::new());
+                                                        set_file_name.set(None);
+                                                    }
+                                                >
+                                                    "Clear"
+                                                </Button>
+                                                <span class="text-sm font-mono text-gray-600">{move || file_name.get()}</span>
+                                            </div>
+                                            {move || upload_error.get().map(|err| view! {
+                                                <MessageBar intent=MessageBarIntent::Warning>
+                                                    <MessageBarBody>{err}</MessageBarBody>
+                                                </MessageBar>
+                                            })}
+                                            <Textarea
+                                                class="w-full h-40 font-mono"
+                                                value=transcript_text
+                                                placeholder="Or paste a session transcript here, one JSON message or event per line..."
+                                            />
+                                        </Space>
+                                    </div>
+                                </Card>
+
+                                <Card class="w-full">
+                                    <div class="p-5">
+                                        <h3 class="text-xl font-semibold mb-4">"Session Cost"</h3>
+                                        {move || {
+                                            let transcript = transcript.get();
+                                            let cost = cost.get();
+                                            let errors = transcript.errors;
+                                            let error_count = errors.len();
+                                            let unpriced = cost.unpriced_models;
+                                            let total_cost = cost.total_cost;
+                                            let turn_count = cost.turns.len();
+                                            let call_count = transcript.calls.len();
+                                            let tokens = format!(
+                                                "{} / {} / {} / {}",
+                                                cost.turns.iter().map(|t| t.input_tokens).sum::<usize>(),
+                                                cost.turns.iter().map(|t| t.cache_read_tokens).sum::<usize>(),
+                                                cost.turns.iter().map(|t| t.cache_write_tokens).sum::<usize>(),
+                                                cost.turns.iter().map(|t| t.output_tokens).sum::<usize>(),
+                                            );
+                                            view! {
+                                                <Grid cols=3 x_gap=12>
+                                                    <GridItem>
+                                                        <Card class="bg-gray-100">
+                                                            <div class="p-3 text-center">
+                                                                <div class="text-xs text-gray-600 mb-1">"Turns / API calls"</div>
+                                                                <div class="font-semibold">
+                                                                    {format!("{} / {}", turn_count, call_count)}
+                                                                </div>
+                                                            </div>
+                                                        </Card>
+                                                    </GridItem>
+                                                    <GridItem>
+                                                        <Card class="bg-gray-100">
+                                                            <div class="p-3 text-center">
+                                                                <div class="text-xs text-gray-600 mb-1">"Input / cache read / cache write / output"</div>
+                                                                <div class="font-semibold">{tokens}</div>
+                                                            </div>
+                                                        </Card>
+                                                    </GridItem>
+                                                    <GridItem>
+                                                        <Card class="bg-gray-100">
+                                                            <div class="p-3 text-center">
+                                                                <div class="text-xs text-gray-600 mb-1">"Session cost"</div>
+                                                                <div class="font-semibold text-blue-500">{format!("${:.4}", total_cost)}</div>
+                                                            </div>
+                                                        </Card>
+                                                    </GridItem>
+                                                </Grid>
+
+                                                {(!unpriced.is_empty()).then(|| view! {
+                                                    <div class="mt-4">
+                                                        <MessageBar intent=MessageBarIntent::Warning>
+                                                            <MessageBarBody>
+                                                                {format!("Not in the catalogue, counted as $0: {}", unpriced.join(", "))}
+                                                            </MessageBarBody>
+                                                        </MessageBar>
+                                                    </div>
+                                                })}
+
+                                                {(error_count > 0).then(move || view! {
+                                                    <div class="mt-4">
+                                                        <MessageBar intent=MessageBarIntent::Warning>
+                                                            <MessageBarBody>
+                                                                <div class="max-h-40 overflow-auto text-sm">
+                                                                    {errors.into_iter().map(|err| view! { <div>{err}</div> }).collect_view()}
+                                                                </div>
+                                                            </MessageBarBody>
+                                                        </MessageBar>
+                                                    </div>
+                                                })}
+                                            }
+                                        }}
+                                        <h4 class="font-semibold mt-4 mb-2">"Cost per turn"</h4>
+                                        <LineChart series=cost_series x_labels=x_labels y_format=format_dollars />
+                                    </div>
+                                </Card>
+
+                                <Card class="w-full">
+                                    <div class="p-5">
+                                        <h3 class="text-xl font-semibold mb-4">"Per Turn"</h3>
+                                        <div class="overflow-x-auto max-h-96">
+                                            <table class="w-full border-collapse text-sm">
+                                                <thead>
+                                                    <tr class="bg-gray-200">
+                                                        <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Turn"</th>
+                                                        <th class="border border-gray-300 px-3 py-2 text-left font-semibold">"Prompt"</th>
+                                                        <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Calls"</th>
+                                                        <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Input"</th>
+                                                        <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Cache Read"</th>
+                                                        <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Cache Write"</th>
+                                                        <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Output"</th>
+                                                        <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Cost"</th>
+                                                        <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Cumulative"</th>
+                                                    </tr>
+                                                </thead>
+                                                <tbody>
+                                                    {move || cost.get().turns.into_iter().enumerate().map(|(index, turn)| {
+                                                        let title = turn.prompt.clone();
+                                                        view! {
+                                                        <tr class="hover:bg-gray-50">
+                                                            <td class="border border-gray-300 px-3 py-2 text-right">{index + 1}</td>
+                                                            <td class="border border-gray-300 px-3 py-2 max-w-md truncate" title=title>
+                                                                {turn.prompt}
+                                                            </td>
+                                                            <td class="border border-gray-300 px-3 py-2 text-right">{turn.calls}</td>
+                                                            <td class="border border-gray-300 px-3 py-2 text-right">{turn.input_tokens}</td>
+                                                            <td class="border border-gray-300 px-3 py-2 text-right">{turn.cache_read_tokens}</td>
+                                                            <td class="border border-gray-300 px-3 py-2 text-right">{turn.cache_write_tokens}</td>
+                                                            <td class="border border-gray-300 px-3 py-2 text-right">{turn.output_tokens}</td>
+                                                            <td class="border border-gray-300 px-3 py-2 text-right">{format!("${:.6}", turn.cost)}</td>
+                                                            <td class="border border-gray-300 px-3 py-2 text-right">{format!("${:.6}", turn.cumulative_cost)}</td>
+                                                        </tr>
+                                                        }
+                                                    }).collect_view()}
+                                                </tbody>
+                                            </table>
+                                        </div>
+                                    </div>
+                                </Card>
+                            </div>
+                        }.into_any()
+                    }
+                }}
+            </div>
+        </div>
+    }
+}
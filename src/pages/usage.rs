@@ -0,0 +1,301 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use std::collections::HashMap;
+use thaw::*;
+use web_sys::FileList;
+
+use crate::components::{ChargebackReport, PageHeader, SpendAnomalies, SpendTable, UsageAnalytics};
+use crate::models::ModelPricing;
+use crate::utils::analytics::filter_by_date;
+use crate::utils::fetch_model_prices;
+use crate::utils::upload::{file_list_to_vec, read_file_text};
+use crate::utils::usage::{
+    parse_usage_file, summarize_usage, SpendRow, UsageImport, UsageRecord, UNKNOWN_LABEL,
+};
+
+/// Row errors listed per imported file; the rest are only counted.
+const MAX_ERRORS: usize = 20;
+
+/// Whether any group has a real label, so dimensions the imported data does
+/// not carry (e.g. teams in an OpenAI export) stay hidden.
+fn has_labels(rows: &[SpendRow]) -> bool {
+    rows.iter().any(|row| row.label != UNKNOWN_LABEL)
+}
+
+#[component]
+pub fn UsagePage() -> impl IntoView {
+    let (model_prices, set_model_prices) = signal(HashMap::<String, ModelPricing>::new());
+    let (loading, set_loading) = signal(true);
+    let (error, set_error) = signal(Option::<String>::None);
+
+    let imports = RwSignal::new(Vec::<(String, UsageImport)>::new());
+    let (date_from, set_date_from) = signal(String::new());
+    let (date_to, set_date_to) = signal(String::new());
+    let (upload_error, set_upload_error) = signal(Option::<String>::None);
+
+    Effect::new(move |_| {
+        spawn_local(async move {
+            match fetch_model_prices().await {
+                Ok(prices) => {
+                    set_model_prices.set(prices);
+                    set_loading.set(false);
+                }
+                Err(e) => {
+                    set_error.set(Some(format!("Failed to load model prices: {}", e)));
+                    set_loading.set(false);
+                }
+            }
+        });
+    });
+
+    let on_upload = move |files: FileList| {
+        let files = file_list_to_vec(&files);
+        spawn_local(async move {
+            set_upload_error.set(None);
+            for file in files {
+                let name = file.name();
+                let parsed = read_file_text(&file)
+                    .await
+                    .and_then(|text| parse_usage_file(&name, &text));
+                match parsed {
+                    Ok(import) => imports.update(|list| list.push((name, import))),
+                    Err(e) => set_upload_error.set(Some(format!("{}: {}", name, e))),
+                }
+            }
+        });
+    };
+
+    let records = Memo::new(move |_| {
+        imports.with(|list| {
+            list.iter()
+                .flat_map(|(_, import)| import.records.iter().cloned())
+                .collect::<Vec<UsageRecord>>()
+        })
+    });
+    let filtered = Memo::new(move |_| {
+        records.with(|records| filter_by_date(records, &date_from.get(), &date_to.get()))
+    });
+    let summary =
+        Memo::new(move |_| model_prices.with(|prices| summarize_usage(&filtered.get(), prices)));
+
+    view! {
+        <div class="min-h-screen bg-gray-100 p-6">
+            <div class="max-w-7xl mx-auto">
+                <PageHeader
+                    title="Usage"
+                    subtitle="Import provider usage exports and price them against the current catalogue"
+                />
+
+                {move || {
+                    if loading.get() {
+                        view! {
+                            <div class="text-center py-12">
+                                <Spinner size=SpinnerSize::Huge />
+                                <p class="mt-4 text-gray-600">"Loading model prices..."</p>
+                            </div>
+                        }.into_any()
+                    } else if let Some(err) = error.get() {
+                        view! {
+                            <MessageBar intent=MessageBarIntent::Error>
+                                <MessageBarBody>
+                                    <div>
+                                        <strong>"Error: "</strong>
+                                        {err}
+                                    </div>
+                                </MessageBarBody>
+                            </MessageBar>
+                        }.into_any()
+                    } else {
+                        view! {
+                            <div class="space-y-4">
+                                <Card class="w-full">
+                                    <div class="p-5">
+                                        <h3 class="text-xl font-semibold mb-4">"Usage Exports"</h3>
+                                        <Space vertical=true>
+                                            <div class="text-sm text-gray-600">
+                                                "OpenAI usage CSV or JSON, Anthropic console CSV, LiteLLM proxy spend logs (CSV or JSON), or any CSV with model, input and output token columns."
+                                            </div>
+                                            <div class="flex items-center gap-3">
+                                                <Upload multiple=true accept=".csv,.json" custom_request=on_upload>
+                                                    <Button>"Import usage"</Button>
+                                                </Upload>
+                                                <Button
+                                                    disabled=Signal::derive(move || imports.get().is_empty())
+                                                    on_click=move |_| imports.set(Vec::new())
+                                                >
+                                                    "Clear"
+                                                </Button>
+                                            </div>
+                                            {move || upload_error.get().map(|err| view! {
+                                                <MessageBar intent=MessageBarIntent::Warning>
+                                                    <MessageBarBody>{err}</MessageBarBody>
+                                                </MessageBar>
+                                            })}
+                                            {move || imports.get().into_iter().map(|(name, import)| {
+                                                let hidden = import.errors.len().saturating_sub(MAX_ERRORS);
+                                                view! {
+                                                    <div class="border-b border-gray-200 py-1 text-sm">
+                                                        <div class="flex justify-between">
+                                                            <span class="font-mono">{name}</span>
+                                                            <span class="text-gray-600">
+                                                                {format!(
+                                                                    "{} · {} rows · {} invalid",
+                                                                    import.format.label(),
+                                                                    import.records.len(),
+                                                                    import.errors.len(),
+                                                                )}
+                                                            </span>
+                                                        </div>
+                                                        <div class="text-xs text-red-600">
+                                                            {import.errors.into_iter().take(MAX_ERRORS).map(|e| view! { <div>{e}</div> }).collect_view()}
+                                                            {(hidden > 0).then(|| format!("...and {} more", hidden))}
+                                                        </div>
+                                                    </div>
+                                                }
+                                            }).collect_view()}
+                                        </Space>
+                                    </div>
+                                </Card>
+
+                                <Card class="w-full">
+                                    <div class="p-5 flex items-center gap-3 text-sm">
+                                        <span class="text-gray-600">"Date range:"</span>
+                                        <input
+                                            type="date"
+                                            class="p-2 border border-gray-300 rounded text-sm"
+                                            prop:value=date_from
+                                            on:change=move |ev| set_date_from.set(event_target_value(&ev))
+                                        />
+                                        <span class="text-gray-600">"to"</span>
+                                        <input
+                                            type="date"
+                                            class="p-2 border border-gray-300 rounded text-sm"
+                                            prop:value=date_to
+                                            on:change=move |ev| set_date_to.set(event_target_value(&ev))
+                                        />
+                                        <Button
+                                            size=ButtonSize::Small
+                                            disabled=Signal::derive(move || date_from.get().is_empty() && date_to.get().is_empty())
+                                            on_click=move |_| {
+                                                set_date_from.set(String::new());
+                                                set_date_to.set(String::new());
+                                            }
+                                        >
+                                            "All dates"
+                                        </Button>
+                                        <span class="text-gray-500">
+                                            {move || format!("{} of {} rows", filtered.with(Vec::len), records.with(Vec::len))}
+                                        </span>
+                                    </div>
+                                </Card>
+
+                                {move || {
+                                    let summary = summary.get();
+                                    let unpriced = summary.unpriced_models;
+                                    let logged = summary.total_logged_cost;
+                                    view! {
+                                        <Card class="w-full">
+                                            <div class="p-5">
+                                                <h3 class="text-xl font-semibold mb-4">"Actual Spend"</h3>
+                                                <Grid cols=4 x_gap=12>
+                                                    <GridItem>
+                                                        <Card class="bg-gray-100">
+                                                            <div class="p-3 text-center">
+                                                                <div class="text-xs text-gray-600 mb-1">"Total cost"</div>
+                                                                <div class="font-semibold text-blue-500">{format!("${:.2}", summary.total_cost)}</div>
+                                                                {logged.map(|logged| view! {
+                                                                    <div class="text-xs text-gray-500">
+                                                                        {format!("logged ${:.2} ({:+.2})", logged, summary.total_cost - logged)}
+                                                                    </div>
+                                                                })}
+                                                            </div>
+                                                        </Card>
+                                                    </GridItem>
+                                                    <GridItem>
+                                                        <Card class="bg-gray-100">
+                                                            <div class="p-3 text-center">
+                                                                <div class="text-xs text-gray-600 mb-1">"Requests"</div>
+                                                                <div class="font-semibold">{summary.total_requests}</div>
+                                                            </div>
+                                                        </Card>
+                                                    </GridItem>
+                                                    <GridItem>
+                                                        <Card class="bg-gray-100">
+                                                            <div class="p-3 text-center">
+                                                                <div class="text-xs text-gray-600 mb-1">"Input tokens"</div>
+                                                                <div class="font-semibold">{summary.total_input_tokens}</div>
+                                                            </div>
+                                                        </Card>
+                                                    </GridItem>
+                                                    <GridItem>
+                                                        <Card class="bg-gray-100">
+                                                            <div class="p-3 text-center">
+                                                                <div class="text-xs text-gray-600 mb-1">"Output tokens"</div>
+                                                                <div class="font-semibold">{summary.total_output_tokens}</div>
+                                                            </div>
+                                                        </Card>
+                                                    </GridItem>
+                                                </Grid>
+                                                {(!unpriced.is_empty()).then(|| view! {
+                                                    <div class="mt-4">
+                                                        <MessageBar intent=MessageBarIntent::Warning>
+                                                            <MessageBarBody>
+                                                                {format!("Not in the catalogue, counted as $0: {}", unpriced.join(", "))}
+                                                            </MessageBarBody>
+                                                        </MessageBar>
+                                                    </div>
+                                                })}
+                                            </div>
+                                        </Card>
+                                    }
+                                }}
+
+                                <UsageAnalytics model_prices=model_prices records=filtered />
+                                <SpendAnomalies model_prices=model_prices records=filtered />
+
+                                <SpendTable
+                                    title="Spend by Model"
+                                    label_header="Model"
+                                    rows=Signal::derive(move || summary.get().by_model)
+                                />
+                                <SpendTable
+                                    title="Spend by Day"
+                                    label_header="Day"
+                                    rows=Signal::derive(move || summary.get().by_day)
+                                />
+                                <SpendTable
+                                    title="Spend by API Key"
+                                    label_header="API Key"
+                                    rows=Signal::derive(move || summary.get().by_key)
+                                />
+                                {move || has_labels(&summary.get().by_team).then(|| view! {
+                                    <SpendTable
+                                        title="Spend by Team"
+                                        label_header="Team"
+                                        rows=Signal::derive(move || summary.get().by_team)
+                                    />
+                                })}
+                                {move || has_labels(&summary.get().by_user).then(|| view! {
+                                    <SpendTable
+                                        title="Spend by User"
+                                        label_header="User"
+                                        rows=Signal::derive(move || summary.get().by_user)
+                                    />
+                                })}
+                                {move || has_labels(&summary.get().by_tag).then(|| view! {
+                                    <SpendTable
+                                        title="Spend by Tag"
+                                        label_header="Tag"
+                                        rows=Signal::derive(move || summary.get().by_tag)
+                                        note="Requests with several tags count toward each of them."
+                                    />
+                                })}
+                                <ChargebackReport model_prices=model_prices records=filtered />
+                            </div>
+                        }.into_any()
+                    }
+                }}
+            </div>
+        </div>
+    }
+}
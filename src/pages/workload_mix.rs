@@ -0,0 +1,318 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use std::collections::HashMap;
+use thaw::*;
+
+use crate::components::{ModelSelect, PageHeader};
+use crate::models::ModelPricing;
+use crate::utils::workload::{estimate_workload, RequestTemplate};
+use crate::utils::{estimate_tokens_for_model, fetch_model_prices, preload_tokenizer};
+
+#[derive(Debug, Clone, Copy)]
+struct TemplateRow {
+    id: usize,
+    name: RwSignal<String>,
+    model: RwSignal<String>,
+    input_text: RwSignal<String>,
+    output_text: RwSignal<String>,
+    input_tokens: RwSignal<usize>,
+    output_tokens: RwSignal<usize>,
+    weight: RwSignal<f64>,
+    /// Input and output token counts, re-tokenized only when this template's
+    /// text, counts or model change.
+    tokens: Memo<(usize, usize)>,
+}
+
+impl TemplateRow {
+    fn new(
+        id: usize,
+        name: &str,
+        model: &str,
+        input_tokens: usize,
+        output_tokens: usize,
+        weight: f64,
+    ) -> Self {
+        let model = RwSignal::new(model.to_string());
+        let input_text = RwSignal::new(String::new());
+        let output_text = RwSignal::new(String::new());
+        let input_tokens = RwSignal::new(input_tokens);
+        let output_tokens = RwSignal::new(output_tokens);
+        // Token counts come from the sample text when one is given, otherwise
+        // from the entered counts.
+        let tokens = Memo::new(move |_| {
+            let model = model.get();
+            let count = |text: RwSignal<String>, fallback: RwSignal<usize>| {
+                text.with(|text| {
+                    if text.is_empty() {
+                        fallback.get()
+                    } else {
+                        estimate_tokens_for_model(text, &model)
+                    }
+                })
+            };
+            (
+                count(input_text, input_tokens),
+                count(output_text, output_tokens),
+            )
+        });
+        Self {
+            id,
+            name: RwSignal::new(name.to_string()),
+            model,
+            input_text,
+            output_text,
+            input_tokens,
+            output_tokens,
+            weight: RwSignal::new(weight),
+            tokens,
+        }
+    }
+
+    fn to_template(self) -> RequestTemplate {
+        let (input_tokens, output_tokens) = self.tokens.get();
+        RequestTemplate {
+            name: self.name.get(),
+            model: self.model.get(),
+            input_tokens,
+            output_tokens,
+            weight: self.weight.get(),
+        }
+    }
+}
+
+#[component]
+pub fn WorkloadMixPage() -> impl IntoView {
+    let (model_prices, set_model_prices) = signal(HashMap::<String, ModelPricing>::new());
+    let (loading, set_loading) = signal(true);
+    let (error, set_error) = signal(Option::<String>::None);
+
+    let requests_per_month = RwSignal::new(100_000.0_f64);
+    let (next_id, set_next_id) = signal(3_usize);
+    let templates = RwSignal::new(vec![
+        TemplateRow::new(0, "Classification", "gpt-4o-mini", 300, 5, 60.0),
+        TemplateRow::new(1, "Summarization", "gpt-4o-mini", 2_000, 300, 25.0),
+        TemplateRow::new(2, "Chat", "claude-sonnet-4-5", 1_500, 500, 15.0),
+    ]);
+
+    Effect::new(move |_| {
+        spawn_local(async move {
+            preload_tokenizer();
+        });
+    });
+
+    Effect::new(move |_| {
+        spawn_local(async move {
+            match fetch_model_prices().await {
+                Ok(prices) => {
+                    set_model_prices.set(prices);
+                    set_loading.set(false);
+                }
+                Err(e) => {
+                    set_error.set(Some(format!("Failed to load model prices: {}", e)));
+                    set_loading.set(false);
+                }
+            }
+        });
+    });
+
+    let add_template = move |_| {
+        let id = next_id.get_untracked();
+        set_next_id.set(id + 1);
+        templates.update(|list| {
+            list.push(TemplateRow::new(
+                id,
+                &format!("Request type {}", id + 1),
+                "gpt-4o-mini",
+                500,
+                200,
+                10.0,
+            ))
+        });
+    };
+
+    let estimate = move || {
+        let resolved: Vec<RequestTemplate> = templates
+            .get()
+            .into_iter()
+            .map(TemplateRow::to_template)
+            .collect();
+        model_prices.with(|prices| estimate_workload(&resolved, prices, requests_per_month.get()))
+    };
+
+    view! {
+        <div class="min-h-screen bg-gray-100 p-6">
+            <div class="max-w-7xl mx-auto">
+                <PageHeader
+                    title="Workload Mix"
+                    subtitle="Blend several request types into one monthly cost estimate"
+                />
+
+                {move || {
+                    if loading.get() {
+                        view! {
+                            <div class="text-center py-12">
+                                <Spinner size=SpinnerSize::Huge />
+                                <p class="mt-4 text-gray-600">"Loading model prices..."</p>
+                            </div>
+                        }.into_any()
+                    } else if let Some(err) = error.get() {
+                        view! {
+                            <MessageBar intent=MessageBarIntent::Error>
+                                <MessageBarBody>
+                                    <div>
+                                        <strong>"Error: "</strong>
+                                        {err}
+                                    </div>
+                                </MessageBarBody>
+                            </MessageBar>
+                        }.into_any()
+                    } else {
+                        view! {
+                            <div class="space-y-4">
+                                <Card class="w-full">
+                                    <div class="p-5 flex items-center justify-between">
+                                        <div class="flex items-center gap-3 text-sm">
+                                            <span class="text-gray-600">"Total requests per month:"</span>
+                                            <SpinButton<f64> value=requests_per_month step_page=10_000.0 min=0.0 />
+                                        </div>
+                                        <Button on_click=add_template>"Add request type"</Button>
+                                    </div>
+                                </Card>
+
+                                <div class="grid grid-cols-3 gap-4">
+                                    <For
+                                        each=move || templates.get()
+                                        key=|row| row.id
+                                        let:row
+                                    >
+                                        <Card class="w-full">
+                                            <div class="p-5">
+                                                <Space vertical=true>
+                                                    <div class="flex gap-2">
+                                                        <Input class="flex-1" value=row.name />
+                                                        <Button
+                                                            size=ButtonSize::Small
+                                                            on_click=move |_| templates.update(|list| list.retain(|r| r.id != row.id))
+                                                        >
+                                                            "Remove"
+                                                        </Button>
+                                                    </div>
+                                                    <ModelSelect model_prices=model_prices selected=row.model />
+                                                    <div class="text-sm">
+                                                        <div class="text-gray-600 mb-1">"Weight"</div>
+                                                        <SpinButton<f64> value=row.weight step_page=5.0 min=0.0 />
+                                                    </div>
+                                                    <Grid cols=2 x_gap=8>
+                                                        <GridItem>
+                                                            <div class="text-sm text-gray-600 mb-1">"Input tokens"</div>
+                                                            <SpinButton<usize>
+                                                                value=row.input_tokens
+                                                                step_page=100
+                                                                min=0
+                                                                disabled=Signal::derive(move || !row.input_text.get().is_empty())
+                                                            />
+                                                        </GridItem>
+                                                        <GridItem>
+                                                            <div class="text-sm text-gray-600 mb-1">"Output tokens"</div>
+                                                            <SpinButton<usize>
+                                                                value=row.output_tokens
+                                                                step_page=100
+                                                                min=0
+                                                                disabled=Signal::derive(move || !row.output_text.get().is_empty())
+                                                            />
+                                                        </GridItem>
+                                                    </Grid>
+                                                    <Textarea class="w-full" value=row.input_text placeholder="Optional sample input text..." />
+                                                    <Textarea class="w-full" value=row.output_text placeholder="Optional sample output text..." />
+                                                    <div class="text-xs text-gray-500">
+                                                        {move || {
+                                                            let (input, output) = row.tokens.get();
+                                                            format!("Priced as {} input / {} output tokens", input, output)
+                                                        }}
+                                                    </div>
+                                                </Space>
+                                            </div>
+                                        </Card>
+                                    </For>
+                                </div>
+
+                                <Card class="w-full">
+                                    <div class="p-5">
+                                        <h3 class="text-xl font-semibold mb-4">"Blended Monthly Cost"</h3>
+                                        {move || {
+                                            let estimate = estimate();
+                                            let unpriced = estimate.unpriced_models;
+                                            view! {
+                                                <Grid cols=3 x_gap=12>
+                                                    <GridItem>
+                                                        <Card class="bg-gray-100">
+                                                            <div class="p-3 text-center">
+                                                                <div class="text-xs text-gray-600 mb-1">"Requests / month"</div>
+                                                                <div class="font-semibold">{format!("{:.0}", estimate.total_requests)}</div>
+                                                            </div>
+                                                        </Card>
+                                                    </GridItem>
+                                                    <GridItem>
+                                                        <Card class="bg-gray-100">
+                                                            <div class="p-3 text-center">
+                                                                <div class="text-xs text-gray-600 mb-1">"Blended cost / request"</div>
+                                                                <div class="font-semibold">{format!("${:.6}", estimate.blended_cost_per_request)}</div>
+                                                            </div>
+                                                        </Card>
+                                                    </GridItem>
+                                                    <GridItem>
+                                                        <Card class="bg-gray-100">
+                                                            <div class="p-3 text-center">
+                                                                <div class="text-xs text-gray-600 mb-1">"Total / month"</div>
+                                                                <div class="font-semibold text-blue-500">{format!("${:.2}", estimate.total_cost)}</div>
+                                                            </div>
+                                                        </Card>
+                                                    </GridItem>
+                                                </Grid>
+                                                {(!unpriced.is_empty()).then(|| view! {
+                                                    <div class="mt-4">
+                                                        <MessageBar intent=MessageBarIntent::Warning>
+                                                            <MessageBarBody>
+                                                                {format!("Not in the catalogue, counted as $0: {}", unpriced.join(", "))}
+                                                            </MessageBarBody>
+                                                        </MessageBar>
+                                                    </div>
+                                                })}
+                                                <table class="w-full border-collapse text-sm mt-4">
+                                                    <thead>
+                                                        <tr class="bg-gray-200">
+                                                            <th class="border border-gray-300 px-3 py-2 text-left font-semibold">"Request Type"</th>
+                                                            <th class="border border-gray-300 px-3 py-2 text-left font-semibold">"Model"</th>
+                                                            <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Requests / Month"</th>
+                                                            <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Cost / Request"</th>
+                                                            <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Monthly Cost"</th>
+                                                            <th class="border border-gray-300 px-3 py-2 text-right font-semibold">"Share of Cost"</th>
+                                                        </tr>
+                                                    </thead>
+                                                    <tbody>
+                                                        {estimate.rows.into_iter().map(|row| view! {
+                                                            <tr class="hover:bg-gray-50">
+                                                                <td class="border border-gray-300 px-3 py-2">{row.name}</td>
+                                                                <td class="border border-gray-300 px-3 py-2 font-mono">{row.model}</td>
+                                                                <td class="border border-gray-300 px-3 py-2 text-right">{format!("{:.0}", row.requests)}</td>
+                                                                <td class="border border-gray-300 px-3 py-2 text-right">
+                                                                    {if row.priced { format!("${:.6}", row.cost_per_request) } else { "N/A".to_string() }}
+                                                                </td>
+                                                                <td class="border border-gray-300 px-3 py-2 text-right">{format!("${:.2}", row.monthly_cost)}</td>
+                                                                <td class="border border-gray-300 px-3 py-2 text-right">{format!("{:.1}%", row.cost_share * 100.0)}</td>
+                                                            </tr>
+                                                        }).collect_view()}
+                                                    </tbody>
+                                                </table>
+                                            }
+                                        }}
+                                    </div>
+                                </Card>
+                            </div>
+                        }.into_any()
+                    }
+                }}
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,218 @@
+use crate::models::ModelPricing;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AgentPlan {
+    pub initial_context_tokens: usize,
+    pub thought_tokens: usize,
+    pub action_tokens: usize,
+    pub tool_result_tokens: usize,
+    pub min_steps: usize,
+    pub max_steps: usize,
+    /// Probability that the task finishes after any step past `min_steps`.
+    pub stop_probability: f64,
+    pub use_cache: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AgentStep {
+    pub step: usize,
+    pub input_tokens: usize,
+    pub cached_tokens: usize,
+    pub output_tokens: usize,
+    pub cost: f64,
+    pub cumulative_cost: f64,
+    /// Probability that the task ends exactly after this step.
+    pub stop_probability: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AgentEstimate {
+    pub steps: Vec<AgentStep>,
+    pub expected_steps: f64,
+    pub expected_cost: f64,
+    pub p95_steps: usize,
+    pub p95_cost: f64,
+    pub max_cost: f64,
+}
+
+/// Probability of the task stopping after each step `1..=max_steps`: never
+/// before `min_steps`, geometrically afterwards, and always at `max_steps`.
+fn stop_distribution(plan: &AgentPlan) -> Vec<f64> {
+    let p = plan.stop_probability.clamp(0.0, 1.0);
+    let min = plan.min_steps.clamp(1, plan.max_steps);
+    let mut remaining = 1.0;
+    (1..=plan.max_steps)
+        .map(|step| {
+            let stop = if step == plan.max_steps {
+                remaining
+            } else if step >= min {
+                remaining * p
+            } else {
+                0.0
+            };
+            remaining -= stop;
+            stop
+        })
+        .collect()
+}
+
+/// Simulates an agent loop in which every step re-sends the task context plus
+/// all earlier tool calls and tool results.
+///
+/// For reasoning models the thought tokens are billed at the reasoning price
+/// and dropped from later context; otherwise they are ordinary output that
+/// stays in the transcript.
+pub fn simulate_agent(plan: &AgentPlan, pricing: &ModelPricing) -> AgentEstimate {
+    if plan.max_steps == 0 {
+        return AgentEstimate::default();
+    }
+
+    let input_price = pricing.input_cost_per_token.unwrap_or(0.0);
+    let output_price = pricing.output_cost_per_token.unwrap_or(0.0);
+    let reasoning_price = pricing
+        .output_cost_per_reasoning_token
+        .unwrap_or(output_price);
+    let cache_read_price = pricing.cache_read_input_token_cost.unwrap_or(input_price);
+    let cache_write_price = pricing
+        .cache_creation_input_token_cost
+        .unwrap_or(input_price);
+    let reasoning = pricing.supports_reasoning.unwrap_or(false);
+
+    let kept_thoughts = if reasoning { 0 } else { plan.thought_tokens };
+    let growth_per_step = kept_thoughts + plan.action_tokens + plan.tool_result_tokens;
+    let output_cost = if reasoning {
+        plan.thought_tokens as f64 * reasoning_price + plan.action_tokens as f64 * output_price
+    } else {
+        (plan.thought_tokens + plan.action_tokens) as f64 * output_price
+    };
+
+    let distribution = stop_distribution(plan);
+    let mut steps = Vec::with_capacity(plan.max_steps);
+    let mut previous_input = 0;
+    let mut cumulative_cost = 0.0;
+
+    for (index, stop_probability) in distribution.into_iter().enumerate() {
+        let step = index + 1;
+        let input_tokens = plan.initial_context_tokens + index * growth_per_step;
+        let (cached_tokens, input_cost) = if plan.use_cache {
+            let written = input_tokens - previous_input;
+            (
+                previous_input,
+                previous_input as f64 * cache_read_price + written as f64 * cache_write_price,
+            )
+        } else {
+            (0, input_tokens as f64 * input_price)
+        };
+        let cost = input_cost + output_cost;
+        cumulative_cost += cost;
+        previous_input = input_tokens;
+
+        steps.push(AgentStep {
+            step,
+            input_tokens,
+            cached_tokens,
+            output_tokens: plan.thought_tokens + plan.action_tokens,
+            cost,
+            cumulative_cost,
+            stop_probability,
+        });
+    }
+
+    let expected_steps = steps
+        .iter()
+        .map(|s| s.step as f64 * s.stop_probability)
+        .sum();
+    let expected_cost = steps
+        .iter()
+        .map(|s| s.cumulative_cost * s.stop_probability)
+        .sum();
+
+    let mut cumulative_probability = 0.0;
+    let p95 = steps
+        .iter()
+        .find(|s| {
+            cumulative_probability += s.stop_probability;
+            cumulative_probability >= 0.95 - f64::EPSILON
+        })
+        .or(steps.last())
+        .cloned()
+        .unwrap_or_default();
+
+    AgentEstimate {
+        expected_steps,
+        expected_cost,
+        p95_steps: p95.step,
+        p95_cost: p95.cumulative_cost,
+        max_cost: cumulative_cost,
+        steps,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn plan() -> AgentPlan {
+        AgentPlan {
+            initial_context_tokens: 1_000,
+            thought_tokens: 50,
+            action_tokens: 20,
+            tool_result_tokens: 130,
+            min_steps: 2,
+            max_steps: 4,
+            stop_probability: 0.5,
+            use_cache: false,
+        }
+    }
+
+    fn pricing(reasoning: bool) -> ModelPricing {
+        serde_json::from_value(json!({
+            "input_cost_per_token": 1e-6,
+            "output_cost_per_token": 4e-6,
+            "output_cost_per_reasoning_token": 8e-6,
+            "supports_reasoning": reasoning,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn stop_distribution_is_geometric_after_the_minimum() {
+        assert_eq!(stop_distribution(&plan()), [0.0, 0.5, 0.25, 0.25]);
+        let certain = AgentPlan {
+            stop_probability: 1.0,
+            ..plan()
+        };
+        assert_eq!(stop_distribution(&certain), [0.0, 1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn context_grows_by_every_step() {
+        let estimate = simulate_agent(&plan(), &pricing(false));
+        let inputs: Vec<usize> = estimate.steps.iter().map(|s| s.input_tokens).collect();
+        assert_eq!(inputs, [1_000, 1_200, 1_400, 1_600]);
+        assert!((estimate.expected_steps - 2.75).abs() < 1e-12);
+        assert_eq!(estimate.p95_steps, 4);
+        assert_eq!(estimate.max_cost, estimate.steps[3].cumulative_cost);
+    }
+
+    #[test]
+    fn reasoning_tokens_leave_the_context() {
+        let estimate = simulate_agent(&plan(), &pricing(true));
+        assert_eq!(estimate.steps[1].input_tokens, 1_150);
+        let first = 1_000.0 * 1e-6 + 50.0 * 8e-6 + 20.0 * 4e-6;
+        assert!((estimate.steps[0].cost - first).abs() < 1e-12);
+    }
+
+    #[test]
+    fn no_steps_is_empty() {
+        let plan = AgentPlan {
+            max_steps: 0,
+            ..plan()
+        };
+        assert_eq!(
+            simulate_agent(&plan, &pricing(false)),
+            AgentEstimate::default()
+        );
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::models::ModelPricing;
+use crate::utils::date::week_start;
+use crate::utils::usage::{resolve_model, UsageRecord, UNKNOWN_LABEL};
+
+/// Label of the stack that collects everything outside the top N.
+pub const OTHER_LABEL: &str = "Other";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Granularity {
+    Day,
+    Week,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StackBy {
+    Model,
+    Provider,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UsageTimeSeries {
+    /// Period labels: the day, or the Monday starting the week.
+    pub periods: Vec<String>,
+    /// Cost per period for each of the top groups, most expensive first,
+    /// followed by an "Other" stack when groups were cut.
+    pub stacks: Vec<(String, Vec<f64>)>,
+    pub input_tokens: Vec<f64>,
+    pub output_tokens: Vec<f64>,
+    pub cost: Vec<f64>,
+}
+
+/// Keeps records dated within `from..=to` (YYYY-MM-DD, either bound optional).
+/// Undated records only pass when no bound is set.
+pub fn filter_by_date(records: &[UsageRecord], from: &str, to: &str) -> Vec<UsageRecord> {
+    if from.is_empty() && to.is_empty() {
+        return records.to_vec();
+    }
+    records
+        .iter()
+        .filter(|record| {
+            record.date.as_deref().is_some_and(|date| {
+                (from.is_empty() || date >= from) && (to.is_empty() || date <= to)
+            })
+        })
+        .cloned()
+        .collect()
+}
+
+fn stack_label(
+    record: &UsageRecord,
+    stack_by: StackBy,
+    prices: &HashMap<String, ModelPricing>,
+) -> String {
+    match stack_by {
+        StackBy::Model => record.model.clone(),
+        StackBy::Provider => resolve_model(&record.model, prices)
+            .and_then(|key| prices.get(&key))
+            .and_then(|pricing| pricing.litellm_provider.clone())
+            .unwrap_or_else(|| UNKNOWN_LABEL.to_string()),
+    }
+}
+
+/// Buckets dated records into days or weeks, stacking cost by the `top_n`
+/// most expensive models or providers. `costs` are the records' prices, in
+/// the same order.
+pub fn build_time_series(
+    records: &[UsageRecord],
+    costs: &[f64],
+    prices: &HashMap<String, ModelPricing>,
+    granularity: Granularity,
+    stack_by: StackBy,
+    top_n: usize,
+) -> UsageTimeSeries {
+    let period_of = |record: &UsageRecord| {
+        let date = record.date.as_deref()?;
+        match granularity {
+            Granularity::Day => Some(date.to_string()),
+            Granularity::Week => week_start(date),
+        }
+    };
+
+    let mut periods: Vec<String> = records.iter().filter_map(period_of).collect();
+    periods.sort();
+    periods.dedup();
+    let index: HashMap<&str, usize> = periods
+        .iter()
+        .enumerate()
+        .map(|(i, period)| (period.as_str(), i))
+        .collect();
+
+    let mut totals: BTreeMap<String, f64> = BTreeMap::new();
+    for (record, cost) in records.iter().zip(costs) {
+        if period_of(record).is_some() {
+            *totals
+                .entry(stack_label(record, stack_by, prices))
+                .or_default() += cost;
+        }
+    }
+    let mut ranked: Vec<(String, f64)> = totals.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+    let top: Vec<String> = ranked
+        .iter()
+        .take(top_n.max(1))
+        .map(|(label, _)| label.clone())
+        .collect();
+    let has_other = ranked.len() > top.len();
+
+    let n = periods.len();
+    let mut stacks: Vec<(String, Vec<f64>)> = top
+        .iter()
+        .map(|label| (label.clone(), vec![0.0; n]))
+        .collect();
+    if has_other {
+        stacks.push((OTHER_LABEL.to_string(), vec![0.0; n]));
+    }
+    let mut series = UsageTimeSeries {
+        input_tokens: vec![0.0; n],
+        output_tokens: vec![0.0; n],
+        cost: vec![0.0; n],
+        ..UsageTimeSeries::default()
+    };
+
+    for (record, cost) in records.iter().zip(costs) {
+        let Some(i) = period_of(record).and_then(|period| index.get(period.as_str()).copied())
+        else {
+            continue;
+        };
+        let label = stack_label(record, stack_by, prices);
+        let stack = top
+            .iter()
+            .position(|top_label| *top_label == label)
+            .unwrap_or(top.len());
+        stacks[stack].1[i] += cost;
+        series.input_tokens[i] += record.total_input_tokens() as f64;
+        series.output_tokens[i] += record.output_tokens as f64;
+        series.cost[i] += cost;
+    }
+
+    series.periods = periods;
+    series.stacks = stacks;
+    series
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::usage::parse_usage_csv;
+    use serde_json::json;
+
+    fn records() -> Vec<UsageRecord> {
+        let csv = "date,model,input_tokens,output_tokens\n\
+                   2025-01-13,gpt-4o,100,10\n\
+                   2025-01-14,claude-sonnet-4-5,200,20\n\
+                   2025-01-20,gpt-4o-mini,300,30\n\
+                   ,gpt-4o,400,40\n";
+        parse_usage_csv(csv).unwrap().records
+    }
+
+    fn prices() -> HashMap<String, ModelPricing> {
+        serde_json::from_value(json!({
+            "gpt-4o": {"litellm_provider": "openai"},
+            "gpt-4o-mini": {"litellm_provider": "openai"},
+            "claude-sonnet-4-5": {"litellm_provider": "anthropic"},
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn filters_dated_records() {
+        let records = records();
+        assert_eq!(filter_by_date(&records, "", "").len(), 4);
+        assert_eq!(filter_by_date(&records, "2025-01-14", "").len(), 2);
+        assert_eq!(filter_by_date(&records, "", "2025-01-13").len(), 1);
+    }
+
+    #[test]
+    fn buckets_days_and_cuts_to_the_top_models() {
+        let costs = [1.0, 3.0, 2.0, 5.0];
+        let series = build_time_series(
+            &records(),
+            &costs,
+            &prices(),
+            Granularity::Day,
+            StackBy::Model,
+            2,
+        );
+        assert_eq!(series.periods, ["2025-01-13", "2025-01-14", "2025-01-20"]);
+        let labels: Vec<&str> = series.stacks.iter().map(|(l, _)| l.as_str()).collect();
+        assert_eq!(labels, ["claude-sonnet-4-5", "gpt-4o-mini", OTHER_LABEL]);
+        assert_eq!(series.stacks[2].1, [1.0, 0.0, 0.0]);
+        assert_eq!(series.cost, [1.0, 3.0, 2.0]);
+        assert_eq!(series.input_tokens, [100.0, 200.0, 300.0]);
+    }
+
+    #[test]
+    fn buckets_weeks_by_provider() {
+        let costs = [1.0, 3.0, 2.0, 5.0];
+        let series = build_time_series(
+            &records(),
+            &costs,
+            &prices(),
+            Granularity::Week,
+            StackBy::Provider,
+            5,
+        );
+        assert_eq!(series.periods, ["2025-01-13", "2025-01-20"]);
+        assert_eq!(
+            series.stacks,
+            [
+                ("anthropic".to_string(), vec![3.0, 0.0]),
+                ("openai".to_string(), vec![1.0, 2.0]),
+            ]
+        );
+    }
+}
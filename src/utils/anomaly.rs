@@ -0,0 +1,276 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::models::ModelPricing;
+use crate::utils::date::{format_days, parse_days};
+use crate::utils::usage::{record_cost_split, resolve_model, UsageRecord, UNKNOWN_LABEL};
+
+/// Fewest baseline days needed before a day can be judged.
+pub const MIN_HISTORY: usize = 3;
+/// The baseline deviation never drops below this share of the baseline, so
+/// a perfectly flat history does not turn every cent of change into a spike.
+const STDDEV_FLOOR: f64 = 0.1;
+
+/// Which cost series are scanned for spikes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnomalyScope {
+    Total,
+    Model,
+    Key,
+}
+
+impl AnomalyScope {
+    fn label(&self, record: &UsageRecord) -> String {
+        match self {
+            AnomalyScope::Total => "All usage".to_string(),
+            AnomalyScope::Model => record.model.clone(),
+            AnomalyScope::Key => record
+                .api_key
+                .clone()
+                .unwrap_or_else(|| UNKNOWN_LABEL.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Threshold {
+    /// Standard deviations above the rolling mean.
+    ZScore(f64),
+    /// Percentage above the rolling mean.
+    Percent(f64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnomalySettings {
+    pub scope: AnomalyScope,
+    /// Days before the judged day that form its baseline.
+    pub window: usize,
+    pub threshold: Threshold,
+    /// Days cheaper than this are never flagged.
+    pub min_cost: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenDirection {
+    Input,
+    Output,
+}
+
+impl TokenDirection {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TokenDirection::Input => "input",
+            TokenDirection::Output => "output",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Anomaly {
+    pub date: String,
+    /// The model, key or "All usage", depending on the scope.
+    pub group: String,
+    pub cost: f64,
+    pub baseline: f64,
+    /// Deviations above the baseline; `None`, like the percentage, when the
+    /// baseline is zero and the spend is new.
+    pub z_score: Option<f64>,
+    pub percent_change: Option<f64>,
+    pub driver: SpikeDriver,
+}
+
+/// The model whose cost rose the most against its own baseline, and whether
+/// its input or output side rose more.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpikeDriver {
+    pub model: String,
+    pub increase: f64,
+    pub direction: TokenDirection,
+    pub direction_increase: f64,
+}
+
+/// Input and output cost per model for one day of one group.
+type DayCosts = BTreeMap<String, (f64, f64)>;
+
+fn day_total(day: &DayCosts) -> f64 {
+    day.values().map(|(input, output)| input + output).sum()
+}
+
+/// Scans each group's daily cost against the mean of the preceding `window`
+/// days and returns the days that spike past the threshold, largest
+/// increase first. Days without usage inside the imported range count as zero.
+pub fn detect_anomalies(
+    records: &[UsageRecord],
+    prices: &HashMap<String, ModelPricing>,
+    settings: &AnomalySettings,
+) -> Vec<Anomaly> {
+    let dated: Vec<(i64, &UsageRecord)> = records
+        .iter()
+        .filter_map(|record| Some((parse_days(record.date.as_deref()?)?, record)))
+        .collect();
+    let (Some(first), Some(last)) = (
+        dated.iter().map(|(day, _)| *day).min(),
+        dated.iter().map(|(day, _)| *day).max(),
+    ) else {
+        return Vec::new();
+    };
+    let days = (last - first + 1) as usize;
+
+    let mut groups: BTreeMap<String, Vec<DayCosts>> = BTreeMap::new();
+    for (day, record) in dated {
+        let Some(pricing) = resolve_model(&record.model, prices).and_then(|key| prices.get(&key))
+        else {
+            continue;
+        };
+        let (input, output) = record_cost_split(record, pricing);
+        let series = groups
+            .entry(settings.scope.label(record))
+            .or_insert_with(|| vec![DayCosts::new(); days]);
+        let entry = series[(day - first) as usize]
+            .entry(record.model.clone())
+            .or_default();
+        entry.0 += input;
+        entry.1 += output;
+    }
+
+    let window = settings.window.max(MIN_HISTORY);
+    let mut anomalies = Vec::new();
+    for (group, series) in &groups {
+        let totals: Vec<f64> = series.iter().map(day_total).collect();
+        for i in MIN_HISTORY..days {
+            let cost = totals[i];
+            if cost < settings.min_cost {
+                continue;
+            }
+            let history = &totals[i.saturating_sub(window)..i];
+            let mean = history.iter().fold(0.0, |sum, v| sum + v) / history.len() as f64;
+            let variance =
+                history.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / history.len() as f64;
+            let stddev = variance.sqrt().max(mean * STDDEV_FLOOR);
+            let z_score = (mean > 0.0).then(|| (cost - mean) / stddev);
+            let percent_change = (mean > 0.0).then(|| (cost - mean) / mean * 100.0);
+            let flagged = match settings.threshold {
+                Threshold::ZScore(limit) => z_score.is_none_or(|z| z >= limit),
+                Threshold::Percent(limit) => percent_change.is_none_or(|change| change >= limit),
+            };
+            if !flagged {
+                continue;
+            }
+            anomalies.push(Anomaly {
+                date: format_days(first + i as i64),
+                group: group.clone(),
+                cost,
+                baseline: mean,
+                z_score,
+                percent_change,
+                driver: explain(&series[i.saturating_sub(window)..i], &series[i]),
+            });
+        }
+    }
+    anomalies.sort_by(|a, b| (b.cost - b.baseline).total_cmp(&(a.cost - a.baseline)));
+    anomalies
+}
+
+/// Attributes a spike to the model, and the token direction within it, whose
+/// cost rose the most over its average across the baseline days.
+fn explain(history: &[DayCosts], day: &DayCosts) -> SpikeDriver {
+    let baseline_of = |model: &str| {
+        let (input, output) = history
+            .iter()
+            .filter_map(|costs| costs.get(model))
+            .fold((0.0, 0.0), |acc, (input, output)| {
+                (acc.0 + input, acc.1 + output)
+            });
+        let n = history.len().max(1) as f64;
+        (input / n, output / n)
+    };
+
+    let (driver_model, (input_increase, output_increase)) = day
+        .iter()
+        .map(|(model, (input, output))| {
+            let (base_input, base_output) = baseline_of(model);
+            (model.clone(), (input - base_input, output - base_output))
+        })
+        .max_by(|a, b| (a.1 .0 + a.1 .1).total_cmp(&(b.1 .0 + b.1 .1)))
+        .unwrap_or_else(|| (UNKNOWN_LABEL.to_string(), (0.0, 0.0)));
+    let (direction, direction_increase) = if output_increase >= input_increase {
+        (TokenDirection::Output, output_increase)
+    } else {
+        (TokenDirection::Input, input_increase)
+    };
+
+    SpikeDriver {
+        model: driver_model,
+        increase: input_increase + output_increase,
+        direction,
+        direction_increase,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::usage::parse_usage_csv;
+    use serde_json::json;
+
+    fn records() -> Vec<UsageRecord> {
+        let mut csv = String::from("date,model,api_key,input_tokens,output_tokens\n");
+        for day in 10..=14 {
+            csv.push_str(&format!("2025-01-{},gpt-4o,key-a,1000,0\n", day));
+        }
+        csv.push_str("2025-01-15,gpt-4o,key-a,1000,10000\n");
+        csv.push_str("2025-01-15,unknown-model,key-b,99999,99999\n");
+        parse_usage_csv(&csv).unwrap().records
+    }
+
+    fn prices() -> HashMap<String, ModelPricing> {
+        serde_json::from_value(json!({
+            "gpt-4o": {"input_cost_per_token": 1e-6, "output_cost_per_token": 1e-6},
+        }))
+        .unwrap()
+    }
+
+    fn settings(threshold: Threshold) -> AnomalySettings {
+        AnomalySettings {
+            scope: AnomalyScope::Key,
+            window: 7,
+            threshold,
+            min_cost: 0.0,
+        }
+    }
+
+    #[test]
+    fn flags_a_spike_and_explains_it() {
+        let anomalies = detect_anomalies(&records(), &prices(), &settings(Threshold::ZScore(3.0)));
+        assert_eq!(anomalies.len(), 1);
+        let anomaly = &anomalies[0];
+        assert_eq!(anomaly.date, "2025-01-15");
+        assert_eq!(anomaly.group, "key-a");
+        assert!((anomaly.baseline - 1e-3).abs() < 1e-12);
+        assert!((anomaly.percent_change.unwrap() - 1_000.0).abs() < 1e-6);
+        assert_eq!(anomaly.driver.model, "gpt-4o");
+        assert_eq!(anomaly.driver.direction, TokenDirection::Output);
+        assert!((anomaly.driver.direction_increase - 1e-2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn thresholds_and_minimum_cost_suppress_flags() {
+        let records = records();
+        assert!(
+            detect_anomalies(&records, &prices(), &settings(Threshold::Percent(2_000.0)))
+                .is_empty()
+        );
+        let cheap = AnomalySettings {
+            min_cost: 1.0,
+            ..settings(Threshold::ZScore(3.0))
+        };
+        assert!(detect_anomalies(&records, &prices(), &cheap).is_empty());
+    }
+
+    #[test]
+    fn needs_enough_history() {
+        // Three days of data leave no day with a full baseline.
+        let records = &records()[3..];
+        let settings = settings(Threshold::ZScore(0.0));
+        assert!(detect_anomalies(records, &prices(), &settings).is_empty());
+    }
+}
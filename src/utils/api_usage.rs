@@ -0,0 +1,482 @@
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::models::ModelPricing;
+use crate::utils::chat::estimate_body_tokens;
+use crate::utils::usage::resolve_model;
+
+/// Prompt size above which long-context prices apply, where listed.
+const LONG_CONTEXT_TOKENS: usize = 200_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiProvider {
+    OpenAiChat,
+    OpenAiResponses,
+    Anthropic,
+    Gemini,
+}
+
+impl ApiProvider {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ApiProvider::OpenAiChat => "OpenAI Chat Completions",
+            ApiProvider::OpenAiResponses => "OpenAI Responses",
+            ApiProvider::Anthropic => "Anthropic Messages",
+            ApiProvider::Gemini => "Gemini generateContent",
+        }
+    }
+}
+
+/// Billed tokens of one API call, split so that every count is priced once.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ApiUsage {
+    /// Uncached text input.
+    pub input_tokens: usize,
+    pub cached_tokens: usize,
+    /// Cache writes at the default (5 minute) TTL.
+    pub cache_write_tokens: usize,
+    pub cache_write_1h_tokens: usize,
+    pub audio_input_tokens: usize,
+    /// Visible text output, without reasoning or audio.
+    pub output_tokens: usize,
+    pub reasoning_tokens: usize,
+    pub audio_output_tokens: usize,
+}
+
+impl ApiUsage {
+    pub fn prompt_tokens(&self) -> usize {
+        self.input_tokens
+            + self.cached_tokens
+            + self.cache_write_tokens
+            + self.cache_write_1h_tokens
+            + self.audio_input_tokens
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiCall {
+    pub provider: ApiProvider,
+    pub model: String,
+    pub usage: ApiUsage,
+    /// Our own count of the request's prompt tokens, when a request was given.
+    pub estimated_prompt_tokens: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CostComponent {
+    pub label: &'static str,
+    pub tokens: usize,
+    pub price_per_token: f64,
+    pub cost: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallCost {
+    /// Catalogue key the model resolved to.
+    pub model_key: String,
+    /// Whether long-context prices applied to input and output.
+    pub long_context: bool,
+    pub components: Vec<CostComponent>,
+    pub total: f64,
+}
+
+fn count(value: &Value, pointer: &str) -> usize {
+    value.pointer(pointer).and_then(Value::as_u64).unwrap_or(0) as usize
+}
+
+/// Recognises the shape of a bare usage block.
+fn detect_provider(usage: &Value) -> Option<ApiProvider> {
+    if usage.get("promptTokenCount").is_some() {
+        Some(ApiProvider::Gemini)
+    } else if usage.get("prompt_tokens").is_some() {
+        Some(ApiProvider::OpenAiChat)
+    } else if [
+        "input_tokens_details",
+        "output_tokens_details",
+        "cached_input_tokens",
+    ]
+    .iter()
+    .any(|key| usage.get(*key).is_some())
+    {
+        Some(ApiProvider::OpenAiResponses)
+    } else if usage.get("input_tokens").is_some() {
+        Some(ApiProvider::Anthropic)
+    } else {
+        None
+    }
+}
+
+fn read_usage(provider: ApiProvider, usage: &Value) -> ApiUsage {
+    match provider {
+        // Prompt and completion totals include their cached, audio and
+        // reasoning details.
+        ApiProvider::OpenAiChat => {
+            let cached_tokens = count(usage, "/prompt_tokens_details/cached_tokens");
+            let audio_input_tokens = count(usage, "/prompt_tokens_details/audio_tokens");
+            let reasoning_tokens = count(usage, "/completion_tokens_details/reasoning_tokens");
+            let audio_output_tokens = count(usage, "/completion_tokens_details/audio_tokens");
+            ApiUsage {
+                input_tokens: count(usage, "/prompt_tokens")
+                    .saturating_sub(cached_tokens + audio_input_tokens),
+                cached_tokens,
+                audio_input_tokens,
+                output_tokens: count(usage, "/completion_tokens")
+                    .saturating_sub(reasoning_tokens + audio_output_tokens),
+                reasoning_tokens,
+                audio_output_tokens,
+                ..ApiUsage::default()
+            }
+        }
+        ApiProvider::OpenAiResponses => {
+            // Codex CLI logs flatten the details into `cached_input_tokens`
+            // and `reasoning_output_tokens`.
+            let cached_tokens = count(usage, "/input_tokens_details/cached_tokens")
+                + count(usage, "/cached_input_tokens");
+            let reasoning_tokens = count(usage, "/output_tokens_details/reasoning_tokens")
+                + count(usage, "/reasoning_output_tokens");
+            ApiUsage {
+                input_tokens: count(usage, "/input_tokens").saturating_sub(cached_tokens),
+                cached_tokens,
+                output_tokens: count(usage, "/output_tokens").saturating_sub(reasoning_tokens),
+                reasoning_tokens,
+                ..ApiUsage::default()
+            }
+        }
+        // Input excludes cache reads and writes; output includes any thinking.
+        ApiProvider::Anthropic => {
+            let cache_writes = count(usage, "/cache_creation_input_tokens");
+            let cache_write_1h_tokens =
+                count(usage, "/cache_creation/ephemeral_1h_input_tokens").min(cache_writes);
+            ApiUsage {
+                input_tokens: count(usage, "/input_tokens"),
+                cached_tokens: count(usage, "/cache_read_input_tokens"),
+                cache_write_tokens: cache_writes - cache_write_1h_tokens,
+                cache_write_1h_tokens,
+                output_tokens: count(usage, "/output_tokens"),
+                ..ApiUsage::default()
+            }
+        }
+        // The prompt count includes cached content; thoughts are billed as
+        // output on top of the candidates.
+        ApiProvider::Gemini => {
+            let cached_tokens = count(usage, "/cachedContentTokenCount");
+            let audio_input_tokens = usage
+                .get("promptTokensDetails")
+                .and_then(Value::as_array)
+                .map(|details| {
+                    details
+                        .iter()
+                        .filter(|d| d.get("modality").and_then(Value::as_str) == Some("AUDIO"))
+                        .map(|d| count(d, "/tokenCount"))
+                        .sum()
+                })
+                .unwrap_or(0);
+            ApiUsage {
+                input_tokens: count(usage, "/promptTokenCount")
+                    .saturating_sub(cached_tokens + audio_input_tokens),
+                cached_tokens,
+                audio_input_tokens,
+                output_tokens: count(usage, "/candidatesTokenCount"),
+                reasoning_tokens: count(usage, "/thoughtsTokenCount"),
+                ..ApiUsage::default()
+            }
+        }
+    }
+}
+
+/// Reads an OpenAI, Anthropic or Gemini usage block into billed tokens.
+pub fn parse_usage_block(usage: &Value) -> Option<(ApiProvider, ApiUsage)> {
+    let provider = detect_provider(usage)?;
+    Some((provider, read_usage(provider, usage)))
+}
+
+/// Reads a pasted response (and optionally its request) from the OpenAI,
+/// Anthropic or Gemini APIs: the provider, the model and the billed usage.
+pub fn parse_api_call(request: &str, response: &str) -> Result<ApiCall, String> {
+    let response: Value = serde_json::from_str(response.trim())
+        .map_err(|e| format!("Invalid response JSON: {}", e))?;
+    let request: Value = if request.trim().is_empty() {
+        Value::Null
+    } else {
+        serde_json::from_str(request.trim()).map_err(|e| format!("Invalid request JSON: {}", e))?
+    };
+
+    let block = response
+        .get("usageMetadata")
+        .or_else(|| response.get("usage"))
+        .ok_or("response has no \"usage\" or \"usageMetadata\" block")?;
+    // A Responses payload says so itself, even when its usage block has no
+    // details to tell it apart from an Anthropic one.
+    let provider = if response.get("object").and_then(Value::as_str) == Some("response") {
+        ApiProvider::OpenAiResponses
+    } else {
+        detect_provider(block).ok_or("unrecognised usage block")?
+    };
+    let usage = read_usage(provider, block);
+    let model = ["model", "modelVersion"]
+        .iter()
+        .find_map(|key| response.get(*key).and_then(Value::as_str))
+        .or_else(|| request.get("model").and_then(Value::as_str))
+        .ok_or("no model in the response or request")?
+        .to_string();
+    let estimated_prompt_tokens = (!request.is_null())
+        .then(|| estimate_body_tokens(&request, &model).ok())
+        .flatten();
+
+    Ok(ApiCall {
+        provider,
+        usage,
+        model,
+        estimated_prompt_tokens,
+    })
+}
+
+/// Prices every component of a model's usage with its catalogue entry.
+/// Components without their own price fall back to the plain input or output
+/// price; zero-token components are left out. Long prompts use the above-200k
+/// prices, and cache prices without a long-context variant are scaled by the
+/// same factor as the input price.
+pub fn price_usage(
+    model: &str,
+    usage: &ApiUsage,
+    prices: &HashMap<String, ModelPricing>,
+) -> Result<CallCost, String> {
+    let model_key = resolve_model(model, prices)
+        .ok_or_else(|| format!("\"{}\" is not in the catalogue", model))?;
+    let pricing = &prices[&model_key];
+
+    let long_context = usage.prompt_tokens() > LONG_CONTEXT_TOKENS
+        && (pricing.input_cost_per_token_above_200k_tokens.is_some()
+            || pricing.output_cost_per_token_above_200k_tokens.is_some());
+    let (input_price, output_price) = if long_context {
+        (
+            pricing
+                .input_cost_per_token_above_200k_tokens
+                .or(pricing.input_cost_per_token),
+            pricing
+                .output_cost_per_token_above_200k_tokens
+                .or(pricing.output_cost_per_token),
+        )
+    } else {
+        (pricing.input_cost_per_token, pricing.output_cost_per_token)
+    };
+    let input_price = input_price.unwrap_or(0.0);
+    let output_price = output_price.unwrap_or(0.0);
+    let long_context_scale = match pricing.input_cost_per_token {
+        Some(base) if long_context && base > 0.0 => input_price / base,
+        _ => 1.0,
+    };
+    let cache_price = |base: Option<f64>, above_200k: Option<f64>| {
+        if long_context {
+            above_200k.or(base.map(|price| price * long_context_scale))
+        } else {
+            base
+        }
+    };
+    let cache_read_price = cache_price(
+        pricing.cache_read_input_token_cost,
+        pricing.cache_read_input_token_cost_above_200k_tokens,
+    )
+    .unwrap_or(input_price);
+    let cache_write_price = cache_price(
+        pricing.cache_creation_input_token_cost,
+        pricing.cache_creation_input_token_cost_above_200k_tokens,
+    )
+    .unwrap_or(input_price);
+
+    let components: Vec<CostComponent> = [
+        ("Input", usage.input_tokens, input_price),
+        ("Cache read", usage.cached_tokens, cache_read_price),
+        ("Cache write", usage.cache_write_tokens, cache_write_price),
+        (
+            "Cache write (1h)",
+            usage.cache_write_1h_tokens,
+            cache_price(pricing.cache_creation_input_token_cost_above_1hr, None)
+                .unwrap_or(cache_write_price),
+        ),
+        (
+            "Audio input",
+            usage.audio_input_tokens,
+            pricing.input_cost_per_audio_token.unwrap_or(input_price),
+        ),
+        ("Output", usage.output_tokens, output_price),
+        (
+            "Reasoning",
+            usage.reasoning_tokens,
+            pricing
+                .output_cost_per_reasoning_token
+                .unwrap_or(output_price),
+        ),
+        (
+            "Audio output",
+            usage.audio_output_tokens,
+            pricing.output_cost_per_audio_token.unwrap_or(output_price),
+        ),
+    ]
+    .into_iter()
+    .filter(|(_, tokens, _)| *tokens > 0)
+    .map(|(label, tokens, price_per_token)| CostComponent {
+        label,
+        tokens,
+        price_per_token,
+        cost: tokens as f64 * price_per_token,
+    })
+    .collect();
+
+    Ok(CallCost {
+        model_key,
+        long_context,
+        total: components.iter().map(|c| c.cost).sum(),
+        components,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn prices() -> HashMap<String, ModelPricing> {
+        serde_json::from_value(json!({
+            "claude-sonnet-4-5": {
+                "input_cost_per_token": 3e-6,
+                "output_cost_per_token": 1.5e-5,
+                "input_cost_per_token_above_200k_tokens": 6e-6,
+                "output_cost_per_token_above_200k_tokens": 2.25e-5,
+                "cache_read_input_token_cost": 3e-7,
+                "cache_read_input_token_cost_above_200k_tokens": 6e-7,
+                "cache_creation_input_token_cost": 3.75e-6,
+                "cache_creation_input_token_cost_above_1hr": 6e-6,
+            },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn reads_each_usage_shape() {
+        let (provider, usage) = parse_usage_block(&json!({
+            "prompt_tokens": 100,
+            "completion_tokens": 50,
+            "prompt_tokens_details": {"cached_tokens": 40},
+            "completion_tokens_details": {"reasoning_tokens": 20},
+        }))
+        .unwrap();
+        assert_eq!(provider, ApiProvider::OpenAiChat);
+        assert_eq!(
+            (
+                usage.input_tokens,
+                usage.cached_tokens,
+                usage.output_tokens,
+                usage.reasoning_tokens
+            ),
+            (60, 40, 30, 20)
+        );
+
+        let (provider, usage) = parse_usage_block(&json!({
+            "input_tokens": 10,
+            "cache_read_input_tokens": 100,
+            "cache_creation_input_tokens": 30,
+            "cache_creation": {"ephemeral_1h_input_tokens": 20},
+            "output_tokens": 5,
+        }))
+        .unwrap();
+        assert_eq!(provider, ApiProvider::Anthropic);
+        assert_eq!(
+            (usage.cache_write_tokens, usage.cache_write_1h_tokens),
+            (10, 20)
+        );
+        assert_eq!(usage.prompt_tokens(), 140);
+
+        let (provider, usage) = parse_usage_block(&json!({
+            "promptTokenCount": 100,
+            "cachedContentTokenCount": 60,
+            "candidatesTokenCount": 10,
+            "thoughtsTokenCount": 5,
+        }))
+        .unwrap();
+        assert_eq!(provider, ApiProvider::Gemini);
+        assert_eq!((usage.input_tokens, usage.reasoning_tokens), (40, 5));
+
+        let (provider, usage) = parse_usage_block(&json!({
+            "input_tokens": 100,
+            "input_tokens_details": {"cached_tokens": 80},
+            "output_tokens": 10,
+        }))
+        .unwrap();
+        assert_eq!(provider, ApiProvider::OpenAiResponses);
+        assert_eq!(usage.input_tokens, 20);
+
+        assert_eq!(parse_usage_block(&json!({"total": 1})), None);
+    }
+
+    #[test]
+    fn prices_every_component() {
+        let usage = ApiUsage {
+            input_tokens: 1_000,
+            cached_tokens: 1_000,
+            cache_write_tokens: 1_000,
+            cache_write_1h_tokens: 1_000,
+            output_tokens: 1_000,
+            ..ApiUsage::default()
+        };
+        let cost = price_usage("anthropic/claude-sonnet-4-5", &usage, &prices()).unwrap();
+        assert_eq!(cost.model_key, "claude-sonnet-4-5");
+        assert!(!cost.long_context);
+        let prices: Vec<f64> = cost.components.iter().map(|c| c.price_per_token).collect();
+        assert_eq!(prices, [3e-6, 3e-7, 3.75e-6, 6e-6, 1.5e-5]);
+        assert!(price_usage("unknown", &usage, &self::prices()).is_err());
+    }
+
+    #[test]
+    fn long_prompts_use_long_context_cache_prices() {
+        let usage = ApiUsage {
+            input_tokens: 1_000,
+            cached_tokens: 200_000,
+            cache_write_tokens: 1_000,
+            output_tokens: 1_000,
+            ..ApiUsage::default()
+        };
+        let cost = price_usage("claude-sonnet-4-5", &usage, &prices()).unwrap();
+        assert!(cost.long_context);
+        let price = |label: &str| {
+            cost.components
+                .iter()
+                .find(|c| c.label == label)
+                .map(|c| c.price_per_token)
+                .unwrap()
+        };
+        assert_eq!(price("Input"), 6e-6);
+        assert_eq!(price("Cache read"), 6e-7);
+        // No listed long-context write price: scaled like the input price.
+        assert!((price("Cache write") - 7.5e-6).abs() < 1e-15);
+        assert_eq!(price("Output"), 2.25e-5);
+    }
+
+    #[test]
+    fn reads_a_pasted_call() {
+        let request =
+            r#"{"model": "gpt-4", "messages": [{"role": "user", "content": "hello world"}]}"#;
+        let response = r#"{"usage": {"prompt_tokens": 10, "completion_tokens": 2}}"#;
+        let call = parse_api_call(request, response).unwrap();
+        assert_eq!(call.provider, ApiProvider::OpenAiChat);
+        assert_eq!(call.model, "gpt-4");
+        assert_eq!(call.estimated_prompt_tokens, Some(9));
+
+        assert!(parse_api_call("", "{}").is_err());
+        assert!(parse_api_call("", r#"{"usage": {"prompt_tokens": 1}}"#).is_err());
+    }
+
+    #[test]
+    fn responses_payloads_without_details_stay_openai() {
+        let response = r#"{"object": "response", "model": "gpt-5", "usage": {"input_tokens": 10, "output_tokens": 4}}"#;
+        let call = parse_api_call("", response).unwrap();
+        assert_eq!(call.provider, ApiProvider::OpenAiResponses);
+        assert_eq!((call.usage.input_tokens, call.usage.output_tokens), (10, 4));
+
+        let response = r#"{"type": "message", "model": "claude-sonnet-4-5", "usage": {"input_tokens": 10, "output_tokens": 4}}"#;
+        assert_eq!(
+            parse_api_call("", response).unwrap().provider,
+            ApiProvider::Anthropic
+        );
+    }
+}
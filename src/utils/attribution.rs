@@ -0,0 +1,232 @@
+use std::collections::BTreeMap;
+
+use crate::utils::usage::{UsageRecord, UNKNOWN_LABEL};
+
+/// Cost center for requests that no rule matches.
+pub const UNATTRIBUTED: &str = "Unattributed";
+
+/// Maps requests whose `field` matches `pattern` to a cost center. Rules are
+/// tried in order and the first match wins.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttributionRule {
+    /// `model`, `api_key`, `user`, `team`, `tag`, or any metadata key.
+    pub field: String,
+    /// Case-insensitive value to match, where `*` matches any run of
+    /// characters. Empty matches any value.
+    pub pattern: String,
+    /// Cost center to charge. Empty charges the matched value itself, so one
+    /// rule can split spend by e.g. every customer ID.
+    pub cost_center: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChargebackRow {
+    pub cost_center: String,
+    /// YYYY-MM, or "(unknown)" for undated records.
+    pub month: String,
+    pub requests: usize,
+    pub input_tokens: usize,
+    pub output_tokens: usize,
+    pub cost: f64,
+}
+
+/// Values of a record field a rule can match on. Tags can yield several.
+pub fn field_values(record: &UsageRecord, field: &str) -> Vec<String> {
+    match field {
+        "model" => vec![record.model.clone()],
+        "api_key" | "key" => record.api_key.iter().cloned().collect(),
+        "user" => record.user.iter().cloned().collect(),
+        "team" => record.team.iter().cloned().collect(),
+        "tag" | "tags" => record.tags.clone(),
+        _ => record.metadata.get(field).into_iter().cloned().collect(),
+    }
+}
+
+/// Fields offered for rules: the built-in ones followed by every metadata
+/// key seen in the records.
+pub fn available_fields(records: &[UsageRecord]) -> Vec<String> {
+    let mut keys: Vec<String> = records
+        .iter()
+        .flat_map(|record| record.metadata.keys().cloned())
+        .collect();
+    keys.sort();
+    keys.dedup();
+    ["model", "api_key", "user", "team", "tag"]
+        .into_iter()
+        .map(str::to_string)
+        .chain(keys)
+        .collect()
+}
+
+/// Case-insensitive glob match supporting `*` only.
+fn matches_pattern(pattern: &str, value: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    let value = value.to_lowercase();
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = value.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+/// Cost center of the first rule matching the record.
+pub fn attribute(record: &UsageRecord, rules: &[AttributionRule]) -> String {
+    rules
+        .iter()
+        .find_map(|rule| {
+            let value = field_values(record, rule.field.trim())
+                .into_iter()
+                .find(|value| rule.pattern.is_empty() || matches_pattern(&rule.pattern, value))?;
+            Some(if rule.cost_center.trim().is_empty() {
+                value
+            } else {
+                rule.cost_center.trim().to_string()
+            })
+        })
+        .unwrap_or_else(|| UNATTRIBUTED.to_string())
+}
+
+/// Spend per cost center and month, ordered by cost center then month.
+/// `costs` are the records' prices, in the same order.
+pub fn build_chargeback(
+    records: &[UsageRecord],
+    costs: &[f64],
+    rules: &[AttributionRule],
+) -> Vec<ChargebackRow> {
+    let mut rows: BTreeMap<(String, String), ChargebackRow> = BTreeMap::new();
+    for (record, cost) in records.iter().zip(costs) {
+        let cost_center = attribute(record, rules);
+        let month = record
+            .date
+            .as_deref()
+            .and_then(|date| date.get(..7))
+            .unwrap_or(UNKNOWN_LABEL)
+            .to_string();
+        let row = rows
+            .entry((cost_center.clone(), month.clone()))
+            .or_insert_with(|| ChargebackRow {
+                cost_center,
+                month,
+                ..ChargebackRow::default()
+            });
+        row.requests += record.requests;
+        row.input_tokens += record.total_input_tokens();
+        row.output_tokens += record.output_tokens;
+        row.cost += cost;
+    }
+    rows.into_values().collect()
+}
+
+/// The chargeback report as CSV, one row per cost center and month.
+pub fn chargeback_csv(rows: &[ChargebackRow]) -> Result<String, String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer
+        .write_record([
+            "cost_center",
+            "month",
+            "requests",
+            "input_tokens",
+            "output_tokens",
+            "cost_usd",
+        ])
+        .map_err(|e| e.to_string())?;
+    for row in rows {
+        writer
+            .write_record([
+                row.cost_center.clone(),
+                row.month.clone(),
+                row.requests.to_string(),
+                row.input_tokens.to_string(),
+                row.output_tokens.to_string(),
+                format!("{:.6}", row.cost),
+            ])
+            .map_err(|e| e.to_string())?;
+    }
+    let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::usage::parse_usage_csv;
+
+    fn rule(field: &str, pattern: &str, cost_center: &str) -> AttributionRule {
+        AttributionRule {
+            field: field.to_string(),
+            pattern: pattern.to_string(),
+            cost_center: cost_center.to_string(),
+        }
+    }
+
+    fn records() -> Vec<UsageRecord> {
+        let csv = "date,model,api_key,tags,customer,input_tokens,output_tokens\n\
+                   2025-01-15,gpt-4o,prod-search,\"web,beta\",acme,100,10\n\
+                   2025-02-01,gpt-4o-mini,prod-chat,,globex,200,20\n\
+                   ,claude-sonnet-4-5,dev,,,300,30\n";
+        parse_usage_csv(csv).unwrap().records
+    }
+
+    #[test]
+    fn globs_match_case_insensitively() {
+        assert!(matches_pattern("prod-*", "PROD-search"));
+        assert!(matches_pattern("*-4o*", "gpt-4o-mini"));
+        assert!(matches_pattern("a*b*c", "abc"));
+        assert!(!matches_pattern("a*b*c", "acb"));
+        assert!(!matches_pattern("gpt-4o", "gpt-4o-mini"));
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let records = records();
+        let rules = [
+            rule("tag", "beta", "Experiments"),
+            rule("api_key", "prod-*", "Production"),
+            rule("customer", "", ""),
+        ];
+        assert_eq!(attribute(&records[0], &rules), "Experiments");
+        assert_eq!(attribute(&records[1], &rules), "Production");
+        assert_eq!(attribute(&records[2], &rules), UNATTRIBUTED);
+        assert_eq!(attribute(&records[1], &rules[2..]), "globex");
+        assert_eq!(field_values(&records[0], "tag"), ["web", "beta"]);
+        assert!(available_fields(&records).contains(&"customer".to_string()));
+    }
+
+    #[test]
+    fn totals_per_cost_center_and_month() {
+        let records = records();
+        let rows = build_chargeback(
+            &records,
+            &[1.0, 2.0, 4.0],
+            &[rule("model", "gpt-*", "OpenAI")],
+        );
+        let keys: Vec<(&str, &str)> = rows
+            .iter()
+            .map(|row| (row.cost_center.as_str(), row.month.as_str()))
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                ("OpenAI", "2025-01"),
+                ("OpenAI", "2025-02"),
+                (UNATTRIBUTED, UNKNOWN_LABEL)
+            ]
+        );
+        assert_eq!(rows[2].input_tokens, 300);
+
+        let csv = chargeback_csv(&rows).unwrap();
+        assert!(csv.starts_with("cost_center,month,requests,input_tokens,output_tokens,cost_usd\n"));
+        assert!(csv.contains("OpenAI,2025-01,1,100,10,1.000000\n"));
+    }
+}
@@ -0,0 +1,274 @@
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::models::ModelPricing;
+use crate::utils::chat::estimate_body_tokens;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModeCost {
+    pub label: &'static str,
+    /// `None` when the catalogue lacks the prices this mode needs.
+    pub cost_per_request: Option<f64>,
+    pub monthly_cost: Option<f64>,
+    /// Fraction saved compared to real-time pricing.
+    pub savings: Option<f64>,
+}
+
+/// Prices one request shape as real-time, batch and batch with a warm prompt
+/// cache covering `cached_share` of the input.
+///
+/// The catalogue has no batch cache price, so batch cache reads assume the
+/// batch discount stacks on the cache read price, as Anthropic and OpenAI
+/// bill it. Cache writes are ignored since a warm cache is reused across the
+/// whole batch.
+pub fn compare_batch_modes(
+    pricing: &ModelPricing,
+    input_tokens: usize,
+    output_tokens: usize,
+    cached_share: f64,
+    requests_per_month: f64,
+) -> Vec<ModeCost> {
+    let (input, output) = pricing.request_cost(input_tokens, output_tokens);
+    let realtime = input + output;
+    let batch = pricing
+        .batch_request_cost(input_tokens, output_tokens)
+        .map(|(input, output)| (input, input + output));
+
+    let batch_cache = batch.and_then(|(batch_input, batch_total)| {
+        let cache_read_price = pricing.cache_read_input_token_cost?;
+        let input_price = pricing.input_cost_per_token.filter(|p| *p > 0.0)?;
+        let batch_input_price = pricing.input_cost_per_token_batches.unwrap_or(input_price);
+        let cached = input_tokens as f64 * cached_share.clamp(0.0, 1.0);
+        let uncached = input_tokens as f64 - cached;
+        let discount = batch_input_price / input_price;
+        let cached_input = cached * cache_read_price * discount + uncached * batch_input_price;
+        Some(batch_total - batch_input + cached_input)
+    });
+
+    [
+        ("Real-time", Some(realtime)),
+        ("Batch", batch.map(|(_, total)| total)),
+        ("Batch + cache", batch_cache),
+    ]
+    .into_iter()
+    .map(|(label, cost)| ModeCost {
+        label,
+        cost_per_request: cost,
+        monthly_cost: cost.map(|c| c * requests_per_month),
+        savings: cost.filter(|_| realtime > 0.0).map(|c| 1.0 - c / realtime),
+    })
+    .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchLine {
+    pub line: usize,
+    pub custom_id: String,
+    pub model: String,
+    pub input_tokens: usize,
+    /// Output tokens if every completion runs to its limit.
+    pub max_output_tokens: usize,
+    /// Whether the output limit came from the model because the body set none.
+    pub limit_from_model: bool,
+    pub input_cost: f64,
+    pub worst_output_cost: f64,
+    /// Worst-case cost through the batch API, if the model has batch prices.
+    pub batch_cost: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchLineError {
+    pub line: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchFileEstimate {
+    pub lines: Vec<BatchLine>,
+    pub errors: Vec<BatchLineError>,
+    pub total_input_tokens: usize,
+    pub total_max_output_tokens: usize,
+    pub input_cost: f64,
+    pub worst_output_cost: f64,
+    /// Worst-case batch total; lines without batch prices count at real-time.
+    pub batch_cost: f64,
+    pub lines_without_batch_price: usize,
+}
+
+fn parse_batch_line(
+    line: usize,
+    text: &str,
+    prices: &HashMap<String, ModelPricing>,
+) -> Result<BatchLine, String> {
+    let request: Value = serde_json::from_str(text).map_err(|e| format!("invalid JSON: {}", e))?;
+    let body = request.get("body").ok_or("missing \"body\"")?;
+    let model = body
+        .get("model")
+        .and_then(Value::as_str)
+        .ok_or("body has no \"model\"")?;
+    let pricing = prices
+        .get(model)
+        .ok_or_else(|| format!("unknown model \"{}\"", model))?;
+    let input_tokens = estimate_body_tokens(body, model)?;
+
+    let requested_limit = body
+        .get("max_completion_tokens")
+        .or_else(|| body.get("max_tokens"))
+        .and_then(Value::as_u64);
+    let (limit, limit_from_model) = match requested_limit {
+        Some(limit) => (limit as usize, false),
+        None if pricing.is_embedding() => (0, false),
+        None => (
+            pricing
+                .max_output_tokens
+                .filter(|max| *max > 0)
+                .ok_or("no max_tokens and the model lists no output limit")? as usize,
+            true,
+        ),
+    };
+    let completions = body.get("n").and_then(Value::as_u64).unwrap_or(1).max(1) as usize;
+    let max_output_tokens = limit * completions;
+
+    let (input_cost, worst_output_cost) = pricing.request_cost(input_tokens, max_output_tokens);
+    let batch_cost = pricing
+        .batch_request_cost(input_tokens, max_output_tokens)
+        .map(|(input, output)| input + output);
+
+    Ok(BatchLine {
+        line,
+        custom_id: request
+            .get("custom_id")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        model: model.to_string(),
+        input_tokens,
+        max_output_tokens,
+        limit_from_model,
+        input_cost,
+        worst_output_cost,
+        batch_cost,
+    })
+}
+
+/// Prices every line of an OpenAI Batch input file. Output is costed at each
+/// request's `max_tokens` (or the model's limit), so totals are an upper bound.
+pub fn estimate_batch_file(
+    text: &str,
+    prices: &HashMap<String, ModelPricing>,
+) -> BatchFileEstimate {
+    let mut estimate = BatchFileEstimate::default();
+
+    for (index, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match parse_batch_line(index + 1, line, prices) {
+            Ok(parsed) => {
+                estimate.total_input_tokens += parsed.input_tokens;
+                estimate.total_max_output_tokens += parsed.max_output_tokens;
+                estimate.input_cost += parsed.input_cost;
+                estimate.worst_output_cost += parsed.worst_output_cost;
+                match parsed.batch_cost {
+                    Some(cost) => estimate.batch_cost += cost,
+                    None => {
+                        estimate.batch_cost += parsed.input_cost + parsed.worst_output_cost;
+                        estimate.lines_without_batch_price += 1;
+                    }
+                }
+                estimate.lines.push(parsed);
+            }
+            Err(message) => estimate.errors.push(BatchLineError {
+                line: index + 1,
+                message,
+            }),
+        }
+    }
+
+    estimate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn batch_pricing() -> ModelPricing {
+        serde_json::from_value(json!({
+            "input_cost_per_token": 2e-6,
+            "output_cost_per_token": 8e-6,
+            "input_cost_per_token_batches": 1e-6,
+            "output_cost_per_token_batches": 4e-6,
+            "cache_read_input_token_cost": 5e-7,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn batch_halves_the_realtime_cost() {
+        let modes = compare_batch_modes(&batch_pricing(), 1_000, 100, 0.5, 1_000.0);
+        let labels: Vec<&str> = modes.iter().map(|m| m.label).collect();
+        assert_eq!(labels, ["Real-time", "Batch", "Batch + cache"]);
+        assert!((modes[0].cost_per_request.unwrap() - 2.8e-3).abs() < 1e-12);
+        assert!((modes[1].cost_per_request.unwrap() - 1.4e-3).abs() < 1e-12);
+        assert!((modes[1].savings.unwrap() - 0.5).abs() < 1e-9);
+        // 500 cached tokens at half the cache read price, 500 at the batch price.
+        let cached = 500.0 * 2.5e-7 + 500.0 * 1e-6 + 100.0 * 4e-6;
+        assert!((modes[2].cost_per_request.unwrap() - cached).abs() < 1e-12);
+        assert!((modes[2].monthly_cost.unwrap() - cached * 1_000.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn modes_without_prices_are_none() {
+        let pricing: ModelPricing = serde_json::from_value(json!({
+            "input_cost_per_token": 2e-6,
+            "output_cost_per_token": 8e-6,
+        }))
+        .unwrap();
+        let modes = compare_batch_modes(&pricing, 1_000, 100, 0.5, 1_000.0);
+        assert!(modes[0].cost_per_request.is_some());
+        assert_eq!(modes[1].cost_per_request, None);
+        assert_eq!(modes[2].savings, None);
+    }
+
+    #[test]
+    fn prices_every_line_of_a_batch_file() {
+        let prices: HashMap<String, ModelPricing> = serde_json::from_value(json!({
+            "gpt-4o-mini": {
+                "input_cost_per_token": 1.5e-7,
+                "output_cost_per_token": 6e-7,
+                "input_cost_per_token_batches": 7.5e-8,
+                "output_cost_per_token_batches": 3e-7,
+                "max_output_tokens": 16384,
+            },
+            "gpt-4": {"input_cost_per_token": 3e-5, "output_cost_per_token": 6e-5},
+        }))
+        .unwrap();
+        let file = [
+            r#"{"custom_id": "a", "body": {"model": "gpt-4o-mini", "messages": [{"role": "user", "content": "hi"}], "max_tokens": 100, "n": 2}}"#,
+            "",
+            r#"{"custom_id": "b", "body": {"model": "gpt-4o-mini", "messages": [{"role": "user", "content": "hi"}]}}"#,
+            r#"{"custom_id": "c", "body": {"model": "gpt-4", "messages": [{"role": "user", "content": "hi"}]}}"#,
+            r#"{"custom_id": "d", "body": {"model": "unknown", "messages": []}}"#,
+            "not json",
+        ]
+        .join("\n");
+        let estimate = estimate_batch_file(&file, &prices);
+
+        assert_eq!(estimate.lines.len(), 2);
+        assert_eq!(estimate.lines[0].custom_id, "a");
+        assert_eq!(estimate.lines[0].max_output_tokens, 200);
+        assert!(estimate.lines[1].limit_from_model);
+        assert_eq!(estimate.lines[1].max_output_tokens, 16384);
+        assert_eq!(estimate.total_max_output_tokens, 16584);
+        let lines: Vec<usize> = estimate.errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, [4, 5, 6]);
+        assert!(estimate.errors[0].message.contains("output limit"));
+        assert!(estimate.errors[1].message.contains("unknown model"));
+        assert_eq!(estimate.lines_without_batch_price, 0);
+        assert!(
+            (estimate.batch_cost * 2.0 - (estimate.input_cost + estimate.worst_output_cost)).abs()
+                < 1e-12
+        );
+    }
+}
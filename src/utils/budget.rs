@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+
+use crate::models::ModelPricing;
+use crate::utils::TokenCounter;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetRow {
+    pub model: String,
+    pub cost_per_request: f64,
+    pub monthly_cost: f64,
+    /// Requests per month the budget pays for, or `None` for free models.
+    pub affordable_requests: Option<f64>,
+}
+
+impl BudgetRow {
+    pub fn within_budget(&self, monthly_budget: f64) -> bool {
+        self.monthly_cost <= monthly_budget
+    }
+}
+
+/// Monthly cost of serving `requests_per_month` requests shaped like the
+/// given prompt on every non-deprecated chat model, cheapest first. Models
+/// without both an input and an output price, or listed as entirely free,
+/// are left out rather than topping the list as unlimited.
+pub fn solve_budget(
+    prices: &HashMap<String, ModelPricing>,
+    input: &str,
+    output: &str,
+    monthly_budget: f64,
+    requests_per_month: f64,
+    today: &str,
+) -> Vec<BudgetRow> {
+    let mut counter = TokenCounter::new(input, output);
+
+    let mut rows: Vec<BudgetRow> = prices
+        .iter()
+        .filter(|(_, pricing)| !pricing.is_embedding() && !pricing.is_deprecated(today))
+        .filter(|(_, pricing)| {
+            match (pricing.input_cost_per_token, pricing.output_cost_per_token) {
+                (Some(input), Some(output)) => input + output > 0.0,
+                _ => false,
+            }
+        })
+        .map(|(model, pricing)| {
+            let (input_tokens, output_tokens) = counter.counts(model);
+            let (input_cost, output_cost) = pricing.request_cost(input_tokens, output_tokens);
+            let cost_per_request = input_cost + output_cost;
+            BudgetRow {
+                model: model.clone(),
+                cost_per_request,
+                monthly_cost: cost_per_request * requests_per_month,
+                affordable_requests: (cost_per_request > 0.0)
+                    .then(|| (monthly_budget / cost_per_request).floor()),
+            }
+        })
+        .collect();
+
+    rows.sort_by(|a, b| {
+        a.monthly_cost
+            .total_cmp(&b.monthly_cost)
+            .then_with(|| a.model.cmp(&b.model))
+    });
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn solves_affordable_requests_cheapest_first() {
+        let prices: HashMap<String, ModelPricing> = serde_json::from_value(json!({
+            "gpt-4": {"input_cost_per_token": 1e-5, "output_cost_per_token": 3e-5},
+            "gpt-3.5-turbo": {"input_cost_per_token": 1e-6, "output_cost_per_token": 2e-6},
+            "text-embedding-3-small": {"input_cost_per_token": 2e-8, "mode": "embedding"},
+        }))
+        .unwrap();
+        // "hello world" is two tokens and "ok" one in cl100k_base.
+        let rows = solve_budget(&prices, "hello world", "ok", 1.0, 10_000.0, "2025-01-01");
+        let models: Vec<&str> = rows.iter().map(|r| r.model.as_str()).collect();
+        assert_eq!(models, ["gpt-3.5-turbo", "gpt-4"]);
+
+        assert!((rows[0].cost_per_request - 4e-6).abs() < 1e-15);
+        assert_eq!(rows[0].affordable_requests, Some(250_000.0));
+        assert!(rows[0].within_budget(1.0));
+        assert!((rows[1].monthly_cost - 0.5).abs() < 1e-9);
+        assert!(!rows[1].within_budget(0.4));
+    }
+
+    #[test]
+    fn leaves_out_free_and_unpriced_models() {
+        let prices: HashMap<String, ModelPricing> = serde_json::from_value(json!({
+            "gpt-3.5-turbo": {"input_cost_per_token": 1e-6, "output_cost_per_token": 2e-6},
+            "ollama/llama3": {"input_cost_per_token": 0.0, "output_cost_per_token": 0.0},
+            "dall-e-3": {"mode": "image_generation", "output_cost_per_image": 0.04},
+            "rerank-english-v3.0": {"mode": "rerank", "input_cost_per_token": 0.0},
+        }))
+        .unwrap();
+        let rows = solve_budget(&prices, "hello world", "ok", 1.0, 10_000.0, "2025-01-01");
+        let models: Vec<&str> = rows.iter().map(|r| r.model.as_str()).collect();
+        assert_eq!(models, ["gpt-3.5-turbo"]);
+    }
+}
@@ -0,0 +1,357 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use wasm_bindgen::JsValue;
+
+use crate::utils::chat::estimate_body_tokens;
+use crate::utils::storage::{load_json, save_json};
+use crate::utils::usage::model_family;
+
+/// Local storage key of the saved correction factors.
+const CALIBRATION_KEY: &str = "token-calibration";
+/// Families calibrated from fewer requests are flagged as unreliable.
+pub const MIN_SAMPLES: usize = 10;
+
+/// One logged request: our estimate of its prompt against the provider's count.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalibrationSample {
+    pub model: String,
+    pub estimated_tokens: usize,
+    pub reported_tokens: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CalibrationImport {
+    pub samples: Vec<CalibrationSample>,
+    /// Entries that could not be read, as "Entry N: reason".
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalibrationRow {
+    pub family: String,
+    pub samples: usize,
+    pub estimated_tokens: usize,
+    pub reported_tokens: usize,
+    /// Reported over estimated tokens across all samples.
+    pub factor: f64,
+    /// Mean absolute error of the raw estimates, in percent.
+    pub error_percent: f64,
+    /// Mean absolute error once the factor is applied, in percent.
+    pub corrected_error_percent: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CorrectionFactor {
+    pub factor: f64,
+    pub samples: usize,
+}
+
+/// Saved correction factors by model family.
+pub type Calibration = BTreeMap<String, CorrectionFactor>;
+
+/// A JSON value that may arrive as a JSON-encoded string, as request and
+/// message columns do in spend log exports.
+fn embedded(value: &Value) -> Value {
+    match value {
+        Value::String(text) => match serde_json::from_str::<Value>(text) {
+            Ok(parsed @ (Value::Array(_) | Value::Object(_))) => parsed,
+            _ => value.clone(),
+        },
+        _ => value.clone(),
+    }
+}
+
+fn count(value: &Value) -> Option<usize> {
+    value
+        .as_u64()
+        .map(|n| n as usize)
+        .or_else(|| value.as_str()?.trim().parse().ok())
+}
+
+/// Prompt tokens the provider billed: OpenAI `prompt_tokens`, Anthropic
+/// `input_tokens` plus cache reads and writes, or Gemini `promptTokenCount`.
+fn reported_input_tokens(usage: &Value) -> Option<usize> {
+    if let Some(tokens) = usage.get("prompt_tokens").and_then(count) {
+        return Some(tokens);
+    }
+    if let Some(tokens) = usage.get("promptTokenCount").and_then(count) {
+        return Some(tokens);
+    }
+    let input = usage.get("input_tokens").and_then(count)?;
+    let cache = ["cache_read_input_tokens", "cache_creation_input_tokens"]
+        .iter()
+        .filter_map(|key| usage.get(*key).and_then(count))
+        .sum::<usize>();
+    Some(input + cache)
+}
+
+/// Reads one log entry: a `{request, response}` pair, a bare request body
+/// with a `usage` block, or a spend log row with `messages` and
+/// `prompt_tokens`.
+fn sample_from_entry(entry: &Value) -> Result<CalibrationSample, String> {
+    let request = ["request", "request_body", "body"]
+        .iter()
+        .find_map(|key| entry.get(*key))
+        .map(embedded)
+        .unwrap_or_else(|| entry.clone());
+    let mut request = match request {
+        Value::Object(object) => object,
+        _ => return Err("request is not an object".to_string()),
+    };
+    for key in ["messages", "system", "contents"] {
+        if let Some(value) = request.get(key).map(embedded) {
+            request.insert(key.to_string(), value);
+        }
+    }
+    let response = ["response", "response_body"]
+        .iter()
+        .find_map(|key| entry.get(*key))
+        .map(embedded)
+        .map(|response| response.get("body").map(embedded).unwrap_or(response));
+
+    let model = request
+        .get("model")
+        .or_else(|| entry.get("model"))
+        .or_else(|| response.as_ref()?.get("model"))
+        .and_then(Value::as_str)
+        .ok_or("missing model")?
+        .to_string();
+    let reported_tokens = [Some(entry), response.as_ref()]
+        .into_iter()
+        .flatten()
+        .flat_map(|value| {
+            ["usage", "usageMetadata"]
+                .iter()
+                .filter_map(|key| value.get(*key).map(embedded))
+                .chain(std::iter::once(value.clone()))
+                .collect::<Vec<_>>()
+        })
+        .find_map(|usage| reported_input_tokens(&usage))
+        .ok_or("missing usage")?;
+    let estimated_tokens = estimate_body_tokens(&Value::Object(request), &model)?;
+
+    Ok(CalibrationSample {
+        model,
+        estimated_tokens,
+        reported_tokens,
+    })
+}
+
+fn csv_entries(text: &str) -> Result<Vec<Value>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(text.as_bytes());
+    let headers = reader
+        .headers()
+        .map_err(|e| format!("Invalid CSV: {}", e))?
+        .clone();
+    reader
+        .records()
+        .map(|row| {
+            let row = row.map_err(|e| format!("Invalid CSV: {}", e))?;
+            Ok(Value::Object(
+                headers
+                    .iter()
+                    .zip(row.iter())
+                    .map(|(header, value)| {
+                        (
+                            header.trim().to_lowercase(),
+                            Value::String(value.to_string()),
+                        )
+                    })
+                    .collect::<Map<_, _>>(),
+            ))
+        })
+        .collect()
+}
+
+/// Parses a request log as a JSON array (optionally under `data`), JSONL, or
+/// CSV, keeping the entries that carry both the prompt and the billed usage.
+pub fn parse_calibration_log(name: &str, text: &str) -> Result<CalibrationImport, String> {
+    let trimmed = text.trim_start();
+    let entries = if name.to_lowercase().ends_with(".csv") || !trimmed.starts_with(['[', '{']) {
+        csv_entries(text)?
+    } else {
+        match serde_json::from_str::<Value>(text) {
+            Ok(Value::Array(items)) => items,
+            Ok(Value::Object(mut object)) => match object.remove("data") {
+                Some(Value::Array(items)) => items,
+                _ => vec![Value::Object(object)],
+            },
+            Ok(_) => return Err("Expected a JSON array of log entries".to_string()),
+            // One JSON object per line.
+            Err(_) if trimmed.starts_with('{') => text
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| serde_json::from_str(line).unwrap_or(Value::Null))
+                .collect(),
+            Err(e) => return Err(format!("Invalid JSON: {}", e)),
+        }
+    };
+
+    let mut import = CalibrationImport::default();
+    for (index, entry) in entries.iter().enumerate() {
+        if entry.is_null() {
+            import
+                .errors
+                .push(format!("Entry {}: invalid JSON", index + 1));
+            continue;
+        }
+        match sample_from_entry(entry) {
+            Ok(sample) => import.samples.push(sample),
+            Err(e) => import.errors.push(format!("Entry {}: {}", index + 1, e)),
+        }
+    }
+    Ok(import)
+}
+
+fn mean_error_percent(samples: &[&CalibrationSample], factor: f64) -> f64 {
+    let errors: Vec<f64> = samples
+        .iter()
+        .filter(|sample| sample.reported_tokens > 0)
+        .map(|sample| {
+            let reported = sample.reported_tokens as f64;
+            (sample.estimated_tokens as f64 * factor - reported).abs() / reported * 100.0
+        })
+        .collect();
+    if errors.is_empty() {
+        0.0
+    } else {
+        errors.iter().sum::<f64>() / errors.len() as f64
+    }
+}
+
+/// Estimation error and correction factor per model family, by family name.
+pub fn calibrate(samples: &[CalibrationSample]) -> Vec<CalibrationRow> {
+    let mut families: BTreeMap<String, Vec<&CalibrationSample>> = BTreeMap::new();
+    for sample in samples {
+        families
+            .entry(model_family(&sample.model))
+            .or_default()
+            .push(sample);
+    }
+    families
+        .into_iter()
+        .map(|(family, samples)| {
+            let estimated_tokens: usize = samples.iter().map(|s| s.estimated_tokens).sum();
+            let reported_tokens: usize = samples.iter().map(|s| s.reported_tokens).sum();
+            let factor = if estimated_tokens > 0 {
+                reported_tokens as f64 / estimated_tokens as f64
+            } else {
+                1.0
+            };
+            CalibrationRow {
+                family,
+                samples: samples.len(),
+                estimated_tokens,
+                reported_tokens,
+                factor,
+                error_percent: mean_error_percent(&samples, 1.0),
+                corrected_error_percent: mean_error_percent(&samples, factor),
+            }
+        })
+        .collect()
+}
+
+pub fn load_calibration() -> Calibration {
+    load_json(CALIBRATION_KEY).unwrap_or_default()
+}
+
+pub fn save_calibration(calibration: &Calibration) -> Result<(), JsValue> {
+    save_json(CALIBRATION_KEY, calibration)
+}
+
+/// The saved correction for a model's family, if any.
+pub fn correction_for(
+    calibration: &Calibration,
+    model: &str,
+) -> Option<(String, CorrectionFactor)> {
+    let family = model_family(model);
+    calibration
+        .get(&family)
+        .map(|correction| (family, *correction))
+}
+
+/// Scales an estimated token count by a correction factor.
+pub fn apply_correction(tokens: usize, factor: f64) -> usize {
+    (tokens as f64 * factor).round() as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(model: &str, estimated_tokens: usize, reported_tokens: usize) -> CalibrationSample {
+        CalibrationSample {
+            model: model.to_string(),
+            estimated_tokens,
+            reported_tokens,
+        }
+    }
+
+    #[test]
+    fn reads_request_response_pairs() {
+        let log = concat!(
+            r#"{"request": {"model": "gpt-4", "messages": [{"role": "user", "content": "hello world"}]}, "response": {"usage": {"prompt_tokens": 10}}}"#,
+            "\n",
+            r#"{"request": {"model": "claude-sonnet-4-5-20250929", "system": "hello world", "messages": []}, "response": {"usage": {"input_tokens": 5, "cache_read_input_tokens": 7}}}"#,
+            "\n",
+            r#"{"custom_id": "a", "response": {"body": {"model": "gpt-4", "usage": {"prompt_tokens": 10}}}}"#,
+            "\nnot json\n",
+        );
+        let import = parse_calibration_log("log.jsonl", log).unwrap();
+        assert_eq!(
+            import.samples,
+            [
+                sample("gpt-4", 9, 10),
+                sample("claude-sonnet-4-5-20250929", 2, 12)
+            ]
+        );
+        // Batch output lines carry no request, so there is nothing to estimate.
+        assert_eq!(import.errors.len(), 2);
+        assert!(import.errors[0].starts_with("Entry 3:"));
+        assert_eq!(import.errors[1], "Entry 4: invalid JSON");
+    }
+
+    #[test]
+    fn reads_spend_log_csv() {
+        let csv = "model,messages,prompt_tokens\n\
+                   gpt-4,\"[{\"\"role\"\": \"\"user\"\", \"\"content\"\": \"\"hello world\"\"}]\",11\n";
+        let import = parse_calibration_log("spend.csv", csv).unwrap();
+        assert_eq!(import.samples, [sample("gpt-4", 9, 11)]);
+    }
+
+    #[test]
+    fn calibrates_per_family() {
+        let samples = [
+            sample("claude-sonnet-4-5-20250929", 100, 110),
+            sample("anthropic/claude-sonnet-4-5", 300, 330),
+            sample("gpt-4o", 50, 50),
+        ];
+        let rows = calibrate(&samples);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].family, "claude-sonnet-4-5");
+        assert_eq!(rows[0].samples, 2);
+        assert!((rows[0].factor - 1.1).abs() < 1e-12);
+        assert!((rows[0].error_percent - 100.0 / 11.0).abs() < 1e-9);
+        assert!(rows[0].corrected_error_percent < 1e-9);
+        assert_eq!(rows[1].factor, 1.0);
+    }
+
+    #[test]
+    fn applies_saved_corrections() {
+        let mut calibration = Calibration::new();
+        let correction = CorrectionFactor {
+            factor: 1.1,
+            samples: 20,
+        };
+        calibration.insert("claude-sonnet-4-5".to_string(), correction);
+        assert_eq!(
+            correction_for(&calibration, "claude-sonnet-4-5-20250929"),
+            Some(("claude-sonnet-4-5".to_string(), correction))
+        );
+        assert_eq!(correction_for(&calibration, "gpt-4o"), None);
+        assert_eq!(apply_correction(1_000, 1.1), 1_100);
+    }
+}
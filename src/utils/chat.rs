@@ -0,0 +1,183 @@
+use serde_json::Value;
+
+use crate::utils::estimate_tokens_for_model;
+
+/// Tokens the OpenAI chat format adds around every message.
+const TOKENS_PER_MESSAGE: usize = 3;
+/// Tokens that prime the assistant reply after the last message.
+const TOKENS_PER_REPLY: usize = 3;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChatMessage {
+    pub role: String,
+    pub text: String,
+}
+
+/// Text of a message `content`, which is either a plain string or an array of
+/// parts. Only text parts are kept; images and audio are not tokenized.
+pub fn content_text(content: &Value) -> String {
+    match content {
+        Value::String(text) => text.clone(),
+        Value::Array(parts) => parts
+            .iter()
+            .filter_map(|part| match part {
+                Value::String(text) => Some(text.as_str()),
+                _ => part.get("text").and_then(Value::as_str),
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+/// Reads one chat message, folding tool call names and arguments into its text.
+pub fn parse_message(message: &Value) -> Option<ChatMessage> {
+    let role = message.get("role")?.as_str()?.to_string();
+    let mut text = message.get("content").map(content_text).unwrap_or_default();
+    if let Some(calls) = message.get("tool_calls").and_then(Value::as_array) {
+        for call in calls {
+            if let Some(function) = call.get("function") {
+                for key in ["name", "arguments"] {
+                    if let Some(value) = function.get(key).and_then(Value::as_str) {
+                        text.push('\n');
+                        text.push_str(value);
+                    }
+                }
+            }
+        }
+    }
+    Some(ChatMessage { role, text })
+}
+
+/// Parses the `messages` array of a chat completion request body.
+pub fn parse_messages(body: &Value) -> Result<Vec<ChatMessage>, String> {
+    let messages = body
+        .get("messages")
+        .and_then(Value::as_array)
+        .ok_or("missing \"messages\" array")?;
+    messages
+        .iter()
+        .enumerate()
+        .map(|(index, message)| {
+            parse_message(message).ok_or_else(|| format!("message {} has no role", index + 1))
+        })
+        .collect()
+}
+
+/// Prompt tokens of a chat request, including the per-message framing the
+/// chat format adds.
+pub fn estimate_chat_tokens(messages: &[ChatMessage], model: &str) -> usize {
+    if messages.is_empty() {
+        return 0;
+    }
+    messages
+        .iter()
+        .map(|message| {
+            TOKENS_PER_MESSAGE
+                + estimate_tokens_for_model(&message.role, model)
+                + estimate_tokens_for_model(&message.text, model)
+        })
+        .sum::<usize>()
+        + TOKENS_PER_REPLY
+}
+
+/// Parses the `contents` array of a Gemini request into chat messages.
+fn parse_gemini_contents(contents: &[Value]) -> Vec<ChatMessage> {
+    contents
+        .iter()
+        .map(|content| ChatMessage {
+            role: content
+                .get("role")
+                .and_then(Value::as_str)
+                .unwrap_or("user")
+                .to_string(),
+            text: content.get("parts").map(content_text).unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Prompt tokens of a request body: chat `messages` (plus an Anthropic-style
+/// top-level `system`), Gemini `contents`, or the `prompt` or `input` of
+/// completion and embedding requests.
+pub fn estimate_body_tokens(body: &Value, model: &str) -> Result<usize, String> {
+    let system = ["system", "systemInstruction", "system_instruction"]
+        .iter()
+        .find_map(|key| body.get(*key))
+        .map(|system| {
+            let text = system
+                .get("parts")
+                .map(content_text)
+                .unwrap_or_else(|| content_text(system));
+            estimate_tokens_for_model(&text, model)
+        })
+        .unwrap_or(0);
+    if body.get("messages").is_some() {
+        return parse_messages(body)
+            .map(|messages| system + estimate_chat_tokens(&messages, model));
+    }
+    if let Some(contents) = body.get("contents").and_then(Value::as_array) {
+        return Ok(system + estimate_chat_tokens(&parse_gemini_contents(contents), model));
+    }
+    body.get("prompt")
+        .or_else(|| body.get("input"))
+        .map(|value| estimate_tokens_for_model(&content_text(value), model))
+        .ok_or_else(|| {
+            "body has no \"messages\", \"contents\", \"prompt\" or \"input\"".to_string()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn content_keeps_only_text_parts() {
+        let content = json!([
+            {"type": "text", "text": "first"},
+            {"type": "image_url", "image_url": {"url": "data:..."}},
+            "second",
+        ]);
+        assert_eq!(content_text(&content), "first\nsecond");
+        assert_eq!(content_text(&json!(null)), "");
+    }
+
+    #[test]
+    fn tool_calls_count_as_message_text() {
+        let message = json!({
+            "role": "assistant",
+            "content": null,
+            "tool_calls": [{"function": {"name": "search", "arguments": "{\"q\":1}"}}],
+        });
+        let parsed = parse_message(&message).unwrap();
+        assert_eq!(parsed.text, "\nsearch\n{\"q\":1}");
+        assert!(parse_messages(&json!({"messages": [{"content": "x"}]})).is_err());
+    }
+
+    #[test]
+    fn chat_tokens_add_message_framing() {
+        let messages = [ChatMessage {
+            role: "user".to_string(),
+            text: "hello world".to_string(),
+        }];
+        // 3 framing + 1 role + 2 text + 3 reply priming.
+        assert_eq!(estimate_chat_tokens(&messages, "gpt-4"), 9);
+        assert_eq!(estimate_chat_tokens(&[], "gpt-4"), 0);
+    }
+
+    #[test]
+    fn body_tokens_cover_every_request_shape() {
+        let chat = json!({"messages": [{"role": "user", "content": "hello world"}]});
+        assert_eq!(estimate_body_tokens(&chat, "gpt-4"), Ok(9));
+        let anthropic = json!({
+            "system": "hello world",
+            "messages": [{"role": "user", "content": "hello world"}],
+        });
+        assert_eq!(estimate_body_tokens(&anthropic, "gpt-4"), Ok(11));
+        let gemini = json!({"contents": [{"parts": [{"text": "hello world"}]}]});
+        assert_eq!(estimate_body_tokens(&gemini, "gpt-4"), Ok(9));
+        let embedding = json!({"input": "hello world"});
+        assert_eq!(estimate_body_tokens(&embedding, "gpt-4"), Ok(2));
+        assert!(estimate_body_tokens(&json!({}), "gpt-4").is_err());
+    }
+}
@@ -0,0 +1,129 @@
+use crate::models::ModelPricing;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConversationPlan {
+    pub turns: usize,
+    pub system_tokens: usize,
+    pub user_tokens: usize,
+    pub assistant_tokens: usize,
+    pub use_cache: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TurnCost {
+    pub turn: usize,
+    pub input_tokens: usize,
+    pub cached_tokens: usize,
+    pub output_tokens: usize,
+    pub cost: f64,
+    pub cumulative_tokens: usize,
+    pub cumulative_cost: f64,
+}
+
+/// Simulates a chat session where every turn re-sends the whole history.
+///
+/// With `use_cache`, the prompt sent on the previous turn is read back from
+/// the prompt cache and the new suffix is written to it, using the model's
+/// cache prices where the catalogue has them.
+pub fn simulate_conversation(plan: &ConversationPlan, pricing: &ModelPricing) -> Vec<TurnCost> {
+    let input_price = pricing.input_cost_per_token.unwrap_or(0.0);
+    let output_price = pricing.output_cost_per_token.unwrap_or(0.0);
+    let cache_read_price = pricing.cache_read_input_token_cost.unwrap_or(input_price);
+    let cache_write_price = pricing
+        .cache_creation_input_token_cost
+        .unwrap_or(input_price);
+
+    let mut turns = Vec::with_capacity(plan.turns);
+    let mut previous_input = 0;
+    let mut cumulative_tokens = 0;
+    let mut cumulative_cost = 0.0;
+
+    for turn in 1..=plan.turns {
+        let history = (turn - 1) * (plan.user_tokens + plan.assistant_tokens);
+        let input_tokens = plan.system_tokens + history + plan.user_tokens;
+        let output_tokens = plan.assistant_tokens;
+
+        let (cached_tokens, input_cost) = if plan.use_cache {
+            let cached = previous_input;
+            let written = input_tokens - cached;
+            (
+                cached,
+                cached as f64 * cache_read_price + written as f64 * cache_write_price,
+            )
+        } else {
+            (0, input_tokens as f64 * input_price)
+        };
+        let cost = input_cost + output_tokens as f64 * output_price;
+
+        cumulative_tokens += input_tokens + output_tokens;
+        cumulative_cost += cost;
+        previous_input = input_tokens;
+
+        turns.push(TurnCost {
+            turn,
+            input_tokens,
+            cached_tokens,
+            output_tokens,
+            cost,
+            cumulative_tokens,
+            cumulative_cost,
+        });
+    }
+
+    turns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn plan(use_cache: bool) -> ConversationPlan {
+        ConversationPlan {
+            turns: 3,
+            system_tokens: 100,
+            user_tokens: 10,
+            assistant_tokens: 20,
+            use_cache,
+        }
+    }
+
+    fn pricing() -> ModelPricing {
+        serde_json::from_value(json!({
+            "input_cost_per_token": 1e-6,
+            "output_cost_per_token": 2e-6,
+            "cache_read_input_token_cost": 1e-7,
+            "cache_creation_input_token_cost": 1.25e-6,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn resends_the_whole_history_each_turn() {
+        let turns = simulate_conversation(&plan(false), &pricing());
+        let inputs: Vec<usize> = turns.iter().map(|t| t.input_tokens).collect();
+        assert_eq!(inputs, [110, 140, 170]);
+        assert!(turns.iter().all(|t| t.cached_tokens == 0));
+        assert_eq!(turns[2].cumulative_tokens, 420 + 60);
+        let total = 420.0 * 1e-6 + 60.0 * 2e-6;
+        assert!((turns[2].cumulative_cost - total).abs() < 1e-12);
+    }
+
+    #[test]
+    fn caches_the_previous_prompt() {
+        let turns = simulate_conversation(&plan(true), &pricing());
+        let cached: Vec<usize> = turns.iter().map(|t| t.cached_tokens).collect();
+        assert_eq!(cached, [0, 110, 140]);
+        let second = 110.0 * 1e-7 + 30.0 * 1.25e-6 + 20.0 * 2e-6;
+        assert!((turns[1].cost - second).abs() < 1e-12);
+    }
+
+    #[test]
+    fn zero_turns_is_empty() {
+        let plan = ConversationPlan {
+            turns: 0,
+            ..plan(true)
+        };
+        assert!(simulate_conversation(&plan, &pricing()).is_empty());
+    }
+}
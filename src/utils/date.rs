@@ -0,0 +1,88 @@
+/// Today's date in the browser's UTC clock, formatted as YYYY-MM-DD.
+pub fn today() -> String {
+    let iso: String = js_sys::Date::new_0().to_iso_string().into();
+    iso[..10].to_string()
+}
+
+/// Civil date for a count of days since 1970-01-01 (Howard Hinnant's algorithm).
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Days since 1970-01-01 for a civil date, the inverse of `civil_from_days`.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let month = i64::from(month);
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Formats days since 1970-01-01 as YYYY-MM-DD.
+pub fn format_days(days: i64) -> String {
+    let (year, month, day) = civil_from_days(days);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Days since 1970-01-01 for a YYYY-MM-DD date.
+pub fn parse_days(date: &str) -> Option<i64> {
+    let mut parts = date.get(..10)?.splitn(3, '-');
+    let year = parts.next()?.parse().ok()?;
+    let month = parts
+        .next()?
+        .parse()
+        .ok()
+        .filter(|m| (1..=12).contains(m))?;
+    let day = parts
+        .next()?
+        .parse()
+        .ok()
+        .filter(|d| (1..=31).contains(d))?;
+    Some(days_from_civil(year, month, day))
+}
+
+/// Monday of the week containing a YYYY-MM-DD date.
+pub fn week_start(date: &str) -> Option<String> {
+    // 1970-01-01 was a Thursday, three days after a Monday.
+    parse_days(date).map(|days| format_days(days - (days + 3).rem_euclid(7)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_dates() {
+        assert_eq!(parse_days("1970-01-01"), Some(0));
+        assert_eq!(parse_days("2024-02-29"), Some(19_782));
+        assert_eq!(format_days(19_782), "2024-02-29");
+        assert_eq!(format_days(-1), "1969-12-31");
+        assert_eq!(parse_days("2025-01-15T08:30:00Z"), parse_days("2025-01-15"));
+    }
+
+    #[test]
+    fn rejects_malformed_dates() {
+        assert_eq!(parse_days("2025-13-01"), None);
+        assert_eq!(parse_days("2025-01"), None);
+        assert_eq!(parse_days("not a date"), None);
+    }
+
+    #[test]
+    fn week_starts_on_monday() {
+        assert_eq!(week_start("2025-01-15").as_deref(), Some("2025-01-13"));
+        assert_eq!(week_start("2025-01-13").as_deref(), Some("2025-01-13"));
+        assert_eq!(week_start("2025-01-19").as_deref(), Some("2025-01-13"));
+    }
+}
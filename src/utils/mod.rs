@@ -1,5 +1,34 @@
+pub mod agent;
+pub mod analytics;
+pub mod anomaly;
+pub mod api_usage;
+pub mod attribution;
+pub mod batch;
+pub mod budget;
+pub mod calibration;
+pub mod chat;
+pub mod conversation;
+pub mod date;
+pub mod download;
+pub mod model_query;
+pub mod model_search;
+pub mod price_list;
 pub mod pricing;
+pub mod projection;
+pub mod rag;
+pub mod recommend;
+pub mod routing;
+pub mod storage;
 pub mod tokens;
+pub mod transcript;
+pub mod uncertainty;
+pub mod upload;
+pub mod usage;
+pub mod workload;
 
+pub use download::download_file;
 pub use pricing::fetch_model_prices;
-pub use tokens::{estimate_tokens, preload_tokenizer};
+pub use tokens::{
+    chunk_by_tokens, estimate_tokens, estimate_tokens_for_model, preload_tokenizer,
+    tokenizer_name_for_model, truncate_to_tokens, TokenChunk, TokenCounter,
+};
@@ -0,0 +1,470 @@
+use crate::models::{Capability, ModelPricing};
+use crate::utils::model_search::search_score;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Comparison {
+    Equal,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl Comparison {
+    fn holds(self, value: f64, target: f64) -> bool {
+        match self {
+            // Prices are stored per token, so compare with a relative tolerance.
+            Comparison::Equal => (value - target).abs() <= target.abs() * 1e-9,
+            Comparison::Less => value < target,
+            Comparison::LessOrEqual => value <= target,
+            Comparison::Greater => value > target,
+            Comparison::GreaterOrEqual => value >= target,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TextField {
+    Name,
+    Provider,
+    Mode,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum NumberField {
+    Input,
+    Output,
+    CacheRead,
+    CacheWrite,
+    ContextWindow,
+    MaxOutput,
+}
+
+impl NumberField {
+    fn is_price(self) -> bool {
+        !matches!(self, NumberField::ContextWindow | NumberField::MaxOutput)
+    }
+
+    fn value(self, pricing: &ModelPricing) -> Option<f64> {
+        match self {
+            NumberField::Input => pricing.input_cost_per_token,
+            NumberField::Output => pricing.output_cost_per_token,
+            NumberField::CacheRead => pricing.cache_read_input_token_cost,
+            NumberField::CacheWrite => pricing.cache_creation_input_token_cost,
+            NumberField::ContextWindow => pricing.context_window().map(|n| n as f64),
+            NumberField::MaxOutput => pricing.max_output_tokens.map(|n| n as f64),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FlagField {
+    Capability(Capability),
+    Deprecated,
+    Batch,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Condition {
+    /// A bare word. Negated words exclude names containing them; the others
+    /// make up the query's fuzzy search text.
+    Search(String),
+    /// Case-insensitive substring of the field.
+    Contains(TextField, String),
+    /// Case-insensitive equality with the field.
+    Is(TextField, String),
+    Compare(NumberField, Comparison, f64),
+    Flag(FlagField, bool),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Term {
+    negated: bool,
+    condition: Condition,
+}
+
+/// A parsed model filter such as
+/// `provider:anthropic vision:true input<3/1M ctx>=200k -deprecated`.
+/// A model matches when it satisfies every term and its name fuzzily
+/// matches the bare words.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ModelQuery {
+    terms: Vec<Term>,
+    /// Bare words, searched fuzzily in the model name.
+    text: String,
+}
+
+/// A syntax error and the byte range of the offending term in the query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryError {
+    pub start: usize,
+    pub end: usize,
+    pub message: String,
+}
+
+/// Splits a query into whitespace-separated terms with their byte ranges,
+/// keeping double-quoted values (`name:"gpt 4"`) together.
+fn split_terms(query: &str) -> Result<Vec<(usize, usize)>, QueryError> {
+    let mut terms = Vec::new();
+    let mut start = None;
+    let mut quote_start = None;
+    for (index, ch) in query.char_indices() {
+        if ch == '"' {
+            quote_start = if quote_start.is_some() {
+                None
+            } else {
+                Some(index)
+            };
+            start.get_or_insert(index);
+        } else if ch.is_whitespace() && quote_start.is_none() {
+            if let Some(begin) = start.take() {
+                terms.push((begin, index));
+            }
+        } else {
+            start.get_or_insert(index);
+        }
+    }
+    if let Some(quote) = quote_start {
+        return Err(QueryError {
+            start: quote,
+            end: query.len(),
+            message: "unclosed quote".to_string(),
+        });
+    }
+    if let Some(begin) = start {
+        terms.push((begin, query.len()));
+    }
+    Ok(terms)
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_lowercase().as_str() {
+        "true" | "yes" | "1" => Some(true),
+        "false" | "no" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// Reads a price as dollars per token, accepting an optional `$` and a
+/// `/1M`, `/M`, `/1K`, `/K` or `/token` unit (`3/1M` is $3 per million
+/// tokens).
+fn parse_price(value: &str) -> Option<f64> {
+    let value = value.trim_start_matches('$').to_lowercase();
+    let (amount, per) = match value.split_once('/') {
+        Some((amount, unit)) => {
+            let per = match unit {
+                "1m" | "m" => 1_000_000.0,
+                "1k" | "k" => 1_000.0,
+                "token" => 1.0,
+                _ => return None,
+            };
+            (amount.to_string(), per)
+        }
+        None => (value, 1.0),
+    };
+    amount.parse::<f64>().ok().map(|amount| amount / per)
+}
+
+/// Reads a token count with an optional `k` or `M` suffix.
+fn parse_count(value: &str) -> Option<f64> {
+    let value = value.to_lowercase();
+    let (amount, scale) = if let Some(amount) = value.strip_suffix('k') {
+        (amount, 1_000.0)
+    } else if let Some(amount) = value.strip_suffix('m') {
+        (amount, 1_000_000.0)
+    } else {
+        (value.as_str(), 1.0)
+    };
+    amount.parse::<f64>().ok().map(|amount| amount * scale)
+}
+
+fn flag_field(name: &str) -> Option<FlagField> {
+    match name {
+        "deprecated" => Some(FlagField::Deprecated),
+        "batch" => Some(FlagField::Batch),
+        _ => Capability::from_key(name).map(FlagField::Capability),
+    }
+}
+
+fn number_field(name: &str) -> Option<NumberField> {
+    match name {
+        "input" => Some(NumberField::Input),
+        "output" => Some(NumberField::Output),
+        "cache_read" => Some(NumberField::CacheRead),
+        "cache_write" => Some(NumberField::CacheWrite),
+        "ctx" | "context" => Some(NumberField::ContextWindow),
+        "max_output" => Some(NumberField::MaxOutput),
+        _ => None,
+    }
+}
+
+fn text_field(name: &str) -> Option<TextField> {
+    match name {
+        "name" | "model" => Some(TextField::Name),
+        "provider" => Some(TextField::Provider),
+        "mode" => Some(TextField::Mode),
+        _ => None,
+    }
+}
+
+fn parse_condition(term: &str) -> Result<Condition, String> {
+    let field_end = term
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(term.len());
+    let (field, rest) = term.split_at(field_end);
+    let field = field.to_lowercase();
+
+    let operators = [
+        ("<=", Comparison::LessOrEqual),
+        (">=", Comparison::GreaterOrEqual),
+        ("<", Comparison::Less),
+        (">", Comparison::Greater),
+        (":", Comparison::Equal),
+        ("=", Comparison::Equal),
+    ];
+    let search = || Condition::Search(term.trim_matches('"').to_lowercase());
+    let Some((op, comparison)) = operators.iter().find(|(op, _)| rest.starts_with(op)) else {
+        // A bare word: a known flag on its own, otherwise a name search.
+        return Ok(match flag_field(&field).filter(|_| rest.is_empty()) {
+            Some(flag) => Condition::Flag(flag, true),
+            None => search(),
+        });
+    };
+    // Names such as `llama3:70b` look like a field and value, so anything
+    // before the operator that is not a known field is searched as a name.
+    let known = text_field(&field).is_some()
+        || flag_field(&field).is_some()
+        || number_field(&field).is_some();
+    if !known {
+        return Ok(search());
+    }
+    let value = rest[op.len()..].trim_matches('"');
+    if value.is_empty() {
+        return Err(format!("\"{}\" needs a value", field));
+    }
+    let ordered = *comparison != Comparison::Equal;
+
+    if let Some(text_field) = text_field(&field) {
+        if ordered {
+            return Err(format!("\"{}\" can only be matched with ':'", field));
+        }
+        let value = value.to_lowercase();
+        return Ok(match text_field {
+            TextField::Name => Condition::Contains(text_field, value),
+            _ => Condition::Is(text_field, value),
+        });
+    }
+
+    if let Some(flag) = flag_field(&field) {
+        if ordered {
+            return Err(format!("\"{}\" is true or false", field));
+        }
+        return parse_bool(value)
+            .map(|expected| Condition::Flag(flag, expected))
+            .ok_or_else(|| format!("\"{}\" is true or false, not \"{}\"", field, value));
+    }
+
+    let Some(number_field) = number_field(&field) else {
+        return Ok(search());
+    };
+    let target = if number_field.is_price() {
+        parse_price(value).ok_or_else(|| format!("\"{}\" is not a price like 3/1M", value))?
+    } else {
+        parse_count(value).ok_or_else(|| format!("\"{}\" is not a token count like 200k", value))?
+    };
+    Ok(Condition::Compare(number_field, *comparison, target))
+}
+
+/// Parses a model query. Terms are `field:value`, `field<value` (also `<=`,
+/// `>`, `>=`), a bare flag such as `vision`, or a bare word searched in the
+/// model name; a leading `-` negates a term. Terms whose field is not a
+/// known one, such as `llama3:70b`, are searched in the model name too.
+pub fn parse_model_query(query: &str) -> Result<ModelQuery, QueryError> {
+    let mut terms = Vec::new();
+    let mut words = Vec::new();
+    for (start, end) in split_terms(query)? {
+        let text = &query[start..end];
+        let (negated, body) = match text.strip_prefix('-') {
+            Some(body) if !body.is_empty() => (true, body),
+            _ => (false, text),
+        };
+        let condition = parse_condition(body).map_err(|message| QueryError {
+            start,
+            end,
+            message,
+        })?;
+        match condition {
+            Condition::Search(word) if !negated => words.push(word),
+            condition => terms.push(Term { negated, condition }),
+        }
+    }
+    Ok(ModelQuery {
+        terms,
+        text: words.join(" "),
+    })
+}
+
+impl ModelQuery {
+    /// Whether the query has bare words to rank models by.
+    pub fn has_text(&self) -> bool {
+        !self.text.is_empty()
+    }
+
+    /// How well the model matches the search text (higher is better), or
+    /// `None` when it fails a term or does not match the text at all.
+    pub fn rank(&self, name: &str, pricing: &ModelPricing, today: &str) -> Option<u32> {
+        if !self.matches_terms(name, pricing, today) {
+            return None;
+        }
+        search_score(&self.text, name)
+    }
+
+    fn matches_terms(&self, name: &str, pricing: &ModelPricing, today: &str) -> bool {
+        self.terms.iter().all(|term| {
+            let text = |field: TextField| match field {
+                TextField::Name => Some(name),
+                TextField::Provider => pricing.litellm_provider.as_deref(),
+                TextField::Mode => pricing.mode.as_deref(),
+            };
+            let holds = match &term.condition {
+                Condition::Search(value) => name.to_lowercase().contains(value.as_str()),
+                Condition::Contains(field, value) => {
+                    text(*field).is_some_and(|text| text.to_lowercase().contains(value.as_str()))
+                }
+                Condition::Is(field, value) => {
+                    text(*field).is_some_and(|text| text.eq_ignore_ascii_case(value))
+                }
+                Condition::Compare(field, comparison, target) => field
+                    .value(pricing)
+                    .is_some_and(|value| comparison.holds(value, *target)),
+                Condition::Flag(flag, expected) => {
+                    let value = match flag {
+                        FlagField::Capability(capability) => pricing.supports(*capability),
+                        FlagField::Deprecated => pricing.is_deprecated(today),
+                        FlagField::Batch => pricing.has_batch_pricing(),
+                    };
+                    value == *expected
+                }
+            };
+            holds != term.negated
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn pricing(value: serde_json::Value) -> ModelPricing {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn error(query: &str) -> QueryError {
+        parse_model_query(query).unwrap_err()
+    }
+
+    #[test]
+    fn parses_fields_flags_and_words() {
+        let query =
+            parse_model_query("provider:anthropic vision input<3/1M ctx>=200k -deprecated sonnet")
+                .unwrap();
+        assert_eq!(query.text, "sonnet");
+        assert_eq!(
+            query.terms,
+            [
+                Term {
+                    negated: false,
+                    condition: Condition::Is(TextField::Provider, "anthropic".to_string()),
+                },
+                Term {
+                    negated: false,
+                    condition: Condition::Flag(FlagField::Capability(Capability::Vision), true),
+                },
+                Term {
+                    negated: false,
+                    condition: Condition::Compare(NumberField::Input, Comparison::Less, 3e-6),
+                },
+                Term {
+                    negated: false,
+                    condition: Condition::Compare(
+                        NumberField::ContextWindow,
+                        Comparison::GreaterOrEqual,
+                        200_000.0,
+                    ),
+                },
+                Term {
+                    negated: true,
+                    condition: Condition::Flag(FlagField::Deprecated, true),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_fields_are_name_searches() {
+        let query = parse_model_query("llama3:70b").unwrap();
+        assert!(query.terms.is_empty());
+        assert_eq!(query.text, "llama3:70b");
+        assert_eq!(parse_model_query("gpt-4o").unwrap().text, "gpt-4o");
+    }
+
+    #[test]
+    fn reads_price_units_exactly() {
+        assert_eq!(parse_price("$3/1M"), Some(3e-6));
+        assert_eq!(parse_price("3/m"), Some(3e-6));
+        assert_eq!(parse_price("0.5/1K"), Some(5e-4));
+        assert_eq!(parse_price("0.5/k"), Some(5e-4));
+        assert_eq!(parse_price("2e-6/token"), Some(2e-6));
+        assert_eq!(parse_price("2e-6"), Some(2e-6));
+        assert_eq!(parse_price("3/11m"), None);
+        assert_eq!(parse_price("3/"), None);
+        assert_eq!(parse_count("1.5M"), Some(1_500_000.0));
+    }
+
+    #[test]
+    fn reports_errors_with_their_range() {
+        let query = "vision input<3/11m";
+        let err = error(query);
+        assert_eq!((err.start, err.end), (7, query.len()));
+        assert!(err.message.contains("3/11m"));
+        assert!(parse_model_query("input<3/m").is_ok());
+        assert_eq!(
+            error("vision:maybe").message,
+            "\"vision\" is true or false, not \"maybe\""
+        );
+        assert_eq!(
+            error("provider>openai").message,
+            "\"provider\" can only be matched with ':'"
+        );
+        assert_eq!(error("ctx>").message, "\"ctx\" needs a value");
+        let err = error("name:\"gpt 4");
+        assert_eq!((err.start, err.message.as_str()), (5, "unclosed quote"));
+    }
+
+    #[test]
+    fn ranks_matching_models() {
+        let sonnet = pricing(json!({
+            "litellm_provider": "anthropic",
+            "input_cost_per_token": 3e-6,
+            "max_input_tokens": 200000,
+            "supports_vision": true,
+        }));
+        let query =
+            parse_model_query("provider:anthropic vision input<=3/1M ctx>=200k sonnet").unwrap();
+        assert!(query.has_text());
+        assert!(query
+            .rank("claude-sonnet-4-5", &sonnet, "2025-01-01")
+            .is_some());
+        assert_eq!(query.rank("claude-opus-4-1", &sonnet, "2025-01-01"), None);
+
+        let query = parse_model_query("-sonnet name:\"claude s\"").unwrap();
+        assert!(!query.has_text());
+        assert_eq!(query.rank("claude-sonnet-4-5", &sonnet, "2025-01-01"), None);
+
+        let deprecated = pricing(json!({"deprecation_date": "2024-06-13"}));
+        let query = parse_model_query("-deprecated").unwrap();
+        assert_eq!(query.rank("gpt-4-0314", &deprecated, "2025-01-01"), None);
+        assert_eq!(query.rank("gpt-4-0314", &deprecated, "2024-01-01"), Some(0));
+    }
+}
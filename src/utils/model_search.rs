@@ -0,0 +1,208 @@
+use std::cmp::Ordering;
+
+/// Common short names and the catalogue model each one means.
+const ALIASES: &[(&str, &str)] = &[
+    ("sonnet", "claude-sonnet-4-5"),
+    ("claude", "claude-sonnet-4-5"),
+    ("opus", "claude-opus-4-1"),
+    ("haiku", "claude-haiku-4-5"),
+    ("4o", "gpt-4o"),
+    ("4o mini", "gpt-4o-mini"),
+    ("chatgpt", "gpt-4o"),
+    ("gpt", "gpt-5"),
+    ("gpt5", "gpt-5"),
+    ("gpt5 mini", "gpt-5-mini"),
+    ("gemini", "gemini-2.5-pro"),
+    ("gemini pro", "gemini-2.5-pro"),
+    ("flash", "gemini-2.5-flash"),
+    ("gemini flash", "gemini-2.5-flash"),
+    ("deepseek", "deepseek/deepseek-chat"),
+    ("r1", "deepseek/deepseek-reasoner"),
+    ("grok", "xai/grok-4"),
+    ("mistral", "mistral/mistral-large-latest"),
+];
+
+/// Match tiers, best first.
+const ALIAS: u32 = 1_000;
+const EXACT: u32 = 900;
+const PREFIX: u32 = 800;
+const TOKENS_IN_ORDER: u32 = 700;
+const TOKENS: u32 = 600;
+const SUBSTRING: u32 = 500;
+const FUZZY: u32 = 300;
+
+/// Lower-case words of a name or query, split at any non-alphanumeric
+/// character: `claude-sonnet-4-5` and `sonnet 4.5` both yield numbers as
+/// separate words.
+fn words(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Edit distance between two words.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Typos tolerated in a query word: none for short words, where a single
+/// edit already reaches unrelated names.
+fn allowed_typos(word: &str) -> usize {
+    match word.chars().count() {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+/// Fewest edits turning `query` into a name word or the start of one.
+fn word_distance(query: &str, word: &str) -> usize {
+    let prefix: String = word.chars().take(query.chars().count()).collect();
+    levenshtein(query, word).min(levenshtein(query, &prefix))
+}
+
+/// The alias target for a query, if the whole query is an alias.
+fn resolve_alias(query: &str) -> Option<&'static str> {
+    let query = words(query).join(" ");
+    ALIASES
+        .iter()
+        .find(|(alias, _)| words(alias).join(" ") == query)
+        .map(|(_, model)| *model)
+}
+
+/// Scores how well a model name matches a free-text search, higher being
+/// better: an alias, the exact name, a prefix, every query word starting a
+/// name word (in order or not), a plain substring, then every word within a
+/// few typos. `None` means no match; an empty search matches everything
+/// with a score of 0.
+pub fn search_score(query: &str, name: &str) -> Option<u32> {
+    let query_words = words(query);
+    if query_words.is_empty() {
+        return Some(0);
+    }
+    if resolve_alias(query) == Some(name) {
+        return Some(ALIAS);
+    }
+
+    let query_compact = query_words.concat();
+    let name_words = words(name);
+    let name_compact = name_words.concat();
+    if name_compact == query_compact {
+        return Some(EXACT);
+    }
+    if name_compact.starts_with(&query_compact) {
+        return Some(PREFIX);
+    }
+
+    let mut next = 0;
+    let in_order = query_words.iter().all(|query_word| {
+        match name_words[next..]
+            .iter()
+            .position(|word| word.starts_with(query_word.as_str()))
+        {
+            Some(offset) => {
+                next += offset + 1;
+                true
+            }
+            None => false,
+        }
+    });
+    if in_order {
+        return Some(TOKENS_IN_ORDER);
+    }
+    let all_words = query_words.iter().all(|query_word| {
+        name_words
+            .iter()
+            .any(|word| word.starts_with(query_word.as_str()))
+    });
+    if all_words {
+        return Some(TOKENS);
+    }
+    if name_compact.contains(&query_compact) {
+        return Some(SUBSTRING);
+    }
+
+    let mut typos = 0;
+    for query_word in &query_words {
+        let distance = name_words
+            .iter()
+            .map(|word| word_distance(query_word, word))
+            .min()?;
+        if distance > allowed_typos(query_word) {
+            return None;
+        }
+        typos += distance;
+    }
+    Some(FUZZY.saturating_sub(typos as u32 * 20))
+}
+
+/// Orders ranked model names best match first, preferring shorter names
+/// (the base model over its dated or regional variants), then by name.
+pub fn compare_ranked(a: (u32, &str), b: (u32, &str)) -> Ordering {
+    b.0.cmp(&a.0)
+        .then_with(|| a.1.len().cmp(&b.1.len()))
+        .then_with(|| a.1.cmp(b.1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scores_each_match_tier() {
+        assert_eq!(search_score("sonnet", "claude-sonnet-4-5"), Some(ALIAS));
+        assert_eq!(search_score("4o", "gpt-4o"), Some(ALIAS));
+        assert_eq!(search_score("Flash", "gemini-2.5-flash"), Some(ALIAS));
+        assert_eq!(search_score("gpt 4o", "gpt-4o"), Some(EXACT));
+        assert_eq!(search_score("gpt-4o", "gpt-4o-mini"), Some(PREFIX));
+        assert_eq!(
+            search_score("sonnet 4", "claude-sonnet-4-5"),
+            Some(TOKENS_IN_ORDER)
+        );
+        assert_eq!(search_score("4 sonnet", "claude-sonnet-4-5"), Some(TOKENS));
+        assert_eq!(search_score("onnet", "claude-sonnet-4-5"), Some(SUBSTRING));
+        assert_eq!(search_score("", "gpt-4o"), Some(0));
+    }
+
+    #[test]
+    fn tolerates_typos_in_longer_words() {
+        assert_eq!(search_score("sonet", "claude-sonnet-4-5"), Some(FUZZY - 20));
+        assert_eq!(
+            search_score("clade sonet", "claude-sonnet-4-5"),
+            Some(FUZZY - 40)
+        );
+        assert_eq!(search_score("gpx", "gpt-4o"), None);
+        assert_eq!(search_score("llama", "gpt-4o"), None);
+        assert_eq!(allowed_typos("gpt"), 0);
+        assert_eq!(allowed_typos("sonnet"), 1);
+        assert_eq!(allowed_typos("anthropic"), 2);
+    }
+
+    #[test]
+    fn ranks_better_matches_and_shorter_names_first() {
+        let mut ranked = [
+            (PREFIX, "gpt-4o-mini"),
+            (EXACT, "gpt-4o"),
+            (PREFIX, "gpt-4o-2024-08-06"),
+            (PREFIX, "gpt-4o-audio"),
+        ];
+        ranked.sort_by(|a, b| compare_ranked(*a, *b));
+        let names: Vec<&str> = ranked.iter().map(|(_, name)| *name).collect();
+        assert_eq!(
+            names,
+            ["gpt-4o", "gpt-4o-mini", "gpt-4o-audio", "gpt-4o-2024-08-06"]
+        );
+    }
+}
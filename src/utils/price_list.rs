@@ -0,0 +1,536 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use crate::models::{Capability, ModelPricing};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SortColumn {
+    Model,
+    Input,
+    Output,
+    MaxTokens,
+    Provider,
+    Mode,
+    CacheRead,
+    CacheWrite,
+}
+
+impl SortColumn {
+    pub const ALL: [SortColumn; 8] = [
+        SortColumn::Model,
+        SortColumn::Input,
+        SortColumn::Output,
+        SortColumn::MaxTokens,
+        SortColumn::Provider,
+        SortColumn::Mode,
+        SortColumn::CacheRead,
+        SortColumn::CacheWrite,
+    ];
+
+    /// Name used for the column in the `sort` URL parameter.
+    pub fn key(self) -> &'static str {
+        match self {
+            SortColumn::Model => "model",
+            SortColumn::Input => "input",
+            SortColumn::Output => "output",
+            SortColumn::MaxTokens => "max_tokens",
+            SortColumn::Provider => "provider",
+            SortColumn::Mode => "mode",
+            SortColumn::CacheRead => "cache_read",
+            SortColumn::CacheWrite => "cache_write",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SortColumn::Model => "Model Name",
+            SortColumn::Input => "Input Cost ($/token)",
+            SortColumn::Output => "Output Cost ($/token)",
+            SortColumn::MaxTokens => "Max Tokens",
+            SortColumn::Provider => "Provider",
+            SortColumn::Mode => "Mode",
+            SortColumn::CacheRead => "Cache Read ($/token)",
+            SortColumn::CacheWrite => "Cache Write ($/token)",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<SortColumn> {
+        SortColumn::ALL.into_iter().find(|c| c.key() == key)
+    }
+
+    /// Compares two models on this column. Models without a value sort
+    /// after those with one, whichever the direction.
+    fn compare(
+        self,
+        a: &(String, ModelPricing),
+        b: &(String, ModelPricing),
+        descending: bool,
+    ) -> Ordering {
+        fn by<T>(
+            a: Option<T>,
+            b: Option<T>,
+            cmp: impl Fn(&T, &T) -> Ordering,
+            descending: bool,
+        ) -> Ordering {
+            match (a, b) {
+                (Some(a), Some(b)) if descending => cmp(&b, &a),
+                (Some(a), Some(b)) => cmp(&a, &b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            }
+        }
+        let (pa, pb) = (&a.1, &b.1);
+        match self {
+            SortColumn::Model => by(Some(&a.0), Some(&b.0), |x, y| x.cmp(y), descending),
+            SortColumn::Input => by(
+                pa.input_cost_per_token,
+                pb.input_cost_per_token,
+                f64::total_cmp,
+                descending,
+            ),
+            SortColumn::Output => by(
+                pa.output_cost_per_token,
+                pb.output_cost_per_token,
+                f64::total_cmp,
+                descending,
+            ),
+            SortColumn::MaxTokens => by(pa.max_tokens, pb.max_tokens, i64::cmp, descending),
+            SortColumn::Provider => by(
+                pa.litellm_provider.as_ref(),
+                pb.litellm_provider.as_ref(),
+                |x, y| x.cmp(y),
+                descending,
+            ),
+            SortColumn::Mode => by(
+                pa.mode.as_ref(),
+                pb.mode.as_ref(),
+                |x, y| x.cmp(y),
+                descending,
+            ),
+            SortColumn::CacheRead => by(
+                pa.cache_read_input_token_cost,
+                pb.cache_read_input_token_cost,
+                f64::total_cmp,
+                descending,
+            ),
+            SortColumn::CacheWrite => by(
+                pa.cache_creation_input_token_cost,
+                pb.cache_creation_input_token_cost,
+                f64::total_cmp,
+                descending,
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortKey {
+    pub column: SortColumn,
+    pub descending: bool,
+}
+
+/// Reads a `sort` parameter such as `provider,-input`: comma-separated
+/// column keys in priority order, `-` marking a descending column. Unknown
+/// and repeated columns are ignored.
+pub fn parse_sort(value: &str) -> Vec<SortKey> {
+    let mut keys: Vec<SortKey> = Vec::new();
+    for part in value.split(',').map(str::trim) {
+        let (name, descending) = match part.strip_prefix('-') {
+            Some(name) => (name, true),
+            None => (part, false),
+        };
+        if let Some(column) = SortColumn::from_key(name) {
+            if !keys.iter().any(|key| key.column == column) {
+                keys.push(SortKey { column, descending });
+            }
+        }
+    }
+    keys
+}
+
+pub fn format_sort(keys: &[SortKey]) -> String {
+    keys.iter()
+        .map(|key| {
+            if key.descending {
+                format!("-{}", key.column.key())
+            } else {
+                key.column.key().to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Applies a header click. A plain click sorts by that column alone,
+/// flipping its direction if it already was the only sort; a shift-click
+/// flips the column within the current sort or appends it as the lowest
+/// priority.
+pub fn toggle_sort(keys: &[SortKey], column: SortColumn, additive: bool) -> Vec<SortKey> {
+    let current = keys.iter().position(|key| key.column == column);
+    if additive {
+        let mut keys = keys.to_vec();
+        match current {
+            Some(index) => keys[index].descending = !keys[index].descending,
+            None => keys.push(SortKey {
+                column,
+                descending: false,
+            }),
+        }
+        keys
+    } else {
+        let descending = keys.len() == 1 && current == Some(0) && !keys[0].descending;
+        vec![SortKey { column, descending }]
+    }
+}
+
+/// Sorts by each key in turn, then by model name.
+pub fn sort_models(models: &mut [(String, ModelPricing)], keys: &[SortKey]) {
+    models.sort_by(|a, b| {
+        keys.iter()
+            .map(|key| key.column.compare(a, b, key.descending))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or_else(|| a.0.cmp(&b.0))
+    });
+}
+
+/// Context window buckets offered as facets: label, inclusive lower bound and
+/// exclusive upper bound in tokens.
+pub const CONTEXT_RANGES: [(&str, i64, i64); 5] = [
+    ("Under 32k", 0, 32_000),
+    ("32k to 128k", 32_000, 128_000),
+    ("128k to 200k", 128_000, 200_000),
+    ("200k to 1M", 200_000, 1_000_000),
+    ("1M and above", 1_000_000, i64::MAX),
+];
+
+/// Input price buckets offered as facets, in dollars per million tokens.
+pub const PRICE_RANGES: [(&str, f64, f64); 5] = [
+    ("Free", 0.0, f64::MIN_POSITIVE),
+    ("Under $1 / 1M", f64::MIN_POSITIVE, 1.0),
+    ("$1 to $5 / 1M", 1.0, 5.0),
+    ("$5 to $15 / 1M", 5.0, 15.0),
+    ("$15 / 1M and above", 15.0, f64::INFINITY),
+];
+
+/// Selected facet values. Within a facet a model needs any selected value,
+/// except capabilities, which must all be supported.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PriceListFilters {
+    pub providers: HashSet<String>,
+    pub modes: HashSet<String>,
+    /// `Capability::key` of each required capability.
+    pub capabilities: HashSet<String>,
+    /// Labels from `CONTEXT_RANGES`.
+    pub context_ranges: HashSet<String>,
+    /// Labels from `PRICE_RANGES`.
+    pub price_ranges: HashSet<String>,
+    pub hide_deprecated: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Facet {
+    Provider,
+    Mode,
+    Capability(Capability),
+    Context,
+    Price,
+    Deprecated,
+}
+
+fn context_range(pricing: &ModelPricing) -> Option<&'static str> {
+    let window = pricing.context_window()?;
+    CONTEXT_RANGES
+        .iter()
+        .find(|(_, min, max)| (*min..*max).contains(&window))
+        .map(|(label, _, _)| *label)
+}
+
+fn price_range(pricing: &ModelPricing) -> Option<&'static str> {
+    let price = pricing.input_cost_per_token? * 1_000_000.0;
+    PRICE_RANGES
+        .iter()
+        .find(|(_, min, max)| (*min..*max).contains(&price))
+        .map(|(label, _, _)| *label)
+}
+
+fn any_of(selected: &HashSet<String>, value: Option<&str>) -> bool {
+    selected.is_empty() || value.is_some_and(|value| selected.contains(value))
+}
+
+impl PriceListFilters {
+    pub fn is_empty(&self) -> bool {
+        *self == PriceListFilters::default()
+    }
+
+    pub fn matches(&self, pricing: &ModelPricing, today: &str) -> bool {
+        self.matches_except(pricing, None, today)
+    }
+
+    /// Whether the model passes every facet but `skip`.
+    fn matches_except(&self, pricing: &ModelPricing, skip: Option<Facet>, today: &str) -> bool {
+        (skip == Some(Facet::Provider)
+            || any_of(&self.providers, pricing.litellm_provider.as_deref()))
+            && (skip == Some(Facet::Mode) || any_of(&self.modes, pricing.mode.as_deref()))
+            && self.capabilities.iter().all(|key| {
+                Capability::from_key(key).is_none_or(|capability| {
+                    skip == Some(Facet::Capability(capability)) || pricing.supports(capability)
+                })
+            })
+            && (skip == Some(Facet::Context)
+                || any_of(&self.context_ranges, context_range(pricing)))
+            && (skip == Some(Facet::Price) || any_of(&self.price_ranges, price_range(pricing)))
+            && (skip == Some(Facet::Deprecated)
+                || !self.hide_deprecated
+                || !pricing.is_deprecated(today))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FacetValue {
+    pub value: String,
+    pub label: String,
+    /// Models that would be shown with this value selected, given every
+    /// other facet's selection.
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FacetCounts {
+    pub providers: Vec<FacetValue>,
+    pub modes: Vec<FacetValue>,
+    pub capabilities: Vec<FacetValue>,
+    pub context_ranges: Vec<FacetValue>,
+    pub price_ranges: Vec<FacetValue>,
+    /// Deprecated models among those matching the other facets.
+    pub deprecated: usize,
+}
+
+/// Counts each facet value over `models`, the models already matching the
+/// search box. Providers and modes are listed most common first, leaving out
+/// values with no models unless they are selected.
+pub fn facet_counts(
+    models: &[(String, ModelPricing)],
+    filters: &PriceListFilters,
+    today: &str,
+) -> FacetCounts {
+    let count = |facet: Facet, has_value: &dyn Fn(&ModelPricing) -> bool| {
+        models
+            .iter()
+            .filter(|(_, pricing)| {
+                has_value(pricing) && filters.matches_except(pricing, Some(facet), today)
+            })
+            .count()
+    };
+    let grouped = |facet: Facet,
+                   selected: &HashSet<String>,
+                   value_of: fn(&ModelPricing) -> Option<&str>| {
+        let mut counts: HashMap<String, usize> = selected.iter().map(|v| (v.clone(), 0)).collect();
+        for (_, pricing) in models {
+            if let Some(value) = value_of(pricing) {
+                if filters.matches_except(pricing, Some(facet), today) {
+                    *counts.entry(value.to_string()).or_default() += 1;
+                }
+            }
+        }
+        let mut values: Vec<FacetValue> = counts
+            .into_iter()
+            .map(|(value, count)| FacetValue {
+                label: value.clone(),
+                value,
+                count,
+            })
+            .collect();
+        values.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+        values
+    };
+
+    FacetCounts {
+        providers: grouped(Facet::Provider, &filters.providers, |p| {
+            p.litellm_provider.as_deref()
+        }),
+        modes: grouped(Facet::Mode, &filters.modes, |p| p.mode.as_deref()),
+        capabilities: Capability::ALL
+            .into_iter()
+            .map(|capability| FacetValue {
+                value: capability.key().to_string(),
+                label: capability.label().to_string(),
+                count: count(Facet::Capability(capability), &|p| p.supports(capability)),
+            })
+            .collect(),
+        context_ranges: CONTEXT_RANGES
+            .iter()
+            .map(|(label, _, _)| FacetValue {
+                value: label.to_string(),
+                label: label.to_string(),
+                count: count(Facet::Context, &|p| context_range(p) == Some(*label)),
+            })
+            .collect(),
+        price_ranges: PRICE_RANGES
+            .iter()
+            .map(|(label, _, _)| FacetValue {
+                value: label.to_string(),
+                label: label.to_string(),
+                count: count(Facet::Price, &|p| price_range(p) == Some(*label)),
+            })
+            .collect(),
+        deprecated: count(Facet::Deprecated, &|p| p.is_deprecated(today)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn key(column: SortColumn, descending: bool) -> SortKey {
+        SortKey { column, descending }
+    }
+
+    fn models(value: Value) -> Vec<(String, ModelPricing)> {
+        let prices: HashMap<String, ModelPricing> = serde_json::from_value(value).unwrap();
+        prices.into_iter().collect()
+    }
+
+    fn names(models: &[(String, ModelPricing)]) -> Vec<&str> {
+        models.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    #[test]
+    fn sort_parameter_round_trips() {
+        let keys = parse_sort("provider, -input,bogus,-provider,");
+        assert_eq!(
+            keys,
+            [
+                key(SortColumn::Provider, false),
+                key(SortColumn::Input, true)
+            ]
+        );
+        assert_eq!(format_sort(&keys), "provider,-input");
+        assert!(parse_sort("").is_empty());
+    }
+
+    #[test]
+    fn clicks_replace_and_shift_clicks_append() {
+        let input = [key(SortColumn::Input, false)];
+        assert_eq!(
+            toggle_sort(&input, SortColumn::Input, false),
+            [key(SortColumn::Input, true)]
+        );
+        assert_eq!(
+            toggle_sort(&[key(SortColumn::Input, true)], SortColumn::Input, false),
+            input
+        );
+        assert_eq!(
+            toggle_sort(&input, SortColumn::Mode, false),
+            [key(SortColumn::Mode, false)]
+        );
+
+        let both = toggle_sort(&input, SortColumn::Mode, true);
+        assert_eq!(
+            both,
+            [key(SortColumn::Input, false), key(SortColumn::Mode, false)]
+        );
+        assert_eq!(
+            toggle_sort(&both, SortColumn::Input, true),
+            [key(SortColumn::Input, true), key(SortColumn::Mode, false)]
+        );
+        // A plain click on one of several sort columns keeps it ascending.
+        assert_eq!(
+            toggle_sort(&both, SortColumn::Input, false),
+            [key(SortColumn::Input, false)]
+        );
+    }
+
+    #[test]
+    fn sorts_by_keys_with_missing_values_last() {
+        let mut list = models(json!({
+            "b": {"litellm_provider": "openai", "input_cost_per_token": 2e-6},
+            "a": {"litellm_provider": "openai", "input_cost_per_token": 1e-6},
+            "c": {"litellm_provider": "anthropic", "input_cost_per_token": 3e-6},
+            "d": {"litellm_provider": "anthropic"},
+        }));
+        sort_models(&mut list, &[key(SortColumn::Input, true)]);
+        assert_eq!(names(&list), ["c", "b", "a", "d"]);
+        sort_models(&mut list, &[key(SortColumn::Input, false)]);
+        assert_eq!(names(&list), ["a", "b", "c", "d"]);
+        sort_models(
+            &mut list,
+            &[
+                key(SortColumn::Provider, false),
+                key(SortColumn::Input, true),
+            ],
+        );
+        assert_eq!(names(&list), ["c", "d", "b", "a"]);
+        sort_models(&mut list, &[]);
+        assert_eq!(names(&list), ["a", "b", "c", "d"]);
+    }
+
+    fn catalogue() -> Vec<(String, ModelPricing)> {
+        models(json!({
+            "gpt-4o": {"litellm_provider": "openai", "mode": "chat", "input_cost_per_token": 2.5e-6,
+                       "max_input_tokens": 128000, "supports_vision": true},
+            "gpt-4o-mini": {"litellm_provider": "openai", "mode": "chat", "input_cost_per_token": 1.5e-7,
+                            "max_input_tokens": 128000, "supports_vision": true},
+            "gpt-4-0314": {"litellm_provider": "openai", "mode": "chat", "input_cost_per_token": 3e-5,
+                           "max_input_tokens": 8192, "deprecation_date": "2024-06-13"},
+            "claude-sonnet-4-5": {"litellm_provider": "anthropic", "mode": "chat", "input_cost_per_token": 3e-6,
+                                  "max_input_tokens": 200000, "supports_vision": true},
+            "text-embedding-3-small": {"litellm_provider": "openai", "mode": "embedding",
+                                       "input_cost_per_token": 2e-8, "max_input_tokens": 8191},
+            "ollama/llama3": {"litellm_provider": "ollama", "mode": "chat", "input_cost_per_token": 0.0},
+        }))
+    }
+
+    fn count(values: &[FacetValue], value: &str) -> usize {
+        values
+            .iter()
+            .find(|v| v.value == value)
+            .map(|v| v.count)
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn filters_combine_facets() {
+        let filters = PriceListFilters {
+            providers: HashSet::from(["openai".to_string(), "anthropic".to_string()]),
+            capabilities: HashSet::from(["vision".to_string()]),
+            price_ranges: HashSet::from(["$1 to $5 / 1M".to_string()]),
+            ..PriceListFilters::default()
+        };
+        let mut matching: Vec<String> = catalogue()
+            .iter()
+            .filter(|(_, pricing)| filters.matches(pricing, "2025-01-01"))
+            .map(|(name, _)| name.clone())
+            .collect();
+        matching.sort();
+        assert_eq!(matching, ["claude-sonnet-4-5", "gpt-4o"]);
+        assert!(PriceListFilters::default().is_empty());
+        assert!(!filters.is_empty());
+    }
+
+    #[test]
+    fn counts_ignore_their_own_facet() {
+        let filters = PriceListFilters {
+            providers: HashSet::from(["anthropic".to_string()]),
+            hide_deprecated: true,
+            ..PriceListFilters::default()
+        };
+        let counts = facet_counts(&catalogue(), &filters, "2025-01-01");
+        // Provider counts ignore the provider selection but not the others.
+        assert_eq!(count(&counts.providers, "openai"), 3);
+        assert_eq!(count(&counts.providers, "anthropic"), 1);
+        assert_eq!(counts.providers[0].value, "openai");
+        assert_eq!(count(&counts.modes, "chat"), 1);
+        assert_eq!(count(&counts.modes, "embedding"), 0);
+        assert_eq!(count(&counts.context_ranges, "128k to 200k"), 0);
+        assert_eq!(count(&counts.context_ranges, "200k to 1M"), 1);
+        assert_eq!(counts.deprecated, 0);
+
+        let counts = facet_counts(&catalogue(), &PriceListFilters::default(), "2025-01-01");
+        assert_eq!(count(&counts.capabilities, "vision"), 3);
+        assert_eq!(count(&counts.price_ranges, "Free"), 1);
+        assert_eq!(count(&counts.price_ranges, "$15 / 1M and above"), 1);
+        assert_eq!(counts.deprecated, 1);
+    }
+}
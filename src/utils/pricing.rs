@@ -18,13 +18,12 @@ pub async fn fetch_model_prices(
         }
 
         if let Ok(pricing) = serde_json::from_value::<ModelPricing>(value) {
-            if pricing.input_cost_per_token.is_some()
-                && pricing.output_cost_per_token.is_some()
+            let is_generative = pricing.output_cost_per_token.is_some()
                 && (pricing
                     .mode
                     .as_ref()
-                    .map_or(true, |m| m == "chat" || m == "completion"))
-            {
+                    .is_none_or(|m| m == "chat" || m == "completion"));
+            if pricing.input_cost_per_token.is_some() && (is_generative || pricing.is_embedding()) {
                 filtered_prices.insert(key, pricing);
             }
         }
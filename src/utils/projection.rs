@@ -0,0 +1,100 @@
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrafficPlan {
+    pub requests_per_day: f64,
+    pub days_per_month: f64,
+    /// Month-over-month traffic growth as a fraction (0.05 = 5%).
+    pub monthly_growth: f64,
+    pub horizon_months: usize,
+}
+
+impl Default for TrafficPlan {
+    fn default() -> Self {
+        Self {
+            requests_per_day: 1_000.0,
+            days_per_month: 30.0,
+            monthly_growth: 0.0,
+            horizon_months: 12,
+        }
+    }
+}
+
+impl TrafficPlan {
+    /// Requests in the first month, before any growth is applied.
+    pub fn requests_per_month(&self) -> f64 {
+        self.requests_per_day * self.days_per_month
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MonthProjection {
+    pub month: usize,
+    pub requests: f64,
+    pub cost: f64,
+    pub cumulative_cost: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TrafficProjection {
+    pub daily_cost: f64,
+    pub monthly_cost: f64,
+    pub annual_cost: f64,
+    pub months: Vec<MonthProjection>,
+}
+
+pub fn project_traffic(plan: &TrafficPlan, cost_per_request: f64) -> TrafficProjection {
+    let growth = 1.0 + plan.monthly_growth;
+    let monthly_requests = |month: usize| plan.requests_per_month() * growth.powi(month as i32);
+
+    let mut cumulative_cost = 0.0;
+    let months = (0..plan.horizon_months)
+        .map(|month| {
+            let requests = monthly_requests(month);
+            let cost = requests * cost_per_request;
+            cumulative_cost += cost;
+            MonthProjection {
+                month: month + 1,
+                requests,
+                cost,
+                cumulative_cost,
+            }
+        })
+        .collect();
+
+    TrafficProjection {
+        daily_cost: plan.requests_per_day * cost_per_request,
+        monthly_cost: plan.requests_per_month() * cost_per_request,
+        annual_cost: (0..12).map(monthly_requests).sum::<f64>() * cost_per_request,
+        months,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flat_traffic_scales_linearly() {
+        let projection = project_traffic(&TrafficPlan::default(), 0.01);
+        assert!((projection.daily_cost - 10.0).abs() < 1e-9);
+        assert!((projection.monthly_cost - 300.0).abs() < 1e-9);
+        assert!((projection.annual_cost - 3_600.0).abs() < 1e-9);
+        assert_eq!(projection.months.len(), 12);
+        assert!((projection.months[11].cumulative_cost - 3_600.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn growth_compounds_monthly() {
+        let plan = TrafficPlan {
+            monthly_growth: 0.1,
+            horizon_months: 3,
+            ..TrafficPlan::default()
+        };
+        let projection = project_traffic(&plan, 0.001);
+        let requests: Vec<f64> = projection.months.iter().map(|m| m.requests).collect();
+        assert!((requests[0] - 30_000.0).abs() < 1e-6);
+        assert!((requests[1] - 33_000.0).abs() < 1e-6);
+        assert!((requests[2] - 36_300.0).abs() < 1e-6);
+        // The annual figure covers twelve months whatever the horizon.
+        assert!(projection.annual_cost > projection.months[2].cumulative_cost * 4.0);
+    }
+}
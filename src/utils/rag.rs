@@ -0,0 +1,184 @@
+use crate::models::ModelPricing;
+
+/// Fallback vector storage rate when the embedding model does not list one.
+pub const DEFAULT_VECTOR_STORE_COST_PER_GB_PER_DAY: f64 = 0.10;
+
+const BYTES_PER_GB: f64 = 1_000_000_000.0;
+const BYTES_PER_DIMENSION: usize = 4;
+const BYTES_PER_TOKEN: usize = 4;
+const DAYS_PER_MONTH: f64 = 30.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RagPlan {
+    pub document_tokens: Vec<usize>,
+    pub chunk_size: usize,
+    pub chunk_overlap: usize,
+    pub embedding_dimensions: usize,
+    pub top_k: usize,
+    pub query_tokens: usize,
+    pub prompt_overhead_tokens: usize,
+    pub output_tokens: usize,
+    pub queries_per_month: usize,
+    pub vector_store_cost_per_gb_per_day: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RagCostEstimate {
+    pub chunks: usize,
+    pub embedded_tokens: usize,
+    pub ingestion_cost: f64,
+    pub storage_gb: f64,
+    pub storage_cost_per_month: f64,
+    pub generation_input_tokens: usize,
+    pub query_embedding_cost: f64,
+    pub generation_input_cost: f64,
+    pub generation_output_cost: f64,
+    pub cost_per_query: f64,
+    pub query_cost_per_month: f64,
+    pub total_cost_per_month: f64,
+}
+
+/// Number of chunks and total embedded tokens (overlap included) produced by
+/// chunking a document the same way as `chunk_by_tokens`, which also
+/// rejects an overlap that is not smaller than the chunk size.
+pub fn chunk_stats(
+    tokens: usize,
+    chunk_size: usize,
+    overlap: usize,
+) -> Result<(usize, usize), String> {
+    if overlap >= chunk_size {
+        return Err(format!(
+            "The overlap ({} tokens) must be smaller than the chunk size ({} tokens)",
+            overlap, chunk_size
+        ));
+    }
+    if tokens == 0 {
+        return Ok((0, 0));
+    }
+    let step = chunk_size - overlap;
+    let chunks = if tokens <= chunk_size {
+        1
+    } else {
+        1 + (tokens - chunk_size).div_ceil(step)
+    };
+    Ok((chunks, tokens + (chunks - 1) * overlap))
+}
+
+pub fn estimate_rag_costs(
+    plan: &RagPlan,
+    embedding: &ModelPricing,
+    generation: &ModelPricing,
+) -> Result<RagCostEstimate, String> {
+    let embedding_price = embedding.input_cost_per_token.unwrap_or(0.0);
+
+    let (chunks, embedded_tokens) = plan
+        .document_tokens
+        .iter()
+        .map(|&tokens| chunk_stats(tokens, plan.chunk_size, plan.chunk_overlap))
+        .try_fold((0, 0), |(c, t), stats| {
+            stats.map(|(chunks, tokens)| (c + chunks, t + tokens))
+        })?;
+    let ingestion_cost = embedded_tokens as f64 * embedding_price;
+
+    let bytes_per_chunk = plan.embedding_dimensions * BYTES_PER_DIMENSION;
+    let storage_bytes = chunks * bytes_per_chunk + embedded_tokens * BYTES_PER_TOKEN;
+    let storage_gb = storage_bytes as f64 / BYTES_PER_GB;
+    let storage_cost_per_month =
+        storage_gb * plan.vector_store_cost_per_gb_per_day * DAYS_PER_MONTH;
+
+    let retrieved_tokens = plan.top_k.min(chunks) * plan.chunk_size;
+    let generation_input_tokens =
+        plan.prompt_overhead_tokens + plan.query_tokens + retrieved_tokens;
+    let query_embedding_cost = plan.query_tokens as f64 * embedding_price;
+    let generation_input_cost =
+        generation_input_tokens as f64 * generation.input_cost_per_token.unwrap_or(0.0);
+    let generation_output_cost =
+        plan.output_tokens as f64 * generation.output_cost_per_token.unwrap_or(0.0);
+    let cost_per_query = query_embedding_cost + generation_input_cost + generation_output_cost;
+    let query_cost_per_month = cost_per_query * plan.queries_per_month as f64;
+
+    Ok(RagCostEstimate {
+        chunks,
+        embedded_tokens,
+        ingestion_cost,
+        storage_gb,
+        storage_cost_per_month,
+        generation_input_tokens,
+        query_embedding_cost,
+        generation_input_cost,
+        generation_output_cost,
+        cost_per_query,
+        query_cost_per_month,
+        total_cost_per_month: storage_cost_per_month + query_cost_per_month,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{chunk_by_tokens, estimate_tokens};
+    use serde_json::json;
+
+    fn pricing(input: f64, output: f64) -> ModelPricing {
+        serde_json::from_value(json!({
+            "input_cost_per_token": input,
+            "output_cost_per_token": output,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn chunk_stats_matches_chunk_by_tokens() {
+        let text = "word ".repeat(1_000);
+        let tokens = estimate_tokens(&text);
+        for (size, overlap) in [(100, 0), (128, 32), (500, 499), (2_000, 10)] {
+            let chunks = chunk_by_tokens(&text, size, overlap).unwrap();
+            let embedded: usize = chunks.iter().map(|c| c.token_count).sum();
+            assert_eq!(
+                chunk_stats(tokens, size, overlap),
+                Ok((chunks.len(), embedded))
+            );
+        }
+    }
+
+    #[test]
+    fn chunk_stats_of_empty_document() {
+        assert_eq!(chunk_stats(0, 512, 64), Ok((0, 0)));
+    }
+
+    #[test]
+    fn chunk_stats_rejects_overlap_not_smaller_than_size() {
+        assert!(chunk_stats(10_000, 512, 512).is_err());
+        assert!(chunk_stats(10_000, 512, 600).is_err());
+        assert!(chunk_stats(0, 0, 0).is_err());
+        assert!(chunk_by_tokens("word", 512, 512).is_err());
+    }
+
+    #[test]
+    fn estimates_ingestion_storage_and_query_costs() {
+        let plan = RagPlan {
+            document_tokens: vec![1_000, 500],
+            chunk_size: 500,
+            chunk_overlap: 0,
+            embedding_dimensions: 1_000,
+            top_k: 2,
+            query_tokens: 10,
+            prompt_overhead_tokens: 90,
+            output_tokens: 100,
+            queries_per_month: 1_000,
+            vector_store_cost_per_gb_per_day: DEFAULT_VECTOR_STORE_COST_PER_GB_PER_DAY,
+        };
+        let estimate =
+            estimate_rag_costs(&plan, &pricing(1e-7, 0.0), &pricing(1e-6, 2e-6)).unwrap();
+
+        assert_eq!(estimate.chunks, 3);
+        assert_eq!(estimate.embedded_tokens, 1_500);
+        assert!((estimate.ingestion_cost - 1.5e-4).abs() < 1e-12);
+        // 3 chunks of 4,000 vector bytes plus 1,500 tokens of text.
+        assert!((estimate.storage_gb - 18_000.0 / BYTES_PER_GB).abs() < 1e-15);
+        assert_eq!(estimate.generation_input_tokens, 1_100);
+        let per_query = 10.0 * 1e-7 + 1_100.0 * 1e-6 + 100.0 * 2e-6;
+        assert!((estimate.cost_per_query - per_query).abs() < 1e-12);
+        assert!((estimate.query_cost_per_month - per_query * 1_000.0).abs() < 1e-9);
+    }
+}
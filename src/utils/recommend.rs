@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use crate::models::{Capability, ModelPricing};
+use crate::utils::TokenCounter;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ModelRequirements {
+    pub capabilities: Vec<Capability>,
+    pub min_context_window: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recommendation {
+    pub model: String,
+    pub input_tokens: usize,
+    pub output_tokens: usize,
+    pub cost: f64,
+    pub input_cost_per_token: f64,
+    pub output_cost_per_token: f64,
+    pub context_window: Option<i64>,
+}
+
+/// Ranks every non-deprecated chat model that satisfies `requirements` and
+/// fits the given prompt, cheapest first. Models without both an input and
+/// an output price, or listed as entirely free (such as local models), are
+/// left out rather than ranked as the cheapest.
+pub fn recommend_models(
+    prices: &HashMap<String, ModelPricing>,
+    requirements: &ModelRequirements,
+    input: &str,
+    output: &str,
+    today: &str,
+) -> Vec<Recommendation> {
+    let mut counter = TokenCounter::new(input, output);
+
+    let mut recommendations: Vec<Recommendation> = prices
+        .iter()
+        .filter(|(_, pricing)| !pricing.is_embedding() && !pricing.is_deprecated(today))
+        .filter(|(_, pricing)| {
+            match (pricing.input_cost_per_token, pricing.output_cost_per_token) {
+                (Some(input), Some(output)) => input + output > 0.0,
+                _ => false,
+            }
+        })
+        .filter(|(_, pricing)| {
+            requirements
+                .capabilities
+                .iter()
+                .all(|&capability| pricing.supports(capability))
+        })
+        .filter(|(_, pricing)| {
+            requirements.min_context_window <= 0
+                || pricing
+                    .context_window()
+                    .is_some_and(|window| window >= requirements.min_context_window)
+        })
+        .filter_map(|(model, pricing)| {
+            let (input_tokens, output_tokens) = counter.counts(model);
+            if pricing.fits_request(input_tokens, output_tokens) == Some(false) {
+                return None;
+            }
+            let (input_cost, output_cost) = pricing.request_cost(input_tokens, output_tokens);
+            Some(Recommendation {
+                model: model.clone(),
+                input_tokens,
+                output_tokens,
+                cost: input_cost + output_cost,
+                input_cost_per_token: pricing.input_cost_per_token.unwrap_or(0.0),
+                output_cost_per_token: pricing.output_cost_per_token.unwrap_or(0.0),
+                context_window: pricing.context_window(),
+            })
+        })
+        .collect();
+
+    // Break ties (e.g. an empty prompt) by list price, then by name.
+    recommendations.sort_by(|a, b| {
+        a.cost
+            .total_cmp(&b.cost)
+            .then_with(|| {
+                (a.input_cost_per_token + a.output_cost_per_token)
+                    .total_cmp(&(b.input_cost_per_token + b.output_cost_per_token))
+            })
+            .then_with(|| a.model.cmp(&b.model))
+    });
+    recommendations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn prices(models: Value) -> HashMap<String, ModelPricing> {
+        serde_json::from_value(models).unwrap()
+    }
+
+    fn names(recommendations: &[Recommendation]) -> Vec<&str> {
+        recommendations.iter().map(|r| r.model.as_str()).collect()
+    }
+
+    #[test]
+    fn ranks_cheapest_first_and_skips_unpriced_models() {
+        let prices = prices(json!({
+            "pricey": {"input_cost_per_token": 3e-6, "output_cost_per_token": 15e-6},
+            "cheap": {"input_cost_per_token": 1e-7, "output_cost_per_token": 4e-7},
+            "ollama/llama3": {"input_cost_per_token": 0.0, "output_cost_per_token": 0.0},
+            "unlisted-output": {"input_cost_per_token": 1e-9},
+            "embedder": {"input_cost_per_token": 1e-9, "output_cost_per_token": 0.0, "mode": "embedding"},
+            "retired": {"input_cost_per_token": 1e-9, "output_cost_per_token": 1e-9, "deprecation_date": "2024-01-01"},
+        }));
+        let ranked = recommend_models(
+            &prices,
+            &ModelRequirements::default(),
+            "hello",
+            "world",
+            "2025-01-01",
+        );
+        assert_eq!(names(&ranked), ["cheap", "pricey"]);
+        assert!(ranked[0].cost < ranked[1].cost);
+    }
+
+    #[test]
+    fn filters_by_capability_and_context_window() {
+        let prices = prices(json!({
+            "small": {"input_cost_per_token": 1e-7, "output_cost_per_token": 1e-7, "max_input_tokens": 8000, "supports_vision": true},
+            "large": {"input_cost_per_token": 1e-6, "output_cost_per_token": 1e-6, "max_input_tokens": 200000, "supports_vision": true},
+            "blind": {"input_cost_per_token": 1e-8, "output_cost_per_token": 1e-8, "max_input_tokens": 200000},
+        }));
+        let requirements = ModelRequirements {
+            capabilities: vec![Capability::Vision],
+            min_context_window: 100_000,
+        };
+        let ranked = recommend_models(&prices, &requirements, "hi", "", "2025-01-01");
+        assert_eq!(names(&ranked), ["large"]);
+    }
+}
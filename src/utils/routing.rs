@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+
+use crate::models::ModelPricing;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CascadeTier {
+    pub model: String,
+    /// Fraction of the calls reaching this tier that escalate to the next one.
+    pub escalation_rate: f64,
+    /// Whether a call escalated to this tier sends the full original prompt
+    /// again, rather than a short hand-off from the previous tier.
+    pub resend_prompt: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CascadePlan {
+    pub tiers: Vec<CascadeTier>,
+    pub prompt_tokens: usize,
+    pub output_tokens: usize,
+    pub handoff_tokens: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TierCost {
+    pub model: String,
+    /// Fraction of all requests that reach this tier.
+    pub traffic_share: f64,
+    pub input_tokens: usize,
+    pub cost_per_call: f64,
+    /// This tier's contribution to the blended cost of one request.
+    pub cost_per_request: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CascadeEstimate {
+    pub tiers: Vec<TierCost>,
+    pub blended_cost_per_request: f64,
+    pub top_model_cost_per_request: f64,
+}
+
+impl CascadeEstimate {
+    /// Fraction saved compared to sending every request to the last tier.
+    pub fn savings(&self) -> f64 {
+        if self.top_model_cost_per_request > 0.0 {
+            1.0 - self.blended_cost_per_request / self.top_model_cost_per_request
+        } else {
+            0.0
+        }
+    }
+}
+
+fn call_cost(pricing: Option<&ModelPricing>, input_tokens: usize, output_tokens: usize) -> f64 {
+    pricing
+        .map(|p| {
+            let (input, output) = p.request_cost(input_tokens, output_tokens);
+            input + output
+        })
+        .unwrap_or(0.0)
+}
+
+/// Every request starts at the first tier; a share of each tier's calls
+/// escalates to the next one. The last tier never escalates.
+pub fn simulate_cascade(
+    plan: &CascadePlan,
+    prices: &HashMap<String, ModelPricing>,
+) -> CascadeEstimate {
+    let mut traffic_share = 1.0;
+    let mut tiers = Vec::with_capacity(plan.tiers.len());
+
+    for (index, tier) in plan.tiers.iter().enumerate() {
+        let input_tokens = if index == 0 || tier.resend_prompt {
+            plan.prompt_tokens
+        } else {
+            plan.handoff_tokens
+        };
+        let cost_per_call = call_cost(prices.get(&tier.model), input_tokens, plan.output_tokens);
+        tiers.push(TierCost {
+            model: tier.model.clone(),
+            traffic_share,
+            input_tokens,
+            cost_per_call,
+            cost_per_request: traffic_share * cost_per_call,
+        });
+        traffic_share *= tier.escalation_rate.clamp(0.0, 1.0);
+    }
+
+    let top_model_cost_per_request = plan
+        .tiers
+        .last()
+        .map(|tier| {
+            call_cost(
+                prices.get(&tier.model),
+                plan.prompt_tokens,
+                plan.output_tokens,
+            )
+        })
+        .unwrap_or(0.0);
+
+    CascadeEstimate {
+        blended_cost_per_request: tiers.iter().map(|t| t.cost_per_request).sum(),
+        top_model_cost_per_request,
+        tiers,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn tier(model: &str, escalation_rate: f64, resend_prompt: bool) -> CascadeTier {
+        CascadeTier {
+            model: model.to_string(),
+            escalation_rate,
+            resend_prompt,
+        }
+    }
+
+    fn prices() -> HashMap<String, ModelPricing> {
+        serde_json::from_value(json!({
+            "small": {"input_cost_per_token": 1e-7, "output_cost_per_token": 1e-7},
+            "large": {"input_cost_per_token": 1e-5, "output_cost_per_token": 1e-5},
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn escalated_share_pays_for_both_tiers() {
+        let plan = CascadePlan {
+            tiers: vec![tier("small", 0.2, false), tier("large", 0.5, true)],
+            prompt_tokens: 900,
+            output_tokens: 100,
+            handoff_tokens: 50,
+        };
+        let estimate = simulate_cascade(&plan, &prices());
+        assert_eq!(estimate.tiers[1].traffic_share, 0.2);
+        assert_eq!(estimate.tiers[1].input_tokens, 900);
+        assert!((estimate.top_model_cost_per_request - 1e-2).abs() < 1e-12);
+        assert!((estimate.blended_cost_per_request - (1e-4 + 0.2 * 1e-2)).abs() < 1e-12);
+        assert!((estimate.savings() - 0.79).abs() < 1e-9);
+    }
+
+    #[test]
+    fn handoff_replaces_the_prompt() {
+        let plan = CascadePlan {
+            tiers: vec![tier("small", 1.0, false), tier("large", 0.0, false)],
+            prompt_tokens: 900,
+            output_tokens: 100,
+            handoff_tokens: 50,
+        };
+        let estimate = simulate_cascade(&plan, &prices());
+        assert_eq!(estimate.tiers[1].input_tokens, 50);
+        assert!((estimate.tiers[1].cost_per_call - 150.0 * 1e-5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn no_tiers_saves_nothing() {
+        let plan = CascadePlan {
+            tiers: Vec::new(),
+            prompt_tokens: 1,
+            output_tokens: 1,
+            handoff_tokens: 1,
+        };
+        assert_eq!(simulate_cascade(&plan, &prices()).savings(), 0.0);
+    }
+}
@@ -0,0 +1,24 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use wasm_bindgen::JsValue;
+use web_sys::Storage;
+
+fn local_storage() -> Result<Storage, JsValue> {
+    web_sys::window()
+        .ok_or_else(|| JsValue::from_str("No window available"))?
+        .local_storage()?
+        .ok_or_else(|| JsValue::from_str("Local storage is unavailable"))
+}
+
+/// Reads a JSON value saved under `key` in the browser's local storage.
+/// Missing or unreadable entries yield `None`.
+pub fn load_json<T: DeserializeOwned>(key: &str) -> Option<T> {
+    let text = local_storage().ok()?.get_item(key).ok()??;
+    serde_json::from_str(&text).ok()
+}
+
+/// Saves `value` as JSON under `key` in the browser's local storage.
+pub fn save_json<T: Serialize>(key: &str, value: &T) -> Result<(), JsValue> {
+    let text = serde_json::to_string(value).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    local_storage()?.set_item(key, &text)
+}
@@ -1,13 +1,233 @@
 use once_cell::sync::Lazy;
-use tiktoken_rs::{cl100k_base, CoreBPE};
+use serde::Serialize;
+use std::collections::HashMap;
+use tiktoken_rs::tokenizer::{get_tokenizer, Tokenizer};
+use tiktoken_rs::{cl100k_base, o200k_base, CoreBPE, Rank};
 
 static TOKENIZER: Lazy<CoreBPE> = Lazy::new(|| cl100k_base().expect("Failed to load tokenizer"));
+static O200K_TOKENIZER: Lazy<CoreBPE> =
+    Lazy::new(|| o200k_base().expect("Failed to load tokenizer"));
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TokenChunk {
+    pub index: usize,
+    pub start_token: usize,
+    pub end_token: usize,
+    pub token_count: usize,
+    pub text: String,
+}
 
 pub fn preload_tokenizer() {
     let _ = &*TOKENIZER;
+    let _ = &*O200K_TOKENIZER;
 }
 
 pub fn estimate_tokens(text: &str) -> usize {
     let tokens = TOKENIZER.encode_with_special_tokens(text);
     tokens.len()
 }
+
+fn model_tokenizer(model: &str) -> Option<Tokenizer> {
+    // Catalogue keys may carry a provider prefix such as "azure/gpt-4o".
+    let name = model.rsplit('/').next().unwrap_or(model);
+    get_tokenizer(name).or_else(|| name.starts_with("gpt-5").then_some(Tokenizer::O200kBase))
+}
+
+/// Name of the tokenizer used to count tokens for `model`. Models using
+/// another or no known OpenAI tokenizer (such as `p50k_base` models) are
+/// approximated with `cl100k_base`.
+pub fn tokenizer_name_for_model(model: &str) -> &'static str {
+    match model_tokenizer(model) {
+        Some(Tokenizer::O200kBase) => "o200k_base",
+        Some(Tokenizer::Cl100kBase) => "cl100k_base",
+        _ => "cl100k_base (approx.)",
+    }
+}
+
+pub fn estimate_tokens_for_model(text: &str, model: &str) -> usize {
+    match model_tokenizer(model) {
+        Some(Tokenizer::O200kBase) => O200K_TOKENIZER.encode_with_special_tokens(text).len(),
+        _ => estimate_tokens(text),
+    }
+}
+
+/// Counts a prompt/completion pair for many models, tokenizing the text only
+/// once per distinct tokenizer.
+pub struct TokenCounter<'a> {
+    input: &'a str,
+    output: &'a str,
+    counts: HashMap<&'static str, (usize, usize)>,
+}
+
+impl<'a> TokenCounter<'a> {
+    pub fn new(input: &'a str, output: &'a str) -> Self {
+        Self {
+            input,
+            output,
+            counts: HashMap::new(),
+        }
+    }
+
+    /// Input and output token counts for `model`.
+    pub fn counts(&mut self, model: &str) -> (usize, usize) {
+        let (input, output) = (self.input, self.output);
+        *self
+            .counts
+            .entry(tokenizer_name_for_model(model))
+            .or_insert_with(|| {
+                (
+                    estimate_tokens_for_model(input, model),
+                    estimate_tokens_for_model(output, model),
+                )
+            })
+    }
+}
+
+/// Longest characters a UTF-8 decode may need to back off: a character is at
+/// most four bytes, so a cut inside one is at most three tokens from its edge.
+const MAX_SPLIT_TOKENS: usize = 3;
+
+/// Decodes `tokens[start..end]`, moving `start` forward and `end` back by
+/// the few tokens needed for both to fall on character boundaries. Returns
+/// the text with the token range actually decoded.
+fn decode_span(tokens: &[Rank], start: usize, end: usize) -> (String, usize, usize) {
+    for from in start..=(start + MAX_SPLIT_TOKENS).min(end) {
+        for to in (end.saturating_sub(MAX_SPLIT_TOKENS).max(from)..=end).rev() {
+            if let Ok(text) = TOKENIZER.decode(tokens[from..to].to_vec()) {
+                return (text, from, to);
+            }
+        }
+    }
+    (String::new(), start, start)
+}
+
+/// Cuts `text` down to at most `max_tokens` tokens, ending on a character
+/// boundary. Text that already fits is returned unchanged.
+pub fn truncate_to_tokens(text: &str, max_tokens: usize) -> String {
+    let tokens = TOKENIZER.encode_with_special_tokens(text);
+    if tokens.len() <= max_tokens {
+        return text.to_string();
+    }
+    decode_span(&tokens, 0, max_tokens).0
+}
+
+/// Splits `text` into consecutive windows of `chunk_size` tokens, each one
+/// starting `overlap` tokens before the end of the previous window. Window
+/// edges move by a token or two where they would split a character. The
+/// overlap must be smaller than the chunk size.
+pub fn chunk_by_tokens(
+    text: &str,
+    chunk_size: usize,
+    overlap: usize,
+) -> Result<Vec<TokenChunk>, String> {
+    if overlap >= chunk_size {
+        return Err(format!(
+            "The overlap ({} tokens) must be smaller than the chunk size ({} tokens)",
+            overlap, chunk_size
+        ));
+    }
+    let tokens = TOKENIZER.encode_with_special_tokens(text);
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < tokens.len() {
+        let (text, from, to) = decode_span(&tokens, start, (start + chunk_size).min(tokens.len()));
+        chunks.push(TokenChunk {
+            index: chunks.len(),
+            start_token: from,
+            end_token: to,
+            token_count: to - from,
+            text,
+        });
+        if to == tokens.len() {
+            break;
+        }
+        start = to.saturating_sub(overlap).max(start + 1);
+    }
+    Ok(chunks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_keeps_text_that_fits() {
+        assert_eq!(truncate_to_tokens("hello world", 10), "hello world");
+    }
+
+    #[test]
+    fn truncate_cuts_to_the_token_budget() {
+        let text = "one two three four five six seven eight";
+        let truncated = truncate_to_tokens(text, 3);
+        assert_eq!(truncated, "one two three");
+        assert_eq!(estimate_tokens(&truncated), 3);
+    }
+
+    #[test]
+    fn truncate_never_splits_a_character() {
+        // Each emoji spans several tokens, so most cuts land inside one.
+        let text = "🦀🦀🦀🦀🦀🦀🦀🦀";
+        for max_tokens in 1..estimate_tokens(text) {
+            let truncated = truncate_to_tokens(text, max_tokens);
+            assert!(!truncated.contains('\u{FFFD}'));
+            assert!(text.starts_with(&truncated));
+            assert!(estimate_tokens(&truncated) <= max_tokens);
+        }
+    }
+
+    #[test]
+    fn chunks_overlap_by_the_requested_tokens() {
+        let text = "a b c d e f g h i j";
+        let tokens = estimate_tokens(text);
+        let chunks = chunk_by_tokens(text, 4, 1).unwrap();
+        assert_eq!(chunks[0].start_token, 0);
+        assert_eq!(chunks[0].end_token, 4);
+        assert_eq!(chunks[1].start_token, 3);
+        assert_eq!(chunks.last().unwrap().end_token, tokens);
+        assert!(chunks.iter().all(|c| c.token_count <= 4));
+    }
+
+    #[test]
+    fn chunks_reject_overlap_not_smaller_than_size() {
+        assert!(chunk_by_tokens("a b c", 4, 4).is_err());
+        assert!(chunk_by_tokens("a b c", 0, 0).is_err());
+    }
+
+    #[test]
+    fn chunks_of_empty_text() {
+        assert_eq!(chunk_by_tokens("", 4, 1).unwrap(), Vec::new());
+    }
+
+    #[test]
+    fn chunks_never_split_a_character() {
+        let text = "🦀🦀🦀🦀🦀🦀🦀🦀";
+        let chunks = chunk_by_tokens(text, 5, 2).unwrap();
+        assert!(chunks.iter().all(|c| !c.text.contains('\u{FFFD}')));
+        assert_eq!(chunks.last().unwrap().end_token, estimate_tokens(text));
+    }
+
+    #[test]
+    fn tokenizer_names_match_the_encoding_used() {
+        assert_eq!(tokenizer_name_for_model("gpt-4o"), "o200k_base");
+        assert_eq!(tokenizer_name_for_model("azure/gpt-5-mini"), "o200k_base");
+        assert_eq!(tokenizer_name_for_model("gpt-4"), "cl100k_base");
+        assert_eq!(
+            tokenizer_name_for_model("text-davinci-003"),
+            "cl100k_base (approx.)"
+        );
+        assert_eq!(
+            tokenizer_name_for_model("claude-sonnet-4-5"),
+            "cl100k_base (approx.)"
+        );
+    }
+
+    #[test]
+    fn counter_reuses_counts_per_tokenizer() {
+        let mut counter = TokenCounter::new("hello world", "ok");
+        assert_eq!(counter.counts("gpt-4"), (2, 1));
+        assert_eq!(
+            counter.counts("gpt-4o").0,
+            estimate_tokens_for_model("hello world", "gpt-4o")
+        );
+    }
+}
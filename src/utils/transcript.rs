@@ -0,0 +1,287 @@
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+
+use crate::models::ModelPricing;
+use crate::utils::api_usage::{parse_usage_block, price_usage, ApiUsage};
+use crate::utils::chat::content_text;
+
+/// Characters of each user prompt kept to label its turn.
+const PROMPT_PREVIEW: usize = 120;
+
+/// Where transcripts keep the usage of an API call: Claude Code and similar
+/// tools nest the API message, Codex CLI logs token count events, and plain
+/// logs put the response at the top level.
+const USAGE_POINTERS: &[&str] = &[
+    "/message/usage",
+    "/payload/info/last_token_usage",
+    "/response/usage",
+    "/usage",
+    "/usageMetadata",
+];
+const MODEL_POINTERS: &[&str] = &[
+    "/message/model",
+    "/payload/model",
+    "/response/model",
+    "/model",
+    "/modelVersion",
+];
+/// Ids shared by the lines a single API call is split across.
+const CALL_ID_POINTERS: &[&str] = &["/message/id", "/requestId", "/request_id", "/response/id"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranscriptCall {
+    /// Index into `Transcript::prompts` of the turn the call belongs to.
+    pub turn: usize,
+    pub model: String,
+    pub usage: ApiUsage,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Transcript {
+    /// Preview of the user prompt that opened each turn.
+    pub prompts: Vec<String>,
+    pub calls: Vec<TranscriptCall>,
+    /// Lines that could not be read, as "Line N: reason".
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TurnCost {
+    pub prompt: String,
+    pub calls: usize,
+    pub input_tokens: usize,
+    pub cache_read_tokens: usize,
+    pub cache_write_tokens: usize,
+    /// Output including reasoning tokens.
+    pub output_tokens: usize,
+    pub cost: f64,
+    /// Session cost up to and including this turn.
+    pub cumulative_cost: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TranscriptCost {
+    pub turns: Vec<TurnCost>,
+    pub total_cost: f64,
+    /// Models missing from the catalogue, counted as $0.
+    pub unpriced_models: Vec<String>,
+}
+
+fn find<'a>(line: &'a Value, pointers: &[&str]) -> Option<&'a Value> {
+    pointers.iter().find_map(|pointer| line.pointer(pointer))
+}
+
+/// The text of a user prompt, or `None` for other lines, including the tool
+/// results that agent transcripts record as user messages.
+fn user_prompt(line: &Value) -> Option<String> {
+    let message = find(line, &["/message", "/payload"]).unwrap_or(line);
+    let role = message
+        .get("role")
+        .or_else(|| line.get("type"))
+        .and_then(Value::as_str);
+    let is_user_event = message.get("type").and_then(Value::as_str) == Some("user_message");
+    if role != Some("user") && !is_user_event {
+        return None;
+    }
+    let content = message.get("content").or_else(|| message.get("message"))?;
+    let only_tool_results = content.as_array().is_some_and(|parts| {
+        !parts.is_empty()
+            && parts
+                .iter()
+                .all(|part| part.get("type").and_then(Value::as_str) == Some("tool_result"))
+    });
+    if only_tool_results {
+        return None;
+    }
+    let text = content_text(content);
+    Some(text.trim().chars().take(PROMPT_PREVIEW).collect())
+}
+
+/// Walks a JSONL session transcript into its API calls, grouped into turns
+/// that each start at a user prompt. Lines repeating an earlier call's id,
+/// as streamed content blocks do, replace that call rather than adding one.
+pub fn parse_transcript(text: &str) -> Transcript {
+    let mut transcript = Transcript::default();
+    let mut model = String::new();
+    let mut call_ids: HashMap<String, usize> = HashMap::new();
+
+    for (index, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let line: Value = match serde_json::from_str(line) {
+            Ok(line) => line,
+            Err(e) => {
+                transcript
+                    .errors
+                    .push(format!("Line {}: invalid JSON ({})", index + 1, e));
+                continue;
+            }
+        };
+        if let Some(name) = find(&line, MODEL_POINTERS).and_then(Value::as_str) {
+            model = name.to_string();
+        }
+        if let Some(prompt) = user_prompt(&line) {
+            // Tools that log a prompt twice should not open an empty turn.
+            let turn_has_calls = transcript
+                .calls
+                .last()
+                .is_some_and(|call| call.turn + 1 == transcript.prompts.len());
+            if turn_has_calls || transcript.prompts.is_empty() {
+                transcript.prompts.push(prompt);
+            } else if let Some(last) = transcript.prompts.last_mut() {
+                if last.is_empty() {
+                    *last = prompt;
+                }
+            }
+            continue;
+        }
+        let Some(block) = find(&line, USAGE_POINTERS) else {
+            continue;
+        };
+        let Some((_, usage)) = parse_usage_block(block) else {
+            transcript
+                .errors
+                .push(format!("Line {}: unrecognised usage block", index + 1));
+            continue;
+        };
+        if model.is_empty() {
+            transcript.errors.push(format!(
+                "Line {}: usage before any model is named",
+                index + 1
+            ));
+            continue;
+        }
+        if transcript.prompts.is_empty() {
+            transcript.prompts.push(String::new());
+        }
+        let call = TranscriptCall {
+            turn: transcript.prompts.len() - 1,
+            model: model.clone(),
+            usage,
+        };
+        let id = find(&line, CALL_ID_POINTERS)
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        match id.as_ref().and_then(|id| call_ids.get(id)) {
+            Some(&existing) => transcript.calls[existing] = call,
+            None => {
+                if let Some(id) = id {
+                    call_ids.insert(id, transcript.calls.len());
+                }
+                transcript.calls.push(call);
+            }
+        }
+    }
+    transcript
+}
+
+/// Prices each call with its model's catalogue entry and totals them per turn.
+pub fn price_transcript(
+    transcript: &Transcript,
+    prices: &HashMap<String, ModelPricing>,
+) -> TranscriptCost {
+    let mut turns: Vec<TurnCost> = transcript
+        .prompts
+        .iter()
+        .map(|prompt| TurnCost {
+            prompt: prompt.clone(),
+            ..TurnCost::default()
+        })
+        .collect();
+    let mut unpriced = BTreeSet::new();
+
+    for call in &transcript.calls {
+        let usage = &call.usage;
+        let turn = &mut turns[call.turn];
+        turn.calls += 1;
+        turn.input_tokens += usage.input_tokens + usage.audio_input_tokens;
+        turn.cache_read_tokens += usage.cached_tokens;
+        turn.cache_write_tokens += usage.cache_write_tokens + usage.cache_write_1h_tokens;
+        turn.output_tokens +=
+            usage.output_tokens + usage.reasoning_tokens + usage.audio_output_tokens;
+        match price_usage(&call.model, usage, prices) {
+            Ok(cost) => turn.cost += cost.total,
+            Err(_) => {
+                unpriced.insert(call.model.clone());
+            }
+        }
+    }
+
+    let mut total_cost = 0.0;
+    for turn in &mut turns {
+        total_cost += turn.cost;
+        turn.cumulative_cost = total_cost;
+    }
+    TranscriptCost {
+        turns,
+        total_cost,
+        unpriced_models: unpriced.into_iter().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn transcript() -> String {
+        [
+            json!({"type": "user", "message": {"role": "user", "content": "Fix the bug"}}),
+            json!({"type": "assistant", "message": {"id": "msg_1", "model": "claude-sonnet-4-5", "usage": {"input_tokens": 100, "output_tokens": 1}}}),
+            // A streamed second block of the same call replaces the first.
+            json!({"type": "assistant", "message": {"id": "msg_1", "model": "claude-sonnet-4-5", "usage": {"input_tokens": 100, "cache_read_input_tokens": 1000, "output_tokens": 50}}}),
+            json!({"type": "user", "message": {"role": "user", "content": [{"type": "tool_result", "content": "ok"}]}}),
+            json!({"type": "assistant", "message": {"id": "msg_2", "model": "claude-sonnet-4-5", "usage": {"input_tokens": 10, "output_tokens": 20}}}),
+            json!({"type": "user", "message": {"role": "user", "content": "Now add a test"}}),
+            json!({"type": "assistant", "message": {"id": "msg_3", "model": "local-model", "usage": {"input_tokens": 5, "output_tokens": 5}}}),
+        ]
+        .iter()
+        .map(Value::to_string)
+        .chain(["not json".to_string()])
+        .collect::<Vec<_>>()
+        .join("\n")
+    }
+
+    #[test]
+    fn groups_calls_into_turns() {
+        let transcript = parse_transcript(&transcript());
+        assert_eq!(transcript.prompts, ["Fix the bug", "Now add a test"]);
+        let turns: Vec<usize> = transcript.calls.iter().map(|call| call.turn).collect();
+        assert_eq!(turns, [0, 0, 1]);
+        assert_eq!(transcript.calls[0].usage.cached_tokens, 1_000);
+        assert_eq!(transcript.errors.len(), 1);
+        assert!(transcript.errors[0].starts_with("Line 8:"));
+    }
+
+    #[test]
+    fn prices_turns_cumulatively() {
+        let prices: HashMap<String, ModelPricing> = serde_json::from_value(json!({
+            "claude-sonnet-4-5": {
+                "input_cost_per_token": 3e-6,
+                "output_cost_per_token": 1.5e-5,
+                "cache_read_input_token_cost": 3e-7,
+            },
+        }))
+        .unwrap();
+        let cost = price_transcript(&parse_transcript(&transcript()), &prices);
+        let first = 110.0 * 3e-6 + 1_000.0 * 3e-7 + 70.0 * 1.5e-5;
+        assert_eq!(cost.turns[0].calls, 2);
+        assert_eq!(cost.turns[0].output_tokens, 70);
+        assert!((cost.turns[0].cost - first).abs() < 1e-12);
+        assert!((cost.turns[1].cumulative_cost - first).abs() < 1e-12);
+        assert_eq!(cost.total_cost, cost.turns[1].cumulative_cost);
+        assert_eq!(cost.unpriced_models, ["local-model"]);
+    }
+
+    #[test]
+    fn usage_needs_a_model() {
+        let line = json!({"usage": {"prompt_tokens": 1, "completion_tokens": 1}}).to_string();
+        let transcript = parse_transcript(&line);
+        assert!(transcript.calls.is_empty());
+        assert_eq!(
+            transcript.errors,
+            ["Line 1: usage before any model is named"]
+        );
+    }
+}
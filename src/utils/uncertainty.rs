@@ -0,0 +1,288 @@
+use crate::models::ModelPricing;
+use crate::utils::projection::TrafficPlan;
+
+/// Number of Monte Carlo samples drawn per estimate.
+pub const SAMPLES: usize = 10_000;
+const SEED: u64 = 0x5EED_0FC0_5EED_0FC0;
+/// One-sided 95% quantile of the standard normal distribution.
+const Z_95: f64 = 1.644_853_6;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputDistribution {
+    /// Triangular distribution between `min` and `max` peaking at `typical`.
+    Range { min: f64, typical: f64, max: f64 },
+    /// Normal distribution; negative draws count as zero tokens.
+    Normal { mean: f64, stddev: f64 },
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Percentiles {
+    pub mean: f64,
+    pub p50: f64,
+    pub p95: f64,
+    pub worst: f64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct UncertaintyEstimate {
+    pub output_tokens: Percentiles,
+    pub cost_per_request: Percentiles,
+    /// Standard deviation of the sampled per-request cost.
+    pub cost_stddev: f64,
+    /// Output length cap applied to every sample, if the model lists one.
+    pub output_cap: Option<usize>,
+    /// Share of samples that hit the output cap.
+    pub capped_share: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolumeCost {
+    pub label: &'static str,
+    pub requests: f64,
+    pub cost: Percentiles,
+}
+
+/// SplitMix64, seeded so the estimate stays stable between re-renders.
+struct Rng(u64);
+
+impl Rng {
+    fn next_f64(&mut self) -> f64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+impl OutputDistribution {
+    /// Longest output the distribution can produce, if it is bounded.
+    fn upper_bound(&self) -> Option<f64> {
+        match *self {
+            OutputDistribution::Range { min, max, .. } => Some(min.max(max).max(0.0)),
+            OutputDistribution::Normal { .. } => None,
+        }
+    }
+
+    fn sample(&self, rng: &mut Rng) -> f64 {
+        match *self {
+            OutputDistribution::Range { min, typical, max } => {
+                let (min, max) = (min.min(max), min.max(max));
+                let typical = typical.clamp(min, max);
+                if max <= min {
+                    return min;
+                }
+                let u = rng.next_f64();
+                let split = (typical - min) / (max - min);
+                if u < split {
+                    min + (u * (max - min) * (typical - min)).sqrt()
+                } else {
+                    max - ((1.0 - u) * (max - min) * (max - typical)).sqrt()
+                }
+            }
+            OutputDistribution::Normal { mean, stddev } => {
+                let u1 = rng.next_f64().max(f64::MIN_POSITIVE);
+                let u2 = rng.next_f64();
+                let z = (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos();
+                mean + stddev.max(0.0) * z
+            }
+        }
+    }
+}
+
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let index = ((sorted.len() - 1) as f64 * p).round() as usize;
+    sorted[index]
+}
+
+fn summarize(mut values: Vec<f64>) -> (Percentiles, f64) {
+    if values.is_empty() {
+        return (Percentiles::default(), 0.0);
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+    let summary = Percentiles {
+        mean,
+        p50: percentile(&values, 0.5),
+        p95: percentile(&values, 0.95),
+        worst: values[values.len() - 1],
+    };
+    (summary, variance.sqrt())
+}
+
+/// Samples output lengths from `distribution` and prices each one together
+/// with the fixed `input_tokens`.
+///
+/// The worst case is the distribution's upper bound, capped at the model's
+/// `max_output_tokens` since no response can be longer. An unbounded
+/// distribution on a model without a cap falls back to the longest sample.
+pub fn simulate_output_costs(
+    distribution: &OutputDistribution,
+    input_tokens: usize,
+    pricing: &ModelPricing,
+) -> UncertaintyEstimate {
+    let output_cap = pricing
+        .max_output_tokens
+        .filter(|max| *max > 0)
+        .map(|max| max as usize);
+    let mut rng = Rng(SEED);
+    let mut capped = 0;
+
+    let tokens: Vec<f64> = (0..SAMPLES)
+        .map(|_| {
+            let sample = distribution.sample(&mut rng).max(0.0).round() as usize;
+            match output_cap {
+                Some(cap) if sample >= cap => {
+                    capped += 1;
+                    cap as f64
+                }
+                _ => sample as f64,
+            }
+        })
+        .collect();
+    let costs: Vec<f64> = tokens
+        .iter()
+        .map(|&t| {
+            let (input, output) = pricing.request_cost(input_tokens, t as usize);
+            input + output
+        })
+        .collect();
+
+    let (mut output_tokens, _) = summarize(tokens);
+    let (mut cost_per_request, cost_stddev) = summarize(costs);
+    let worst_tokens = match (distribution.upper_bound(), output_cap) {
+        (Some(bound), Some(cap)) => Some((bound.round() as usize).min(cap)),
+        (Some(bound), None) => Some(bound.round() as usize),
+        (None, cap) => cap,
+    };
+    if let Some(worst) = worst_tokens {
+        let (input, output) = pricing.request_cost(input_tokens, worst);
+        output_tokens.worst = worst as f64;
+        cost_per_request.worst = input + output;
+    }
+
+    UncertaintyEstimate {
+        output_tokens,
+        cost_per_request,
+        cost_stddev,
+        output_cap,
+        capped_share: capped as f64 / SAMPLES as f64,
+    }
+}
+
+/// Scales a per-request estimate to the daily, monthly and first-year volumes
+/// of `plan`.
+///
+/// Independent requests average out over a large volume, so the mean and
+/// percentiles use the normal approximation of a sum of `requests` samples.
+/// The worst case assumes every request hits the worst case.
+pub fn project_uncertainty(estimate: &UncertaintyEstimate, plan: &TrafficPlan) -> Vec<VolumeCost> {
+    let growth = 1.0 + plan.monthly_growth;
+    let annual_requests: f64 = (0..12)
+        .map(|month| plan.requests_per_month() * growth.powi(month))
+        .sum();
+    let per_request = estimate.cost_per_request;
+
+    [
+        ("Daily", plan.requests_per_day),
+        ("Monthly", plan.requests_per_month()),
+        ("Annual", annual_requests),
+    ]
+    .into_iter()
+    .map(|(label, requests)| {
+        let mean = per_request.mean * requests;
+        VolumeCost {
+            label,
+            requests,
+            cost: Percentiles {
+                mean,
+                p50: mean,
+                p95: mean + Z_95 * estimate.cost_stddev * requests.max(0.0).sqrt(),
+                worst: per_request.worst * requests,
+            },
+        }
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn pricing(max_output_tokens: Option<i64>) -> ModelPricing {
+        serde_json::from_value(json!({
+            "input_cost_per_token": 1e-6,
+            "output_cost_per_token": 2e-6,
+            "max_output_tokens": max_output_tokens,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn range_samples_stay_within_bounds() {
+        let range = OutputDistribution::Range {
+            min: 100.0,
+            typical: 200.0,
+            max: 1_000.0,
+        };
+        let estimate = simulate_output_costs(&range, 500, &pricing(None));
+        let tokens = estimate.output_tokens;
+        assert!(tokens.p50 >= 100.0 && tokens.p95 <= 1_000.0);
+        // The mean of a triangular distribution is (min + typical + max) / 3.
+        assert!((tokens.mean - 433.3).abs() < 10.0);
+        assert_eq!(tokens.worst, 1_000.0);
+        assert!((estimate.cost_per_request.worst - (500.0 * 1e-6 + 1_000.0 * 2e-6)).abs() < 1e-12);
+        assert_eq!(estimate.capped_share, 0.0);
+    }
+
+    #[test]
+    fn output_cap_limits_samples_and_worst_case() {
+        let normal = OutputDistribution::Normal {
+            mean: 1_000.0,
+            stddev: 200.0,
+        };
+        let estimate = simulate_output_costs(&normal, 0, &pricing(Some(1_000)));
+        assert_eq!(estimate.output_cap, Some(1_000));
+        assert_eq!(estimate.output_tokens.worst, 1_000.0);
+        assert!((estimate.capped_share - 0.5).abs() < 0.05);
+    }
+
+    #[test]
+    fn negative_draws_count_as_zero() {
+        let normal = OutputDistribution::Normal {
+            mean: -100.0,
+            stddev: 10.0,
+        };
+        let estimate = simulate_output_costs(&normal, 0, &pricing(None));
+        assert_eq!(estimate.output_tokens.worst, 0.0);
+        assert_eq!(estimate.cost_per_request.mean, 0.0);
+    }
+
+    #[test]
+    fn volumes_scale_the_per_request_estimate() {
+        let estimate = UncertaintyEstimate {
+            cost_per_request: Percentiles {
+                mean: 0.01,
+                p50: 0.01,
+                p95: 0.02,
+                worst: 0.05,
+            },
+            cost_stddev: 0.005,
+            ..UncertaintyEstimate::default()
+        };
+        let volumes = project_uncertainty(&estimate, &TrafficPlan::default());
+        let monthly = &volumes[1];
+        assert_eq!(monthly.label, "Monthly");
+        assert!((monthly.cost.mean - 300.0).abs() < 1e-9);
+        assert!((monthly.cost.worst - 1_500.0).abs() < 1e-9);
+        let spread = Z_95 * 0.005 * 30_000f64.sqrt();
+        assert!((monthly.cost.p95 - 300.0 - spread).abs() < 1e-9);
+    }
+}
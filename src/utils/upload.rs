@@ -0,0 +1,15 @@
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{File, FileList};
+
+/// Reads the whole contents of a user-selected file as UTF-8 text.
+pub async fn read_file_text(file: &File) -> Result<String, String> {
+    let text = JsFuture::from(file.text())
+        .await
+        .map_err(|e| format!("Failed to read {}: {:?}", file.name(), e))?;
+    text.as_string()
+        .ok_or_else(|| format!("{} is not a text file", file.name()))
+}
+
+pub fn file_list_to_vec(files: &FileList) -> Vec<File> {
+    (0..files.length()).filter_map(|i| files.get(i)).collect()
+}
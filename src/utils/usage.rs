@@ -0,0 +1,798 @@
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::models::ModelPricing;
+use crate::utils::date::{format_days, parse_days};
+
+const DATE_COLUMNS: &[&str] = &[
+    "date",
+    "day",
+    "usage_date_utc",
+    "usage_date",
+    "start_time_iso",
+    "start_time",
+    "starttime",
+    "timestamp",
+    "created_at",
+];
+const MODEL_COLUMNS: &[&str] = &["model", "model_version", "model_name", "model_id"];
+const KEY_COLUMNS: &[&str] = &["api_key_id", "api_key", "api_key_name", "key_name", "key"];
+const REQUEST_COLUMNS: &[&str] = &["num_model_requests", "num_requests", "requests"];
+const INPUT_COLUMNS: &[&str] = &[
+    "input_tokens",
+    "usage_input_tokens_no_cache",
+    "prompt_tokens",
+    "input",
+];
+/// Input columns that count cached tokens as well, in the OpenAI API's usage
+/// naming.
+const INCLUSIVE_INPUT_COLUMNS: &[&str] = &["prompt_tokens"];
+const CACHED_COLUMNS: &[&str] = &[
+    "input_cached_tokens",
+    "usage_input_tokens_cache_read",
+    "cache_read_input_tokens",
+    "cached_tokens",
+];
+/// Every matching column is summed, since Anthropic splits cache writes by TTL.
+const CACHE_WRITE_COLUMNS: &[&str] = &[
+    "usage_input_tokens_cache_write_5m",
+    "usage_input_tokens_cache_write_1h",
+    "cache_creation_input_tokens",
+    "cache_write_tokens",
+];
+const OUTPUT_COLUMNS: &[&str] = &[
+    "output_tokens",
+    "usage_output_tokens",
+    "completion_tokens",
+    "output",
+];
+const BATCH_COLUMNS: &[&str] = &["batch"];
+const SPEND_COLUMNS: &[&str] = &["spend", "cost"];
+const USER_COLUMNS: &[&str] = &["user", "end_user", "user_id"];
+const TEAM_ALIAS_COLUMNS: &[&str] = &["team_alias"];
+const TEAM_COLUMNS: &[&str] = &["team_id", "team"];
+const TAG_COLUMNS: &[&str] = &["request_tags", "tags"];
+const METADATA_COLUMNS: &[&str] = &["metadata"];
+/// LiteLLM marks requests answered from its own response cache, which cost nothing.
+const PROXY_CACHE_HIT_COLUMNS: &[&str] = &["cache_hit"];
+const KNOWN_COLUMNS: &[&[&str]] = &[
+    DATE_COLUMNS,
+    MODEL_COLUMNS,
+    KEY_COLUMNS,
+    REQUEST_COLUMNS,
+    INPUT_COLUMNS,
+    CACHED_COLUMNS,
+    CACHE_WRITE_COLUMNS,
+    OUTPUT_COLUMNS,
+    BATCH_COLUMNS,
+    SPEND_COLUMNS,
+    USER_COLUMNS,
+    TEAM_ALIAS_COLUMNS,
+    TEAM_COLUMNS,
+    TAG_COLUMNS,
+    METADATA_COLUMNS,
+    PROXY_CACHE_HIT_COLUMNS,
+];
+/// Longer column values are payloads such as prompts rather than attributes,
+/// so they are left out of a record's metadata.
+const MAX_METADATA_VALUE: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsageFormat {
+    LiteLlm,
+    OpenAi,
+    Anthropic,
+    Generic,
+}
+
+impl UsageFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            UsageFormat::LiteLlm => "LiteLLM spend logs",
+            UsageFormat::OpenAi => "OpenAI usage",
+            UsageFormat::Anthropic => "Anthropic console",
+            UsageFormat::Generic => "Generic",
+        }
+    }
+
+    fn detect(columns: &[String]) -> Self {
+        let has = |name: &str| columns.iter().any(|c| c == name);
+        if has("spend") && (has("request_id") || has("call_type") || has("model_group")) {
+            UsageFormat::LiteLlm
+        } else if has("num_model_requests") || has("api_key_id") || has("input_cached_tokens") {
+            UsageFormat::OpenAi
+        } else if has("usage_input_tokens_no_cache") || has("model_version") {
+            UsageFormat::Anthropic
+        } else {
+            UsageFormat::Generic
+        }
+    }
+}
+
+/// One row of provider usage. `input_tokens` excludes cached and cache-write
+/// tokens, which are priced separately.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageRecord {
+    pub date: Option<String>,
+    pub model: String,
+    pub api_key: Option<String>,
+    pub requests: usize,
+    pub input_tokens: usize,
+    pub cached_tokens: usize,
+    pub cache_write_tokens: usize,
+    pub output_tokens: usize,
+    pub batch: bool,
+    pub proxy_cache_hit: bool,
+    pub user: Option<String>,
+    pub team: Option<String>,
+    pub tags: Vec<String>,
+    /// Scalar request metadata, such as LiteLLM's `metadata` column (nested
+    /// objects flattened to dotted keys) and any other columns of the export.
+    pub metadata: BTreeMap<String, String>,
+    /// Cost recorded by the source, e.g. LiteLLM's `spend`.
+    pub logged_cost: Option<f64>,
+}
+
+impl UsageRecord {
+    /// All prompt tokens, cached or not.
+    pub fn total_input_tokens(&self) -> usize {
+        self.input_tokens + self.cached_tokens + self.cache_write_tokens
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageImport {
+    pub format: UsageFormat,
+    pub records: Vec<UsageRecord>,
+    /// Rows that could not be read, as "Row N: reason".
+    pub errors: Vec<String>,
+}
+
+fn normalize_column(name: &str) -> String {
+    name.trim().to_lowercase().replace([' ', '-', '.'], "_")
+}
+
+fn parse_count(value: &str) -> Option<usize> {
+    let cleaned = value.trim().replace(',', "");
+    if cleaned.is_empty() {
+        return Some(0);
+    }
+    cleaned
+        .parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite() && *v >= 0.0)
+        .map(|v| v.round() as usize)
+}
+
+/// Whether `value` starts with a `YYYY-MM-DD` date.
+fn starts_with_iso_date(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    bytes.len() >= 10
+        && bytes[..10].iter().enumerate().all(|(i, b)| match i {
+            4 | 7 => *b == b'-',
+            _ => b.is_ascii_digit(),
+        })
+}
+
+/// Normalizes ISO dates and timestamps, compact `YYYYMMDD` dates, and Unix
+/// seconds or milliseconds, to `YYYY-MM-DD`.
+pub fn parse_date(value: &str) -> Option<String> {
+    let value = value.trim();
+    if starts_with_iso_date(value) {
+        return Some(value[..10].to_string());
+    }
+    if value.len() == 8 && value.bytes().all(|b| b.is_ascii_digit()) {
+        let date = format!("{}-{}-{}", &value[..4], &value[4..6], &value[6..]);
+        if parse_days(&date).is_some() {
+            return Some(date);
+        }
+    }
+    let seconds = value.parse::<f64>().ok().filter(|v| v.is_finite())?;
+    let seconds = if seconds > 1e12 {
+        seconds / 1000.0
+    } else {
+        seconds
+    };
+    Some(format_days((seconds / 86_400.0).floor() as i64))
+}
+
+fn parse_flag(value: &str) -> bool {
+    value.eq_ignore_ascii_case("true") || value == "1"
+}
+
+/// Scalar fields of a JSON object string, with arrays joined by commas and
+/// nested objects flattened to `parent.child` keys.
+fn parse_metadata(value: &str) -> BTreeMap<String, String> {
+    let mut metadata = BTreeMap::new();
+    if let Ok(Value::Object(object)) = serde_json::from_str::<Value>(value) {
+        flatten_metadata("", object, &mut metadata);
+    }
+    metadata
+}
+
+fn flatten_metadata(
+    prefix: &str,
+    object: serde_json::Map<String, Value>,
+    metadata: &mut BTreeMap<String, String>,
+) {
+    for (key, value) in object {
+        let key = format!("{}{}", prefix, key);
+        let value = match value {
+            Value::String(s) => s,
+            Value::Number(n) => n.to_string(),
+            Value::Bool(b) => b.to_string(),
+            Value::Array(items) => items
+                .iter()
+                .filter_map(|item| item.as_str())
+                .collect::<Vec<_>>()
+                .join(","),
+            Value::Object(nested) => {
+                flatten_metadata(&format!("{}.", key), nested, metadata);
+                continue;
+            }
+            Value::Null => continue,
+        };
+        if !value.is_empty() {
+            metadata.insert(key, value);
+        }
+    }
+}
+
+/// Tags given as a JSON array or a comma-separated list.
+fn parse_tags(value: &str) -> Vec<String> {
+    let tags: Vec<String> = match serde_json::from_str::<Vec<String>>(value) {
+        Ok(tags) => tags,
+        Err(_) => value.split(',').map(str::to_string).collect(),
+    };
+    tags.into_iter()
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
+/// Builds a record from a row of normalized column names to values.
+fn record_from_row(
+    row: &HashMap<String, String>,
+    format: UsageFormat,
+) -> Result<UsageRecord, String> {
+    let field = |aliases: &[&str]| {
+        aliases
+            .iter()
+            .find_map(|alias| row.get(*alias))
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+    };
+    let count = |aliases: &[&str], name: &str| -> Result<usize, String> {
+        match field(aliases) {
+            Some(value) => {
+                parse_count(value).ok_or_else(|| format!("invalid {} \"{}\"", name, value))
+            }
+            None => Ok(0),
+        }
+    };
+
+    let model = field(MODEL_COLUMNS).ok_or("missing model")?.to_string();
+    let cached_tokens = count(CACHED_COLUMNS, "cached tokens")?;
+    let mut input_tokens = count(INPUT_COLUMNS, "input tokens")?;
+    let input_column = INPUT_COLUMNS
+        .iter()
+        .find(|alias| field(&[**alias]).is_some());
+    // OpenAI, and LiteLLM following it, report cached tokens as part of the
+    // input tokens.
+    if matches!(format, UsageFormat::OpenAi | UsageFormat::LiteLlm)
+        || input_column.is_some_and(|column| INCLUSIVE_INPUT_COLUMNS.contains(column))
+    {
+        input_tokens = input_tokens.saturating_sub(cached_tokens);
+    }
+    let mut cache_write_tokens = 0;
+    for column in CACHE_WRITE_COLUMNS {
+        if let Some(value) = row.get(*column) {
+            cache_write_tokens += parse_count(value)
+                .ok_or_else(|| format!("invalid cache write tokens \"{}\"", value))?;
+        }
+    }
+    let mut metadata = field(METADATA_COLUMNS)
+        .map(parse_metadata)
+        .unwrap_or_default();
+    for (column, value) in row {
+        let value = value.trim();
+        let known = KNOWN_COLUMNS
+            .iter()
+            .any(|aliases| aliases.contains(&column.as_str()));
+        if !known && !value.is_empty() && value.len() <= MAX_METADATA_VALUE {
+            metadata
+                .entry(column.clone())
+                .or_insert_with(|| value.to_string());
+        }
+    }
+    let from_metadata = |keys: &[&str]| keys.iter().find_map(|key| metadata.get(*key).cloned());
+    let mut tags = field(TAG_COLUMNS).map(parse_tags).unwrap_or_default();
+    if tags.is_empty() {
+        tags = metadata
+            .get("tags")
+            .map(|t| parse_tags(t))
+            .unwrap_or_default();
+    }
+    let logged_cost = match field(SPEND_COLUMNS) {
+        Some(value) => Some(
+            value
+                .replace(['$', ','], "")
+                .parse::<f64>()
+                .map_err(|_| format!("invalid spend \"{}\"", value))?,
+        ),
+        None => None,
+    };
+    let requests = match field(REQUEST_COLUMNS) {
+        Some(value) => {
+            parse_count(value).ok_or_else(|| format!("invalid requests \"{}\"", value))?
+        }
+        None => 1,
+    };
+
+    Ok(UsageRecord {
+        date: field(DATE_COLUMNS).and_then(parse_date),
+        model,
+        api_key: from_metadata(&["user_api_key_alias"])
+            .or_else(|| field(KEY_COLUMNS).map(str::to_string)),
+        requests,
+        input_tokens,
+        cached_tokens,
+        cache_write_tokens,
+        output_tokens: count(OUTPUT_COLUMNS, "output tokens")?,
+        batch: field(BATCH_COLUMNS).is_some_and(parse_flag),
+        proxy_cache_hit: field(PROXY_CACHE_HIT_COLUMNS).is_some_and(parse_flag),
+        user: field(USER_COLUMNS)
+            .map(str::to_string)
+            .or_else(|| from_metadata(&["user_api_key_user_id", "user_api_key_end_user_id"])),
+        // Prefer readable team aliases over team IDs wherever they come from.
+        team: field(TEAM_ALIAS_COLUMNS)
+            .map(str::to_string)
+            .or_else(|| from_metadata(&["user_api_key_team_alias"]))
+            .or_else(|| field(TEAM_COLUMNS).map(str::to_string))
+            .or_else(|| from_metadata(&["user_api_key_team_id"])),
+        tags,
+        logged_cost,
+        metadata,
+    })
+}
+
+fn import_rows(rows: Vec<HashMap<String, String>>) -> UsageImport {
+    let mut columns: Vec<String> = rows.iter().flat_map(|row| row.keys().cloned()).collect();
+    columns.sort();
+    columns.dedup();
+    let format = UsageFormat::detect(&columns);
+
+    let mut records = Vec::new();
+    let mut errors = Vec::new();
+    for (index, row) in rows.iter().enumerate() {
+        match record_from_row(row, format) {
+            Ok(record) => records.push(record),
+            Err(e) => errors.push(format!("Row {}: {}", index + 1, e)),
+        }
+    }
+    UsageImport {
+        format,
+        records,
+        errors,
+    }
+}
+
+pub fn parse_usage_csv(text: &str) -> Result<UsageImport, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(text.as_bytes());
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| format!("Invalid CSV header: {}", e))?
+        .iter()
+        .map(normalize_column)
+        .collect();
+
+    let mut rows = Vec::new();
+    for result in reader.records() {
+        let record = result.map_err(|e| format!("Invalid CSV: {}", e))?;
+        rows.push(
+            headers
+                .iter()
+                .cloned()
+                .zip(record.iter().map(str::to_string))
+                .collect(),
+        );
+    }
+    Ok(import_rows(rows))
+}
+
+fn json_row(object: &serde_json::Map<String, Value>) -> HashMap<String, String> {
+    object
+        .iter()
+        .filter_map(|(key, value)| {
+            let value = match value {
+                Value::String(s) => s.clone(),
+                Value::Number(n) => n.to_string(),
+                Value::Bool(b) => b.to_string(),
+                Value::Array(_) | Value::Object(_) => value.to_string(),
+                Value::Null => return None,
+            };
+            Some((normalize_column(key), value))
+        })
+        .collect()
+}
+
+/// Reads an OpenAI usage API response (buckets of results) or a plain array of
+/// row objects, optionally wrapped in a `data` field.
+pub fn parse_usage_json(text: &str) -> Result<UsageImport, String> {
+    let value: Value = serde_json::from_str(text).map_err(|e| format!("Invalid JSON: {}", e))?;
+    let items = match &value {
+        Value::Array(items) => items,
+        Value::Object(object) => object
+            .get("data")
+            .and_then(Value::as_array)
+            .ok_or("Expected an array of rows or an object with a \"data\" array")?,
+        _ => return Err("Expected an array of rows or an object with a \"data\" array".into()),
+    };
+
+    let mut rows = Vec::new();
+    for item in items {
+        let Some(object) = item.as_object() else {
+            continue;
+        };
+        match object.get("results").and_then(Value::as_array) {
+            Some(results) => {
+                let start = object.get("start_time").map(|v| v.to_string());
+                for result in results.iter().filter_map(Value::as_object) {
+                    let mut row = json_row(result);
+                    if let Some(start) = &start {
+                        row.entry("start_time".to_string())
+                            .or_insert_with(|| start.clone());
+                    }
+                    rows.push(row);
+                }
+            }
+            None => rows.push(json_row(object)),
+        }
+    }
+    Ok(import_rows(rows))
+}
+
+/// Parses a usage export as JSON or CSV, going by the file name and falling
+/// back to the first character of the contents.
+pub fn parse_usage_file(name: &str, text: &str) -> Result<UsageImport, String> {
+    let is_json =
+        name.to_lowercase().ends_with(".json") || text.trim_start().starts_with(['[', '{']);
+    if is_json {
+        parse_usage_json(text)
+    } else {
+        parse_usage_csv(text)
+    }
+}
+
+/// Strips an Anthropic-style "-20250929" or OpenAI-style "-2024-08-06" suffix.
+fn strip_date_suffix(name: &str) -> Option<&str> {
+    if let Some((base, suffix)) = name.rsplit_once('-') {
+        if suffix.len() == 8 && suffix.bytes().all(|b| b.is_ascii_digit()) {
+            return Some(base);
+        }
+    }
+    let split = name.len().checked_sub(11)?;
+    let base = name.get(..split)?;
+    let suffix = name.get(split..)?;
+    (suffix.starts_with('-') && starts_with_iso_date(&suffix[1..])).then_some(base)
+}
+
+/// Groups model versions: the model name without a provider prefix or
+/// trailing date stamp, e.g. "claude-sonnet-4-5" for
+/// "anthropic/claude-sonnet-4-5-20250929".
+pub fn model_family(model: &str) -> String {
+    let bare = model.rsplit('/').next().unwrap_or(model);
+    strip_date_suffix(bare).unwrap_or(bare).to_lowercase()
+}
+
+/// Finds the catalogue entry for a model name from a usage export, trying the
+/// name as-is, without a provider prefix, and without a trailing date stamp.
+pub fn resolve_model(model: &str, prices: &HashMap<String, ModelPricing>) -> Option<String> {
+    let bare = model.rsplit('/').next().unwrap_or(model);
+    [Some(model), Some(bare), strip_date_suffix(bare)]
+        .into_iter()
+        .flatten()
+        .find(|candidate| prices.contains_key(*candidate))
+        .map(str::to_string)
+}
+
+/// Cost of a usage record. Batch rows use the batch input and output prices
+/// where the catalogue lists them; cache reads and writes fall back to the
+/// input price when the model has no cache prices. Proxy cache hits are free.
+pub fn record_cost(record: &UsageRecord, pricing: &ModelPricing) -> f64 {
+    let (input, output) = record_cost_split(record, pricing);
+    input + output
+}
+
+/// A record's cost split into the input side (including cache reads and
+/// writes) and the output side.
+pub fn record_cost_split(record: &UsageRecord, pricing: &ModelPricing) -> (f64, f64) {
+    if record.proxy_cache_hit {
+        return (0.0, 0.0);
+    }
+    let input_price = pricing.input_cost_per_token.unwrap_or(0.0);
+    let output_price = pricing.output_cost_per_token.unwrap_or(0.0);
+    let (input_price, output_price) = if record.batch {
+        (
+            pricing.input_cost_per_token_batches.unwrap_or(input_price),
+            pricing
+                .output_cost_per_token_batches
+                .unwrap_or(output_price),
+        )
+    } else {
+        (input_price, output_price)
+    };
+    let cache_read_price = pricing.cache_read_input_token_cost.unwrap_or(input_price);
+    let cache_write_price = pricing
+        .cache_creation_input_token_cost
+        .unwrap_or(input_price);
+
+    (
+        record.input_tokens as f64 * input_price
+            + record.cached_tokens as f64 * cache_read_price
+            + record.cache_write_tokens as f64 * cache_write_price,
+        record.output_tokens as f64 * output_price,
+    )
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpendRow {
+    pub label: String,
+    pub requests: usize,
+    pub input_tokens: usize,
+    pub output_tokens: usize,
+    pub cost: f64,
+    /// Sum of the source's own cost figures, if any record carried one.
+    pub logged_cost: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UsageSummary {
+    pub total_cost: f64,
+    pub total_requests: usize,
+    pub total_input_tokens: usize,
+    pub total_output_tokens: usize,
+    pub by_model: Vec<SpendRow>,
+    pub by_day: Vec<SpendRow>,
+    pub by_key: Vec<SpendRow>,
+    pub by_user: Vec<SpendRow>,
+    pub by_team: Vec<SpendRow>,
+    /// A record with several tags counts toward each of them.
+    pub by_tag: Vec<SpendRow>,
+    pub total_logged_cost: Option<f64>,
+    /// Models in the export that are not in the catalogue; their rows cost 0.
+    pub unpriced_models: Vec<String>,
+}
+
+/// Label used for records without a value for the grouped field.
+pub const UNKNOWN_LABEL: &str = "(unknown)";
+
+fn label_or_unknown(value: &Option<String>) -> String {
+    value.clone().unwrap_or_else(|| UNKNOWN_LABEL.to_string())
+}
+
+/// Groups priced records by `label`, most expensive first.
+pub fn group_spend<F>(records: &[UsageRecord], costs: &[f64], label: F) -> Vec<SpendRow>
+where
+    F: Fn(&UsageRecord) -> String,
+{
+    group_spend_multi(records, costs, |record| vec![label(record)])
+}
+
+/// Like [`group_spend`], but a record may belong to several groups at once.
+pub fn group_spend_multi<F>(records: &[UsageRecord], costs: &[f64], labels: F) -> Vec<SpendRow>
+where
+    F: Fn(&UsageRecord) -> Vec<String>,
+{
+    let mut groups: HashMap<String, SpendRow> = HashMap::new();
+    for (record, cost) in records.iter().zip(costs) {
+        for key in labels(record) {
+            let row = groups.entry(key.clone()).or_insert_with(|| SpendRow {
+                label: key,
+                ..SpendRow::default()
+            });
+            row.requests += record.requests;
+            row.input_tokens += record.total_input_tokens();
+            row.output_tokens += record.output_tokens;
+            row.cost += cost;
+            if let Some(logged) = record.logged_cost {
+                row.logged_cost = Some(row.logged_cost.unwrap_or(0.0) + logged);
+            }
+        }
+    }
+    let mut rows: Vec<SpendRow> = groups.into_values().collect();
+    rows.sort_by(|a, b| {
+        b.cost
+            .total_cmp(&a.cost)
+            .then_with(|| a.label.cmp(&b.label))
+    });
+    rows
+}
+
+/// Prices every record against the catalogue, with 0 for unknown models.
+pub fn price_records(records: &[UsageRecord], prices: &HashMap<String, ModelPricing>) -> Vec<f64> {
+    records
+        .iter()
+        .map(|record| {
+            resolve_model(&record.model, prices)
+                .and_then(|key| prices.get(&key))
+                .map(|pricing| record_cost(record, pricing))
+                .unwrap_or(0.0)
+        })
+        .collect()
+}
+
+pub fn summarize_usage(
+    records: &[UsageRecord],
+    prices: &HashMap<String, ModelPricing>,
+) -> UsageSummary {
+    let costs = price_records(records, prices);
+
+    let mut unpriced_models: Vec<String> = records
+        .iter()
+        .filter(|record| resolve_model(&record.model, prices).is_none())
+        .map(|record| record.model.clone())
+        .collect();
+    unpriced_models.sort();
+    unpriced_models.dedup();
+
+    let mut by_day = group_spend(records, &costs, |r| label_or_unknown(&r.date));
+    by_day.sort_by(|a, b| a.label.cmp(&b.label));
+
+    UsageSummary {
+        total_cost: costs.iter().sum(),
+        total_requests: records.iter().map(|r| r.requests).sum(),
+        total_input_tokens: records.iter().map(UsageRecord::total_input_tokens).sum(),
+        total_output_tokens: records.iter().map(|r| r.output_tokens).sum(),
+        by_model: group_spend(records, &costs, |r| r.model.clone()),
+        by_day,
+        by_key: group_spend(records, &costs, |r| label_or_unknown(&r.api_key)),
+        by_user: group_spend(records, &costs, |r| label_or_unknown(&r.user)),
+        by_team: group_spend(records, &costs, |r| label_or_unknown(&r.team)),
+        by_tag: group_spend_multi(records, &costs, |r| {
+            if r.tags.is_empty() {
+                vec![UNKNOWN_LABEL.to_string()]
+            } else {
+                r.tags.clone()
+            }
+        }),
+        total_logged_cost: records
+            .iter()
+            .filter_map(|r| r.logged_cost)
+            .fold(None, |total, cost| Some(total.unwrap_or(0.0) + cost)),
+        unpriced_models,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_dates_and_timestamps() {
+        assert_eq!(parse_date("2025-01-15").as_deref(), Some("2025-01-15"));
+        assert_eq!(
+            parse_date("2025-01-15T23:59:59Z").as_deref(),
+            Some("2025-01-15")
+        );
+        assert_eq!(parse_date("20250115").as_deref(), Some("2025-01-15"));
+        assert_eq!(parse_date("1736899200").as_deref(), Some("2025-01-15"));
+        assert_eq!(parse_date("1736899200000").as_deref(), Some("2025-01-15"));
+        assert_eq!(parse_date("yesterday"), None);
+    }
+
+    #[test]
+    fn inclusive_prompt_tokens_exclude_cached_tokens() {
+        let csv = "date,model,prompt_tokens,cached_tokens,completion_tokens\n\
+                   2025-01-15,gpt-4o,1000,400,50\n";
+        let import = parse_usage_csv(csv).unwrap();
+        assert_eq!(import.format, UsageFormat::Generic);
+        let record = &import.records[0];
+        assert_eq!((record.input_tokens, record.cached_tokens), (600, 400));
+        assert_eq!(record.total_input_tokens(), 1_000);
+        assert_eq!(record.output_tokens, 50);
+    }
+
+    #[test]
+    fn exclusive_input_columns_are_kept() {
+        let csv = "usage_date_utc,model_version,usage_input_tokens_no_cache,\
+                   usage_input_tokens_cache_read,usage_input_tokens_cache_write_5m,\
+                   usage_input_tokens_cache_write_1h,usage_output_tokens\n\
+                   2025-01-15,claude-sonnet-4-5-20250929,1000,400,10,20,50\n";
+        let import = parse_usage_csv(csv).unwrap();
+        assert_eq!(import.format, UsageFormat::Anthropic);
+        let record = &import.records[0];
+        assert_eq!(
+            (
+                record.input_tokens,
+                record.cached_tokens,
+                record.cache_write_tokens
+            ),
+            (1_000, 400, 30)
+        );
+    }
+
+    #[test]
+    fn reads_openai_usage_buckets() {
+        let text = json!({"data": [{
+            "start_time": 1736899200,
+            "results": [{"model": "gpt-4o-2024-08-06", "input_tokens": 1000,
+                         "input_cached_tokens": 400, "output_tokens": 50,
+                         "num_model_requests": 3, "batch": true}],
+        }]})
+        .to_string();
+        let import = parse_usage_file("usage.json", &text).unwrap();
+        assert_eq!(import.format, UsageFormat::OpenAi);
+        let record = &import.records[0];
+        assert_eq!(record.date.as_deref(), Some("2025-01-15"));
+        assert_eq!((record.input_tokens, record.cached_tokens), (600, 400));
+        assert_eq!(record.requests, 3);
+        assert!(record.batch);
+    }
+
+    #[test]
+    fn reports_unreadable_rows() {
+        let csv = "model,input_tokens\n,5\ngpt-4o,lots\n";
+        let import = parse_usage_csv(csv).unwrap();
+        assert!(import.records.is_empty());
+        assert_eq!(import.errors.len(), 2);
+    }
+
+    #[test]
+    fn resolves_prefixed_and_dated_models() {
+        let prices: HashMap<String, ModelPricing> =
+            serde_json::from_value(json!({"gpt-4o": {}, "claude-sonnet-4-5": {}})).unwrap();
+        assert_eq!(
+            resolve_model("openai/gpt-4o-2024-08-06", &prices).as_deref(),
+            Some("gpt-4o")
+        );
+        assert_eq!(
+            resolve_model("claude-sonnet-4-5-20250929", &prices).as_deref(),
+            Some("claude-sonnet-4-5")
+        );
+        assert_eq!(resolve_model("llama3", &prices), None);
+        assert_eq!(model_family("azure/gpt-4o-2024-08-06"), "gpt-4o");
+    }
+
+    #[test]
+    fn prices_cache_reads_writes_and_batch() {
+        let pricing: ModelPricing = serde_json::from_value(json!({
+            "input_cost_per_token": 1e-6,
+            "output_cost_per_token": 4e-6,
+            "cache_read_input_token_cost": 1e-7,
+            "cache_creation_input_token_cost": 1.25e-6,
+            "input_cost_per_token_batches": 5e-7,
+            "output_cost_per_token_batches": 2e-6,
+        }))
+        .unwrap();
+        let csv = "model,input_tokens,cache_read_input_tokens,cache_creation_input_tokens,output_tokens,batch\n\
+                   m,1000,1000,1000,1000,false\n\
+                   m,1000,0,0,1000,true\n";
+        let records = parse_usage_csv(csv).unwrap().records;
+        let (input, output) = record_cost_split(&records[0], &pricing);
+        assert!((input - (1e-3 + 1e-4 + 1.25e-3)).abs() < 1e-12);
+        assert!((output - 4e-3).abs() < 1e-12);
+        assert!((record_cost(&records[1], &pricing) - 2.5e-3).abs() < 1e-12);
+    }
+
+    #[test]
+    fn litellm_input_tokens_include_cached_tokens() {
+        let csv = "request_id,call_type,spend,model,input_tokens,cache_read_input_tokens,\
+                   output_tokens,cache_hit,metadata\n\
+                   r1,acompletion,0.01,gpt-4o,1000,400,50,False,\"{\"\"user_api_key_team_alias\"\": \"\"search\"\"}\"\n\
+                   r2,acompletion,0,gpt-4o,1000,0,50,True,\n";
+        let import = parse_usage_csv(csv).unwrap();
+        assert_eq!(import.format, UsageFormat::LiteLlm);
+        let records = &import.records;
+        assert_eq!(
+            (records[0].input_tokens, records[0].cached_tokens),
+            (600, 400)
+        );
+        assert_eq!(records[0].logged_cost, Some(0.01));
+        assert_eq!(records[0].team.as_deref(), Some("search"));
+        assert!(records[1].proxy_cache_hit);
+    }
+}
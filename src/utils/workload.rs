@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+
+use crate::models::ModelPricing;
+use crate::utils::usage::resolve_model;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestTemplate {
+    pub name: String,
+    pub model: String,
+    pub input_tokens: usize,
+    pub output_tokens: usize,
+    /// Relative share of the monthly traffic; weights need not sum to one.
+    pub weight: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateCost {
+    pub name: String,
+    pub model: String,
+    pub requests: f64,
+    pub cost_per_request: f64,
+    pub monthly_cost: f64,
+    /// Whether the model was found in the catalogue; unpriced rows cost nothing.
+    pub priced: bool,
+    /// Fraction of the blended monthly cost caused by this template.
+    pub cost_share: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WorkloadEstimate {
+    pub rows: Vec<TemplateCost>,
+    pub total_requests: f64,
+    pub total_cost: f64,
+    pub blended_cost_per_request: f64,
+    /// Template models missing from the catalogue, in template order.
+    pub unpriced_models: Vec<String>,
+}
+
+/// Splits `requests_per_month` across the templates by weight and prices each
+/// share with its own model. Model names are matched to the catalogue as in
+/// usage imports; templates whose model is still unknown cost nothing and are
+/// listed in `unpriced_models`.
+pub fn estimate_workload(
+    templates: &[RequestTemplate],
+    prices: &HashMap<String, ModelPricing>,
+    requests_per_month: f64,
+) -> WorkloadEstimate {
+    let total_weight: f64 = templates.iter().map(|t| t.weight.max(0.0)).sum();
+    if total_weight <= 0.0 {
+        return WorkloadEstimate::default();
+    }
+
+    let mut rows: Vec<TemplateCost> = templates
+        .iter()
+        .map(|template| {
+            let requests = requests_per_month * template.weight.max(0.0) / total_weight;
+            let pricing = resolve_model(&template.model, prices).and_then(|key| prices.get(&key));
+            let cost_per_request = pricing
+                .map(|pricing| {
+                    let (input, output) =
+                        pricing.request_cost(template.input_tokens, template.output_tokens);
+                    input + output
+                })
+                .unwrap_or(0.0);
+            TemplateCost {
+                name: template.name.clone(),
+                model: template.model.clone(),
+                requests,
+                cost_per_request,
+                monthly_cost: requests * cost_per_request,
+                priced: pricing.is_some(),
+                cost_share: 0.0,
+            }
+        })
+        .collect();
+
+    let total_cost: f64 = rows.iter().map(|row| row.monthly_cost).sum();
+    if total_cost > 0.0 {
+        for row in &mut rows {
+            row.cost_share = row.monthly_cost / total_cost;
+        }
+    }
+
+    let mut unpriced_models: Vec<String> = Vec::new();
+    for row in rows.iter().filter(|row| !row.priced) {
+        if !unpriced_models.contains(&row.model) {
+            unpriced_models.push(row.model.clone());
+        }
+    }
+
+    WorkloadEstimate {
+        rows,
+        total_requests: requests_per_month,
+        total_cost,
+        blended_cost_per_request: if requests_per_month > 0.0 {
+            total_cost / requests_per_month
+        } else {
+            0.0
+        },
+        unpriced_models,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn template(name: &str, model: &str, weight: f64) -> RequestTemplate {
+        RequestTemplate {
+            name: name.to_string(),
+            model: model.to_string(),
+            input_tokens: 1_000,
+            output_tokens: 100,
+            weight,
+        }
+    }
+
+    fn prices() -> HashMap<String, ModelPricing> {
+        serde_json::from_value(json!({
+            "gpt-4o-mini": {"input_cost_per_token": 1.5e-7, "output_cost_per_token": 6e-7},
+            "claude-sonnet-4-5": {"input_cost_per_token": 3e-6, "output_cost_per_token": 1.5e-5},
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn splits_traffic_by_weight() {
+        let templates = [
+            template("Classify", "gpt-4o-mini", 3.0),
+            template("Chat", "anthropic/claude-sonnet-4-5", 1.0),
+        ];
+        let estimate = estimate_workload(&templates, &prices(), 1_000.0);
+        assert_eq!(estimate.rows[0].requests, 750.0);
+        assert_eq!(estimate.rows[1].requests, 250.0);
+        assert!(estimate.rows.iter().all(|row| row.priced));
+        assert!((estimate.rows[1].cost_per_request - 4.5e-3).abs() < 1e-12);
+        let share: f64 = estimate.rows.iter().map(|row| row.cost_share).sum();
+        assert!((share - 1.0).abs() < 1e-12);
+        assert!((estimate.blended_cost_per_request - estimate.total_cost / 1_000.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn reports_unknown_models() {
+        let templates = [
+            template("A", "my-finetune", 1.0),
+            template("B", "my-finetune", 1.0),
+            template("C", "gpt-4o-mini", 1.0),
+        ];
+        let estimate = estimate_workload(&templates, &prices(), 300.0);
+        assert!(!estimate.rows[0].priced);
+        assert_eq!(estimate.rows[0].monthly_cost, 0.0);
+        assert_eq!(estimate.unpriced_models, ["my-finetune"]);
+    }
+
+    #[test]
+    fn zero_weights_are_empty() {
+        let templates = [template("A", "gpt-4o-mini", 0.0)];
+        assert_eq!(
+            estimate_workload(&templates, &prices(), 100.0),
+            WorkloadEstimate::default()
+        );
+    }
+}